@@ -59,6 +59,8 @@ pub enum AppError {
     RivenError(Arc<riven::RiotApiError>),
     #[error("Ravif: {0}")]
     RavifError(Arc<ravif::Error>),
+    #[error("Image: {0}")]
+    ImageError(Arc<image::ImageError>),
     #[error("Reqwest Error: {0}")]
     ReqwestError(Arc<reqwest::Error>),
     #[error("Serde json Error: {0}")]
@@ -93,6 +95,12 @@ impl From<ravif::Error> for AppError {
     }
 }
 
+impl From<image::ImageError> for AppError {
+    fn from(e: image::ImageError) -> Self {
+        AppError::ImageError(Arc::new(e))
+    }
+}
+
 impl From<reqwest::Error> for AppError {
     fn from(e: reqwest::Error) -> Self {
         AppError::ReqwestError(Arc::new(e))
@@ -107,6 +115,11 @@ pub fn get_css_path() -> PathBuf {
     Path::new("ruche").join("style")
 }
 
+/// PNG copies of the sprites and profile icons, read by the server to draw OpenGraph cards.
+pub fn get_og_assets_path() -> PathBuf {
+    Path::new("ruche").join("og-assets")
+}
+
 pub fn get_temp_path() -> PathBuf {
     Path::new("asset-generation").join("tmp")
 }
@@ -216,11 +229,13 @@ pub async fn convert_to_avif(asset_type: AssetType, modified: bool) -> AppResult
 
     let start = Instant::now();
     let default_assets_path = get_assets_path().join(asset_type.get_path());
+    let og_assets_path = get_og_assets_path().join(asset_type.get_path());
     let temp_path = get_temp_path().join(asset_type.get_path());
 
     if !default_assets_path.exists() {
         tokio::fs::create_dir_all(&default_assets_path).await?;
     }
+    tokio::fs::create_dir_all(&og_assets_path).await?;
 
     let size = asset_type.default_size();
     let mut tasks = vec![];
@@ -237,6 +252,7 @@ pub async fn convert_to_avif(asset_type: AssetType, modified: bool) -> AppResult
                 .to_string();
 
             let image_path = default_assets_path.join(format!("{}.avif", name));
+            let og_image_path = og_assets_path.join(format!("{}.png", name));
 
             // Spawn a task for each file processing
             let task = tokio::spawn(async move {
@@ -267,6 +283,11 @@ pub async fn convert_to_avif(asset_type: AssetType, modified: bool) -> AppResult
                 tokio::fs::write(&image_path, out.avif_file)
                     .await
                     .map_err(|e| format!("Write failed {}: {}", image_path.display(), e))?;
+                let og_png = encode_png(&image)
+                    .map_err(|e| format!("PNG encode failed {}: {e}", path.display()))?;
+                tokio::fs::write(&og_image_path, og_png)
+                    .await
+                    .map_err(|e| format!("Write failed {}: {}", og_image_path.display(), e))?;
                 Ok::<(), String>(())
             });
 
@@ -345,10 +366,12 @@ pub async fn rebuild_css_sprite(asset_type: AssetType, modified: bool) -> AppRes
     let sprite_size = (n_images_sqrt * size.0, n_images_sqrt * size.1);
     let mut sprite = image::RgbaImage::new(sprite_size.0, sprite_size.1);
     let mut css_classes = Vec::new();
+    let mut og_positions = HashMap::new();
     for (i, (name, img)) in all_images.iter().enumerate() {
         let x = (i as u32 % n_images_sqrt) * size.0;
         let y = (i as u32 / n_images_sqrt) * size.1;
         image::imageops::overlay(&mut sprite, img, x as i64, y as i64);
+        og_positions.insert(name.clone(), (x, y));
         css_classes.push(format!(
             r#"
 .{}{{
@@ -379,6 +402,24 @@ pub async fn rebuild_css_sprite(asset_type: AssetType, modified: bool) -> AppRes
         ))?;
 
     tokio::fs::write(sprite_path, result.avif_file).await?;
+
+    let og_assets_path = get_og_assets_path();
+    tokio::fs::create_dir_all(&og_assets_path).await?;
+    tokio::fs::write(
+        og_assets_path.join(format!("{}.png", asset_type.get_path())),
+        encode_png(&sprite)?,
+    )
+    .await?;
+    let og_index = OgSpriteIndex {
+        size,
+        positions: og_positions,
+    };
+    tokio::fs::write(
+        og_assets_path.join(format!("{}.json", asset_type.get_path())),
+        serde_json::to_vec(&og_index)?,
+    )
+    .await?;
+
     let css_path = get_css_path().join(format!("{}.css", asset_type.get_path()));
     tokio::fs::write(css_path, css_classes.join("\n")).await?;
 
@@ -390,6 +431,19 @@ pub async fn rebuild_css_sprite(asset_type: AssetType, modified: bool) -> AppRes
     Ok(())
 }
 
+/// Where each image sits in a PNG sprite of `get_og_assets_path`, keyed by id.
+#[derive(Serialize, Deserialize)]
+pub struct OgSpriteIndex {
+    pub size: (u32, u32),
+    pub positions: HashMap<String, (u32, u32)>,
+}
+
+fn encode_png(image: &image::RgbaImage) -> Result<Vec<u8>, image::ImageError> {
+    let mut bytes = std::io::Cursor::new(Vec::new());
+    image.write_to(&mut bytes, ImageFormat::Png)?;
+    Ok(bytes.into_inner())
+}
+
 pub async fn download_and_save_images(images_to_download: Vec<ImageToDownload>) -> AppResult<()> {
     if images_to_download.is_empty() {
         return Ok(());
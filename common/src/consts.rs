@@ -3,11 +3,14 @@ use crate::AssetType;
 pub mod champion;
 pub mod item;
 pub mod map;
+pub mod match_type;
 pub mod perk;
 pub mod platform_route;
+pub mod position;
 pub mod profile_icon;
 pub mod queue;
 pub mod summoner_spell;
+pub mod tier;
 
 pub trait HasStaticSrcAsset {
     const ASSET_TYPE: AssetType;
@@ -0,0 +1,20 @@
+use bitcode::{Decode, Encode};
+use strum::{AsRefStr, Display, EnumIter, IntoStaticStr};
+
+/// `type` filter accepted by match-v5 `get_match_ids_by_puuid`.
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, Hash, Encode, Decode,
+    EnumIter, AsRefStr, Display, IntoStaticStr
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum MatchType {
+    Ranked,
+    Normal,
+    Tourney,
+    Tutorial,
+}
+
+impl MatchType {
+    #[inline]
+    pub fn api_code(self) -> &'static str { self.into() }
+}
@@ -1,6 +1,6 @@
 use bitcode::{Decode, Encode};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
-use strum::{EnumIter, IntoStaticStr};
+use strum::{EnumIter, IntoEnumIterator, IntoStaticStr};
 
 
 #[repr(u16)]
@@ -105,4 +105,13 @@ impl Perk {
 
     #[inline]
     pub fn label(self) -> &'static str { self.into() }
+
+    /// Selectable runes: no styles (`8x00`) and no stat shards (`5xxx`).
+    #[inline]
+    pub fn options_runes() -> Vec<(u16, &'static str)> {
+        Perk::iter()
+            .filter(|p| p.id() > 8000 && p.id() % 100 != 0)
+            .map(|p| (p.id(), p.label()))
+            .collect()
+    }
 }
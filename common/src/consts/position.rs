@@ -0,0 +1,43 @@
+use bitcode::{Decode, Encode};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use strum::{AsRefStr, Display, EnumIter, EnumString, IntoStaticStr};
+
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, Hash, Encode, Decode,
+    IntoPrimitive, TryFromPrimitive, EnumIter, EnumString, AsRefStr, Display, IntoStaticStr
+)]
+#[repr(u8)]
+pub enum Position {
+    #[strum(serialize = "TOP")]
+    Top = 0,
+    #[strum(serialize = "JUNGLE")]
+    Jungle = 1,
+    #[strum(serialize = "MIDDLE")]
+    Middle = 2,
+    #[strum(serialize = "BOTTOM")]
+    Bottom = 3,
+    #[strum(serialize = "UTILITY")]
+    Utility = 4,
+}
+
+impl Position {
+    #[inline]
+    pub fn id(self) -> u8 { self.into() }
+
+    /// `teamPosition` code stored in `lol_match_participants.team_position`.
+    #[inline]
+    pub fn code(self) -> &'static str { self.into() }
+
+    #[inline]
+    pub fn from_code(code: &str) -> Option<Self> { code.parse().ok() }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Position::Top => "Top",
+            Position::Jungle => "Jungle",
+            Position::Middle => "Mid",
+            Position::Bottom => "ADC",
+            Position::Utility => "Support",
+        }
+    }
+}
@@ -157,6 +157,11 @@ impl Queue {
         Self::try_from(id).unwrap_or(Queue::Custom)
     }
 
+    #[cfg(feature = "ssr")]
+    pub fn to_riven(self) -> riven::consts::Queue {
+        riven::consts::Queue(self.id())
+    }
+
     #[inline]
     pub fn options_all() -> Vec<(u16, &'static str)> {
         Queue::iter().map(|q| (q.id(), q.label())).collect()
@@ -51,6 +51,15 @@ impl SummonerSpell {
             .map(|s| s.id())
             .collect()
     }
+
+    /// `(id, label)` without the `Summoner` prefix.
+    #[inline]
+    pub fn options_all() -> Vec<(u16, &'static str)> {
+        SummonerSpell::iter()
+            .filter(|s| *s != SummonerSpell::UNKNOWN)
+            .map(|s| (s.id(), s.label().trim_start_matches("Summoner").trim_start_matches('_')))
+            .collect()
+    }
 }
 
 pub static SUMMONER_SPELL_OPTIONS: Lazy<Vec<u16>> =
@@ -0,0 +1,73 @@
+use bitcode::{Decode, Encode};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use strum::{AsRefStr, Display, EnumIter, EnumString, IntoStaticStr};
+
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Encode, Decode,
+    IntoPrimitive, TryFromPrimitive, EnumIter, EnumString, AsRefStr, Display, IntoStaticStr
+)]
+#[repr(u8)]
+pub enum Tier {
+    #[strum(serialize = "IRON")]
+    Iron = 0,
+    #[strum(serialize = "BRONZE")]
+    Bronze = 1,
+    #[strum(serialize = "SILVER")]
+    Silver = 2,
+    #[strum(serialize = "GOLD")]
+    Gold = 3,
+    #[strum(serialize = "PLATINUM")]
+    Platinum = 4,
+    #[strum(serialize = "EMERALD")]
+    Emerald = 5,
+    #[strum(serialize = "DIAMOND")]
+    Diamond = 6,
+    #[strum(serialize = "MASTER")]
+    Master = 7,
+    #[strum(serialize = "GRANDMASTER")]
+    Grandmaster = 8,
+    #[strum(serialize = "CHALLENGER")]
+    Challenger = 9,
+}
+
+impl Tier {
+    #[inline]
+    pub fn id(self) -> u8 { self.into() }
+
+    /// Riot API code, e.g. `"EMERALD"`.
+    #[inline]
+    pub fn code(self) -> &'static str { self.into() }
+
+    #[inline]
+    pub fn from_code(code: &str) -> Option<Self> { code.parse().ok() }
+
+    /// Master and above have no divisions.
+    #[inline]
+    pub fn is_apex(self) -> bool { self >= Tier::Master }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Tier::Iron => "Iron",
+            Tier::Bronze => "Bronze",
+            Tier::Silver => "Silver",
+            Tier::Gold => "Gold",
+            Tier::Platinum => "Platinum",
+            Tier::Emerald => "Emerald",
+            Tier::Diamond => "Diamond",
+            Tier::Master => "Master",
+            Tier::Grandmaster => "Grandmaster",
+            Tier::Challenger => "Challenger",
+        }
+    }
+
+    /// `"I"`..`"IV"` from the Riot API to 1..=4.
+    pub fn division_from_code(code: &str) -> Option<u8> {
+        match code {
+            "I" => Some(1),
+            "II" => Some(2),
+            "III" => Some(3),
+            "IV" => Some(4),
+            _ => None,
+        }
+    }
+}
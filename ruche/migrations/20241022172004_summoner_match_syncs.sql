@@ -0,0 +1,9 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS summoner_match_syncs
+(
+    summoner_id  INTEGER    NOT NULL REFERENCES summoners (id) ON DELETE CASCADE,
+    queue_id     INTEGER    NOT NULL DEFAULT 0,
+    match_type   VARCHAR(8) NOT NULL DEFAULT '',
+    synced_until TIMESTAMP  NOT NULL,
+    PRIMARY KEY (summoner_id, queue_id, match_type)
+);
@@ -0,0 +1,32 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS summoner_name_history
+(
+    id          SERIAL PRIMARY KEY,
+    summoner_id INTEGER       NOT NULL REFERENCES summoners (id) ON DELETE CASCADE,
+    platform    platform_type NOT NULL,
+    game_name   VARCHAR(16)   NOT NULL,
+    tag_line    VARCHAR(5)    NOT NULL,
+    changed_at  TIMESTAMP     NOT NULL DEFAULT NOW(),
+    UNIQUE (summoner_id, platform, game_name, tag_line)
+);
+CREATE INDEX idx_summoner_name_history_platform_game_name_tagline ON summoner_name_history (platform, game_name, lower(tag_line));
+
+-- Every path renaming a summoner (bulk updates, upserts, account refresh) goes through an UPDATE,
+-- so the previous Riot ID is recorded here instead of in each query.
+CREATE OR REPLACE FUNCTION record_summoner_name_change() RETURNS TRIGGER AS
+$$
+BEGIN
+    IF OLD.game_name IS DISTINCT FROM NEW.game_name OR OLD.tag_line IS DISTINCT FROM NEW.tag_line THEN
+        INSERT INTO summoner_name_history (summoner_id, platform, game_name, tag_line, changed_at)
+        VALUES (OLD.id, OLD.platform, OLD.game_name, OLD.tag_line, NOW())
+        ON CONFLICT (summoner_id, platform, game_name, tag_line) DO UPDATE SET changed_at = EXCLUDED.changed_at;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_summoners_name_history
+    AFTER UPDATE OF game_name, tag_line
+    ON summoners
+    FOR EACH ROW
+EXECUTE FUNCTION record_summoner_name_change();
@@ -0,0 +1,4 @@
+-- Add migration script here
+-- TOP / JUNGLE / MIDDLE / BOTTOM / UTILITY, NULL for modes without positions
+ALTER TABLE lol_match_participants
+    ADD COLUMN IF NOT EXISTS team_position VARCHAR(7);
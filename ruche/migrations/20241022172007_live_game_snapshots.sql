@@ -0,0 +1,8 @@
+-- Add migration script here
+-- Last `LiveGame` seen by the live cache before the game ended (bitcode), keyed like lol_matches.match_id
+CREATE TABLE IF NOT EXISTS live_game_snapshots
+(
+    match_id    VARCHAR(17) PRIMARY KEY,
+    snapshot    BYTEA       NOT NULL,
+    captured_at TIMESTAMP   NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,11 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS api_keys
+(
+    id                  SERIAL PRIMARY KEY,
+    name                VARCHAR(64)          NOT NULL,
+    -- hex sha256 of the key, the key itself is never stored
+    key_hash            VARCHAR(64)          NOT NULL UNIQUE,
+    requests_per_minute INTEGER DEFAULT 60   NOT NULL,
+    created_at          TIMESTAMP DEFAULT NOW() NOT NULL,
+    revoked_at          TIMESTAMP
+);
@@ -0,0 +1,39 @@
+-- Ranked standing per queue: the apex ladders from league-v4 plus stored summoners
+-- refreshed by UpdateLeaderboardsTask. ladder_score orders every tier on one scale.
+CREATE TABLE IF NOT EXISTS league_entries
+(
+    platform      platform_type NOT NULL,
+    queue_id      INTEGER       NOT NULL,
+    puuid         VARCHAR(78)   NOT NULL,
+    tier          SMALLINT      NOT NULL,
+    division      SMALLINT      NOT NULL,
+    league_points INTEGER       NOT NULL,
+    wins          INTEGER       NOT NULL,
+    losses        INTEGER       NOT NULL,
+    ladder_score  INTEGER       NOT NULL,
+    updated_at    TIMESTAMP     NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (platform, queue_id, puuid)
+);
+
+CREATE INDEX IF NOT EXISTS idx_league_entries_ladder
+    ON league_entries (platform, queue_id, ladder_score DESC);
+CREATE INDEX IF NOT EXISTS idx_league_entries_puuid
+    ON league_entries (puuid);
+
+-- Best stored players per champion in ranked solo/duo, rebuilt on every run.
+CREATE TABLE IF NOT EXISTS champion_leaderboard_entries
+(
+    champion_id   INTEGER NOT NULL,
+    position      INTEGER NOT NULL,
+    summoner_id   INTEGER NOT NULL REFERENCES summoners (id) ON DELETE CASCADE,
+    total_matches INTEGER NOT NULL,
+    total_wins    INTEGER NOT NULL,
+    avg_kills     REAL    NOT NULL,
+    avg_deaths    REAL    NOT NULL,
+    avg_assists   REAL    NOT NULL,
+    PRIMARY KEY (champion_id, position)
+);
+
+-- Stored summoners whose league entries were fetched the longest ago go first.
+ALTER TABLE summoners
+    ADD COLUMN IF NOT EXISTS league_checked_at TIMESTAMP DEFAULT NULL;
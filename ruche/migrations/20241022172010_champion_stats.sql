@@ -0,0 +1,50 @@
+-- Bans of stored matches, filled on ingestion: matches updated before this have none.
+CREATE TABLE IF NOT EXISTS lol_match_bans
+(
+    lol_match_id INTEGER NOT NULL REFERENCES lol_matches (id) ON DELETE CASCADE,
+    team_id      INTEGER NOT NULL,
+    champion_id  INTEGER NOT NULL,
+    PRIMARY KEY (lol_match_id, team_id, champion_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_lol_matches_version_queue
+    ON lol_matches (version, queue_id)
+    WHERE trashed = false;
+
+-- Aggregates rebuilt by UpdateChampionStatsTask for the latest patches.
+-- Matches per patch and queue: pick rate denominator, total_matches_with_bans the ban rate one.
+CREATE TABLE IF NOT EXISTS patch_queue_stats
+(
+    version                 VARCHAR(5) NOT NULL,
+    queue_id                INTEGER    NOT NULL,
+    total_matches           INTEGER    NOT NULL,
+    total_matches_with_bans INTEGER    NOT NULL,
+    PRIMARY KEY (version, queue_id)
+);
+
+CREATE TABLE IF NOT EXISTS champion_patch_stats
+(
+    version       VARCHAR(5) NOT NULL,
+    queue_id      INTEGER    NOT NULL,
+    champion_id   INTEGER    NOT NULL,
+    total_matches INTEGER    NOT NULL,
+    total_wins    INTEGER    NOT NULL,
+    total_bans    INTEGER    NOT NULL,
+    avg_kills     REAL       NOT NULL,
+    avg_deaths    REAL       NOT NULL,
+    avg_assists   REAL       NOT NULL,
+    PRIMARY KEY (version, queue_id, champion_id)
+);
+
+-- Most played item builds, rune pages and summoner spells; kind is ChampionBuildKind.
+CREATE TABLE IF NOT EXISTS champion_patch_builds
+(
+    version       VARCHAR(5) NOT NULL,
+    queue_id      INTEGER    NOT NULL,
+    champion_id   INTEGER    NOT NULL,
+    kind          SMALLINT   NOT NULL,
+    build         INTEGER[]  NOT NULL,
+    total_matches INTEGER    NOT NULL,
+    total_wins    INTEGER    NOT NULL,
+    PRIMARY KEY (version, queue_id, champion_id, kind, build)
+);
@@ -0,0 +1,3 @@
+-- Patch filters compare versions numerically (`BackEndMatchFiltersSearch::push_patch_filter`)
+CREATE INDEX IF NOT EXISTS idx_lol_matches_version_array
+    ON lol_matches ((string_to_array(version, '.')::INT[]));
@@ -0,0 +1,16 @@
+-- Match history sort key (`get_matches::ssr::MATCHES_ORDER_KEY`): `lol_matches.match_end`
+-- copied on ingestion, so one index serves the ordered, keyset-paginated history
+ALTER TABLE lol_match_participants
+    ADD COLUMN IF NOT EXISTS match_end TIMESTAMP;
+
+UPDATE lol_match_participants AS lmp
+SET match_end = COALESCE(lm.match_end, 'epoch'::TIMESTAMP)
+FROM lol_matches AS lm
+WHERE lm.id = lmp.lol_match_id
+  AND lmp.match_end IS NULL;
+
+ALTER TABLE lol_match_participants
+    ALTER COLUMN match_end SET NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_lmp_summoner_match_end
+    ON lol_match_participants (summoner_id, match_end DESC, lol_match_id DESC);
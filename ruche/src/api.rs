@@ -0,0 +1,960 @@
+//! Public read-only JSON API, nested under `/api/v1`.
+//!
+//! Every route but `/openapi.json` needs a key from the `api_keys` table, sent as
+//! `X-Api-Key` or `Authorization: Bearer`. Each key has its own per-minute budget.
+//! The response types below are the stable public shape; they are kept apart from the
+//! bitcode view structs so the site can change without breaking API clients.
+use crate::app::SummonerIdentifier;
+use crate::backend::server_fns::get_champions::ssr::inner_get_champions;
+use crate::backend::server_fns::get_encounter::ssr::find_summoner_puuid_by_id;
+use crate::backend::server_fns::get_encounters::ssr::inner_get_encounters;
+use crate::backend::server_fns::get_live_game::ssr::{add_encounters, get_live_game_data};
+use crate::backend::server_fns::get_match_details::ssr::get_match_participants_details;
+use crate::backend::server_fns::get_matches::ssr::fetch_matches;
+use crate::backend::server_fns::get_summoner::ssr::{
+    resolve_id_by_s_identifier, resolve_summoner_by_s_identifier,
+};
+use crate::backend::ssr::AppError;
+use crate::ssr::AppState;
+use crate::utils::Puuid;
+use crate::views::components::match_details::LolMatchParticipantDetails;
+use crate::views::summoner_page::summoner_champions_page::ChampionStats;
+use crate::views::summoner_page::summoner_encounters_page::SummonerEncountersSummoner;
+use crate::views::summoner_page::summoner_live_page::{LiveGame, LiveGameParticipant};
+use crate::views::summoner_page::summoner_matches_page::{
+    MatchesCursor, MatchesResultInfo, SummonerMatch,
+};
+use crate::views::summoner_page::Summoner;
+use crate::views::{parse_date, BackEndMatchFiltersSearch, Patch};
+use axum::extract::{Path, Query, Request, State};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use common::consts::platform_route::PlatformRoute;
+use dashmap::DashMap;
+use http::{HeaderValue, StatusCode};
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, SecurityScheme};
+use utoipa::{IntoParams, Modify, OpenApi, ToSchema};
+
+const API_KEY_HEADER: &str = "x-api-key";
+
+pub fn router(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route(
+            "/summoners/{platform}/{game_name}/{tag_line}",
+            get(get_summoner),
+        )
+        .route(
+            "/summoners/{platform}/{game_name}/{tag_line}/matches",
+            get(get_matches),
+        )
+        .route(
+            "/summoners/{platform}/{game_name}/{tag_line}/champions",
+            get(get_champions),
+        )
+        .route(
+            "/summoners/{platform}/{game_name}/{tag_line}/encounters",
+            get(get_encounters),
+        )
+        .route(
+            "/summoners/{platform}/{game_name}/{tag_line}/live",
+            get(get_live_game),
+        )
+        .route("/matches/{riot_match_id}", get(get_match))
+        .layer(axum::middleware::from_fn_with_state(state, require_api_key))
+        .route("/openapi.json", get(get_openapi))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Ruche API", version = "1"),
+    paths(
+        get_summoner,
+        get_matches,
+        get_champions,
+        get_encounters,
+        get_live_game,
+        get_match
+    ),
+    components(schemas(
+        ApiError,
+        ApiSummoner,
+        ApiMatches,
+        ApiMatchesSummary,
+        ApiMatch,
+        ApiMatchParticipant,
+        ApiChampionStats,
+        ApiEncounters,
+        ApiEncounter,
+        ApiLiveGame,
+        ApiLiveGameBan,
+        ApiLiveGameParticipant,
+        ApiLiveGameLeague,
+        ApiMatchDetailsParticipant
+    )),
+    modifiers(&ApiKeyScheme),
+    security(("api_key" = []))
+)]
+pub struct ApiDoc;
+
+struct ApiKeyScheme;
+
+impl Modify for ApiKeyScheme {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "api_key",
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-Api-Key"))),
+            );
+        }
+    }
+}
+
+static OPENAPI_JSON: Lazy<String> = Lazy::new(|| {
+    ApiDoc::openapi()
+        .to_pretty_json()
+        .expect("openapi document serializes")
+});
+
+pub async fn get_openapi() -> impl IntoResponse {
+    (
+        [(
+            http::header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        )],
+        OPENAPI_JSON.as_str(),
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Keys and rate limits
+// ---------------------------------------------------------------------------
+
+#[derive(Clone, Copy, sqlx::FromRow)]
+struct ApiKeyRow {
+    id: i32,
+    requests_per_minute: i32,
+}
+
+/// Raw key -> row, `None` for unknown or revoked keys so bad keys don't hit the db either.
+/// A revocation applies once the entry expires, up to a minute later.
+static API_KEYS: Lazy<Cache<String, Option<ApiKeyRow>>> = Lazy::new(|| {
+    Cache::builder()
+        .max_capacity(10_000)
+        .time_to_live(Duration::from_secs(60))
+        .build()
+});
+
+/// Key id -> (minute of the current window, requests made in it).
+static API_KEY_WINDOWS: Lazy<DashMap<i32, (u64, u32)>> = Lazy::new(DashMap::new);
+
+async fn find_api_key(db: &sqlx::PgPool, key: &str) -> Result<Option<ApiKeyRow>, AppError> {
+    Ok(sqlx::query_as::<_, ApiKeyRow>(
+        r#"
+        SELECT id, requests_per_minute
+        FROM api_keys
+        WHERE key_hash = encode(sha256(convert_to($1, 'UTF8')), 'hex')
+          AND revoked_at IS NULL
+        "#,
+    )
+    .bind(key)
+    .fetch_optional(db)
+    .await?)
+}
+
+fn request_api_key(request: &Request) -> Option<String> {
+    let headers = request.headers();
+    headers
+        .get(API_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .or_else(|| {
+            headers
+                .get(http::header::AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer "))
+        })
+        .map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty())
+}
+
+/// Counts the request in the key's fixed one-minute window, `Err` with the seconds
+/// left in the window when it is already used up.
+fn consume_rate_limit(key: ApiKeyRow) -> Result<u32, u64> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let minute = now / 60;
+    let limit = key.requests_per_minute.max(0) as u32;
+    let mut window = API_KEY_WINDOWS.entry(key.id).or_insert((minute, 0));
+    if window.0 != minute {
+        *window = (minute, 0);
+    }
+    if window.1 >= limit {
+        return Err(60 - now % 60);
+    }
+    window.1 += 1;
+    Ok(limit - window.1)
+}
+
+pub async fn require_api_key(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(raw_key) = request_api_key(&request) else {
+        return ApiError::response(StatusCode::UNAUTHORIZED, "missing api key");
+    };
+    let db = state.db.clone();
+    let key = match API_KEYS
+        .try_get_with(
+            raw_key.clone(),
+            async move { find_api_key(&db, &raw_key).await },
+        )
+        .await
+    {
+        Ok(Some(key)) => key,
+        Ok(None) => return ApiError::response(StatusCode::UNAUTHORIZED, "invalid api key"),
+        Err(_) => return ApiError::response(StatusCode::INTERNAL_SERVER_ERROR, "internal error"),
+    };
+    let limit = HeaderValue::from(key.requests_per_minute.max(0));
+    match consume_rate_limit(key) {
+        Ok(remaining) => {
+            let mut response = next.run(request).await;
+            let headers = response.headers_mut();
+            headers.insert("x-ratelimit-limit", limit);
+            headers.insert("x-ratelimit-remaining", HeaderValue::from(remaining));
+            response
+        }
+        Err(retry_after) => {
+            let mut response =
+                ApiError::response(StatusCode::TOO_MANY_REQUESTS, "rate limit exceeded");
+            let headers = response.headers_mut();
+            headers.insert("x-ratelimit-limit", limit);
+            headers.insert("x-ratelimit-remaining", HeaderValue::from(0));
+            headers.insert(http::header::RETRY_AFTER, HeaderValue::from(retry_after));
+            response
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Errors
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiError {
+    pub error: String,
+}
+
+impl ApiError {
+    fn response(status: StatusCode, message: &str) -> Response {
+        (
+            status,
+            Json(ApiError {
+                error: message.to_string(),
+            }),
+        )
+            .into_response()
+    }
+}
+
+pub struct ApiFailure(StatusCode, &'static str);
+
+impl IntoResponse for ApiFailure {
+    fn into_response(self) -> Response {
+        ApiError::response(self.0, self.1)
+    }
+}
+
+impl From<AppError> for ApiFailure {
+    fn from(e: AppError) -> Self {
+        match e {
+            AppError::NotFound => ApiFailure(StatusCode::NOT_FOUND, "not found"),
+            e => {
+                leptos::logging::log!("api error: {}", e);
+                ApiFailure(StatusCode::INTERNAL_SERVER_ERROR, "internal error")
+            }
+        }
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, ApiFailure>;
+
+fn to_identifier(
+    (platform, game_name, tag_line): (String, String, String),
+) -> Result<SummonerIdentifier, ApiFailure> {
+    let platform_route = PlatformRoute::from_code(platform.to_uppercase().as_str())
+        .ok_or(ApiFailure(StatusCode::BAD_REQUEST, "unknown platform"))?;
+    Ok(SummonerIdentifier {
+        game_name,
+        tag_line,
+        platform_route,
+    })
+}
+
+/// `resolve_id_by_s_identifier` reports an unknown summoner as a `CustomError`:
+/// only that case is a 404, a database failure stays a 500.
+async fn resolve_id(db: &sqlx::PgPool, identifier: &SummonerIdentifier) -> Result<i32, ApiFailure> {
+    match resolve_id_by_s_identifier(db, identifier).await {
+        Err(AppError::CustomError(_)) => {
+            Err(ApiFailure(StatusCode::NOT_FOUND, "summoner not found"))
+        }
+        result => Ok(result?),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Filters
+// ---------------------------------------------------------------------------
+
+/// Same filters as the summoner pages.
+#[derive(Deserialize, IntoParams, Default)]
+#[into_params(parameter_in = Query)]
+pub struct ApiFilters {
+    /// Only games on this champion.
+    pub champion_id: Option<u16>,
+    /// Only games of this queue.
+    pub queue_id: Option<u16>,
+    /// `major.minor`, e.g. `14.20`.
+    pub patch: Option<String>,
+    /// `major.minor`, inclusive; turns `patch` into a range start.
+    pub patch_to: Option<String>,
+    /// `YYYY-MM-DD`, inclusive.
+    pub start_date: Option<String>,
+    /// `YYYY-MM-DD`, inclusive.
+    pub end_date: Option<String>,
+    /// Item, rune, summoner spells, teammate and opponent champion, duo summoner id,
+    /// e.g. `i3078.r8112.s4.s14.o157`.
+    pub adv: Option<String>,
+    /// 1-based.
+    pub page: Option<u16>,
+    /// Matches only: `next_cursor` of the previous response, takes over `page`.
+    pub cursor: Option<String>,
+}
+
+impl From<ApiFilters> for BackEndMatchFiltersSearch {
+    fn from(filters: ApiFilters) -> Self {
+        BackEndMatchFiltersSearch {
+            start_date: parse_date(filters.start_date),
+            end_date: parse_date(filters.end_date),
+            champion_id: filters.champion_id,
+            queue_id: filters.queue_id,
+            patch_from: filters.patch.as_deref().and_then(Patch::parse),
+            patch_to: filters.patch_to.as_deref().and_then(Patch::parse),
+            page: filters.page,
+            ..Default::default()
+        }
+        .with_advanced_query(filters.adv.as_deref().unwrap_or_default())
+    }
+}
+
+/// `ApiFilters` plus a name search.
+#[derive(Deserialize, IntoParams, Default)]
+#[into_params(parameter_in = Query)]
+pub struct ApiEncountersFilters {
+    pub champion_id: Option<u16>,
+    pub queue_id: Option<u16>,
+    /// `major.minor`, e.g. `14.20`.
+    pub patch: Option<String>,
+    /// `major.minor`, inclusive.
+    pub patch_to: Option<String>,
+    /// `YYYY-MM-DD`, inclusive.
+    pub start_date: Option<String>,
+    /// `YYYY-MM-DD`, inclusive.
+    pub end_date: Option<String>,
+    /// 1-based.
+    pub page: Option<u16>,
+    /// Only players whose Riot ID contains this text.
+    pub search: Option<String>,
+}
+
+impl ApiEncountersFilters {
+    fn split(self) -> (BackEndMatchFiltersSearch, Option<String>) {
+        let filters = ApiFilters {
+            champion_id: self.champion_id,
+            queue_id: self.queue_id,
+            patch: self.patch,
+            patch_to: self.patch_to,
+            start_date: self.start_date,
+            end_date: self.end_date,
+            adv: None,
+            page: self.page,
+            cursor: None,
+        };
+        (filters.into(), self.search)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Response types
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiSummoner {
+    pub game_name: String,
+    pub tag_line: String,
+    pub platform: String,
+    pub profile_icon_id: u16,
+    pub summoner_level: u16,
+    pub pro_player_slug: Option<String>,
+    /// `[game_name, tag_line]` pairs, most recent first.
+    pub previous_names: Vec<(String, String)>,
+}
+
+impl From<Summoner> for ApiSummoner {
+    fn from(summoner: Summoner) -> Self {
+        ApiSummoner {
+            game_name: summoner.game_name,
+            tag_line: summoner.tag_line,
+            platform: summoner.platform.code().to_string(),
+            profile_icon_id: summoner.profile_icon_id,
+            summoner_level: summoner.summoner_level,
+            pro_player_slug: summoner.pro_slug.map(|slug| slug.to_string()),
+            previous_names: summoner.previous_names,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiMatches {
+    pub total_pages: u16,
+    /// Pass as `cursor` for the next rows, absent on the last ones.
+    pub next_cursor: Option<String>,
+    pub summary: ApiMatchesSummary,
+    pub matches: Vec<ApiMatch>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiMatchesSummary {
+    pub total_matches: u32,
+    pub total_wins: u32,
+    pub avg_kills: f32,
+    pub avg_deaths: f32,
+    pub avg_assists: f32,
+    pub avg_kill_participation: u16,
+}
+
+impl From<MatchesResultInfo> for ApiMatchesSummary {
+    fn from(info: MatchesResultInfo) -> Self {
+        ApiMatchesSummary {
+            total_matches: info.total_matches,
+            total_wins: info.total_wins,
+            avg_kills: info.avg_kills,
+            avg_deaths: info.avg_deaths,
+            avg_assists: info.avg_assists,
+            avg_kill_participation: info.avg_kill_participation,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiMatch {
+    pub riot_match_id: String,
+    pub platform: String,
+    pub queue_id: u16,
+    pub queue: String,
+    /// Seconds.
+    pub match_duration: Option<i32>,
+    pub won: bool,
+    pub champion_id: u16,
+    pub champ_level: u16,
+    pub kills: u16,
+    pub deaths: u16,
+    pub assists: u16,
+    pub kill_participation: u16,
+    pub summoner_spell1_id: u16,
+    pub summoner_spell2_id: u16,
+    pub perk_primary_selection_id: u16,
+    pub perk_sub_style_id: u16,
+    pub items: [u32; 7],
+    pub participants: Vec<ApiMatchParticipant>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiMatchParticipant {
+    pub game_name: String,
+    pub tag_line: String,
+    pub platform: String,
+    pub champion_id: u16,
+    pub team_id: u16,
+}
+
+impl From<SummonerMatch> for ApiMatch {
+    fn from(m: SummonerMatch) -> Self {
+        ApiMatch {
+            riot_match_id: m.riot_match_id.to_string(),
+            platform: m.platform.code().to_string(),
+            queue_id: m.queue.id(),
+            queue: m.queue.label().to_string(),
+            match_duration: m.match_duration,
+            won: m.won,
+            champion_id: m.champion_id,
+            champ_level: m.champ_level,
+            kills: m.kills,
+            deaths: m.deaths,
+            assists: m.assists,
+            kill_participation: m.kill_participation,
+            summoner_spell1_id: m.summoner_spell1_id,
+            summoner_spell2_id: m.summoner_spell2_id,
+            perk_primary_selection_id: m.perk_primary_selection_id,
+            perk_sub_style_id: m.perk_sub_style_id,
+            items: [
+                m.item0_id, m.item1_id, m.item2_id, m.item3_id, m.item4_id, m.item5_id, m.item6_id,
+            ],
+            participants: m
+                .participants
+                .into_iter()
+                .map(|p| ApiMatchParticipant {
+                    game_name: p.game_name,
+                    tag_line: p.tag_line,
+                    platform: p.platform.code().to_string(),
+                    champion_id: p.champion_id,
+                    team_id: p.team_id,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiChampionStats {
+    pub champion_id: u16,
+    pub total_matches: u16,
+    pub total_wins: u16,
+    pub win_rate: f32,
+    pub avg_kills: f32,
+    pub avg_deaths: f32,
+    pub avg_assists: f32,
+    pub avg_kda: f32,
+    pub avg_kill_participation: u16,
+    pub avg_gold_earned: u32,
+    pub avg_cs: u32,
+    pub avg_damage_dealt_to_champions: u32,
+    pub avg_damage_taken: u32,
+    pub total_double_kills: u16,
+    pub total_triple_kills: u16,
+    pub total_quadra_kills: u16,
+    pub total_penta_kills: u16,
+}
+
+impl From<ChampionStats> for ApiChampionStats {
+    fn from(c: ChampionStats) -> Self {
+        ApiChampionStats {
+            champion_id: c.champion_id,
+            total_matches: c.total_matches,
+            total_wins: c.total_wins,
+            win_rate: c.win_rate,
+            avg_kills: c.avg_kills,
+            avg_deaths: c.avg_deaths,
+            avg_assists: c.avg_assists,
+            avg_kda: c.avg_kda,
+            avg_kill_participation: c.avg_kill_participation,
+            avg_gold_earned: c.avg_gold_earned,
+            avg_cs: c.avg_cs,
+            avg_damage_dealt_to_champions: c.avg_damage_dealt_to_champions,
+            avg_damage_taken: c.avg_damage_taken,
+            total_double_kills: c.total_double_kills,
+            total_triple_kills: c.total_triple_kills,
+            total_quadra_kills: c.total_quadra_kills,
+            total_penta_kills: c.total_penta_kills,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiEncounters {
+    pub total_pages: u16,
+    pub encounters: Vec<ApiEncounter>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiEncounter {
+    pub game_name: String,
+    pub tag_line: String,
+    pub platform: String,
+    pub profile_icon_id: u16,
+    pub match_count: u16,
+    pub with_match_count: u16,
+    pub with_win_count: u16,
+    pub vs_win_count: u16,
+}
+
+impl From<SummonerEncountersSummoner> for ApiEncounter {
+    fn from(e: SummonerEncountersSummoner) -> Self {
+        ApiEncounter {
+            game_name: e.game_name,
+            tag_line: e.tag_line,
+            platform: e.platform.code().to_string(),
+            profile_icon_id: e.profile_icon_id,
+            match_count: e.match_count,
+            with_match_count: e.with_match_count,
+            with_win_count: e.with_win_count,
+            vs_win_count: e.vs_win_count,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiLiveGame {
+    pub riot_match_id: String,
+    pub queue_id: u16,
+    pub queue: String,
+    pub phase: String,
+    /// Unix millis, 0 until the loading screen ends.
+    pub game_start_time: i64,
+    pub bans: Vec<ApiLiveGameBan>,
+    pub participants: Vec<ApiLiveGameParticipant>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiLiveGameBan {
+    pub champion_id: u16,
+    pub team_id: u16,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiLiveGameParticipant {
+    pub game_name: String,
+    pub tag_line: String,
+    pub platform: String,
+    pub champion_id: u16,
+    pub team_id: u16,
+    pub summoner_level: u16,
+    pub summoner_spell1_id: u16,
+    pub summoner_spell2_id: u16,
+    pub perk_primary_selection_id: u16,
+    pub perk_sub_style_id: u16,
+    /// Games played together with the requested summoner.
+    pub encounter_count: u16,
+    pub league: Option<ApiLiveGameLeague>,
+    pub main_role: Option<String>,
+    /// Newest first.
+    pub recent_form: Vec<bool>,
+    pub premade_group: Option<u8>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiLiveGameLeague {
+    pub tier: String,
+    /// 1..=4, 0 for apex tiers.
+    pub division: u8,
+    pub league_points: u16,
+    pub wins: u16,
+    pub losses: u16,
+}
+
+impl From<LiveGameParticipant> for ApiLiveGameParticipant {
+    fn from(p: LiveGameParticipant) -> Self {
+        ApiLiveGameParticipant {
+            game_name: p.game_name,
+            tag_line: p.tag_line,
+            platform: p.platform.code().to_string(),
+            champion_id: p.champion_id,
+            team_id: p.team_id,
+            summoner_level: p.summoner_level,
+            summoner_spell1_id: p.summoner_spell1_id,
+            summoner_spell2_id: p.summoner_spell2_id,
+            perk_primary_selection_id: p.perk_primary_selection_id,
+            perk_sub_style_id: p.perk_sub_style_id,
+            encounter_count: p.encounter_count,
+            league: p.league.map(|league| ApiLiveGameLeague {
+                tier: league.tier.code().to_string(),
+                division: league.division,
+                league_points: league.league_points,
+                wins: league.wins,
+                losses: league.losses,
+            }),
+            main_role: p.main_role.map(|role| role.code().to_string()),
+            recent_form: p.recent_form,
+            premade_group: p.premade_group,
+        }
+    }
+}
+
+impl From<LiveGame> for ApiLiveGame {
+    fn from(game: LiveGame) -> Self {
+        ApiLiveGame {
+            riot_match_id: game.game_id.to_string(),
+            queue_id: game.queue.id(),
+            queue: game.queue.label().to_string(),
+            phase: game.phase.label().to_string(),
+            game_start_time: game.game_start_time,
+            bans: game
+                .bans
+                .into_iter()
+                .map(|ban| ApiLiveGameBan {
+                    champion_id: ban.champion_id,
+                    team_id: ban.team_id,
+                })
+                .collect(),
+            participants: game.participants.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiMatchDetailsParticipant {
+    pub game_name: String,
+    pub tag_line: String,
+    pub platform: String,
+    pub summoner_level: u16,
+    pub champion_id: u16,
+    pub champ_level: u16,
+    pub team_id: u16,
+    pub won: bool,
+    pub kills: u16,
+    pub deaths: u16,
+    pub assists: u16,
+    pub kill_participation: u16,
+    pub damage_dealt_to_champions: u32,
+    pub damage_taken: u32,
+    pub gold_earned: u32,
+    pub wards_placed: u16,
+    pub cs: u16,
+    pub summoner_spell1_id: u16,
+    pub summoner_spell2_id: u16,
+    pub perk_primary_style_id: u16,
+    pub perk_sub_style_id: u16,
+    pub perk_primary_selection_id: u16,
+    pub items: [u32; 7],
+}
+
+impl From<LolMatchParticipantDetails> for ApiMatchDetailsParticipant {
+    fn from(p: LolMatchParticipantDetails) -> Self {
+        ApiMatchDetailsParticipant {
+            game_name: p.game_name,
+            tag_line: p.tag_line,
+            platform: p.platform.code().to_string(),
+            summoner_level: p.summoner_level,
+            champion_id: p.champion_id,
+            champ_level: p.champ_level,
+            team_id: p.team_id,
+            won: p.won,
+            kills: p.kills,
+            deaths: p.deaths,
+            assists: p.assists,
+            kill_participation: p.kill_participation,
+            damage_dealt_to_champions: p.damage_dealt_to_champions,
+            damage_taken: p.damage_taken,
+            gold_earned: p.gold_earned,
+            wards_placed: p.wards_placed,
+            cs: p.cs,
+            summoner_spell1_id: p.summoner_spell1_id,
+            summoner_spell2_id: p.summoner_spell2_id,
+            perk_primary_style_id: p.perk_primary_style_id,
+            perk_sub_style_id: p.perk_sub_style_id,
+            perk_primary_selection_id: p.perk_primary_selection_id,
+            items: [
+                p.item0_id, p.item1_id, p.item2_id, p.item3_id, p.item4_id, p.item5_id, p.item6_id,
+            ],
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/summoners/{platform}/{game_name}/{tag_line}",
+    tag = "summoners",
+    params(
+        ("platform" = String, Path, description = "Region code, `EUW` or `EUW1`"),
+        ("game_name" = String, Path),
+        ("tag_line" = String, Path)
+    ),
+    responses(
+        (status = 200, body = ApiSummoner),
+        (status = 404, body = ApiError)
+    )
+)]
+pub async fn get_summoner(
+    State(state): State<AppState>,
+    Path(riot_id): Path<(String, String, String)>,
+) -> ApiResult<ApiSummoner> {
+    let identifier = to_identifier(riot_id)?;
+    resolve_id(&state.db, &identifier).await?;
+    let summoner = resolve_summoner_by_s_identifier(&state.db, &identifier).await?;
+    Ok(Json(summoner.into()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/summoners/{platform}/{game_name}/{tag_line}/matches",
+    tag = "summoners",
+    params(
+        ("platform" = String, Path, description = "Region code, `EUW` or `EUW1`"),
+        ("game_name" = String, Path),
+        ("tag_line" = String, Path),
+        ApiFilters
+    ),
+    responses(
+        (status = 200, body = ApiMatches),
+        (status = 404, body = ApiError)
+    )
+)]
+pub async fn get_matches(
+    State(state): State<AppState>,
+    Path(riot_id): Path<(String, String, String)>,
+    Query(filters): Query<ApiFilters>,
+) -> ApiResult<ApiMatches> {
+    let identifier = to_identifier(riot_id)?;
+    resolve_id(&state.db, &identifier).await?;
+    let cursor = filters
+        .cursor
+        .as_deref()
+        .map(|cursor| {
+            MatchesCursor::from_token(cursor)
+                .ok_or(ApiFailure(StatusCode::BAD_REQUEST, "invalid cursor"))
+        })
+        .transpose()?;
+    let result = fetch_matches(&state.db, identifier, filters.into(), None, cursor).await?;
+    Ok(Json(ApiMatches {
+        total_pages: result.total_pages,
+        next_cursor: result.next_cursor.map(|cursor| cursor.to_token()),
+        summary: result.matches_result_info.into(),
+        matches: result.matches.into_iter().map(Into::into).collect(),
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/summoners/{platform}/{game_name}/{tag_line}/champions",
+    tag = "summoners",
+    params(
+        ("platform" = String, Path, description = "Region code, `EUW` or `EUW1`"),
+        ("game_name" = String, Path),
+        ("tag_line" = String, Path),
+        ApiFilters
+    ),
+    responses(
+        (status = 200, body = Vec<ApiChampionStats>),
+        (status = 404, body = ApiError)
+    )
+)]
+pub async fn get_champions(
+    State(state): State<AppState>,
+    Path(riot_id): Path<(String, String, String)>,
+    Query(filters): Query<ApiFilters>,
+) -> ApiResult<Vec<ApiChampionStats>> {
+    let identifier = to_identifier(riot_id)?;
+    let summoner_id = resolve_id(&state.db, &identifier).await?;
+    let champions = inner_get_champions(&state.db, summoner_id, filters.into()).await?;
+    Ok(Json(champions.into_iter().map(Into::into).collect()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/summoners/{platform}/{game_name}/{tag_line}/encounters",
+    tag = "summoners",
+    params(
+        ("platform" = String, Path, description = "Region code, `EUW` or `EUW1`"),
+        ("game_name" = String, Path),
+        ("tag_line" = String, Path),
+        ApiEncountersFilters
+    ),
+    responses(
+        (status = 200, body = ApiEncounters),
+        (status = 404, body = ApiError)
+    )
+)]
+pub async fn get_encounters(
+    State(state): State<AppState>,
+    Path(riot_id): Path<(String, String, String)>,
+    Query(query): Query<ApiEncountersFilters>,
+) -> ApiResult<ApiEncounters> {
+    let identifier = to_identifier(riot_id)?;
+    let summoner_id = resolve_id(&state.db, &identifier).await?;
+    let (filters, search) = query.split();
+    let result = inner_get_encounters(&state.db, summoner_id, filters, search).await?;
+    Ok(Json(ApiEncounters {
+        total_pages: result.total_pages,
+        encounters: result.encounters.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// Served from the live game cache when the summoner is already tracked.
+#[utoipa::path(
+    get,
+    path = "/api/v1/summoners/{platform}/{game_name}/{tag_line}/live",
+    tag = "summoners",
+    params(
+        ("platform" = String, Path, description = "Region code, `EUW` or `EUW1`"),
+        ("game_name" = String, Path),
+        ("tag_line" = String, Path)
+    ),
+    responses(
+        (status = 200, body = Option<ApiLiveGame>, description = "`null` when not in game"),
+        (status = 404, body = ApiError)
+    )
+)]
+pub async fn get_live_game(
+    State(state): State<AppState>,
+    Path(riot_id): Path<(String, String, String)>,
+) -> ApiResult<Option<ApiLiveGame>> {
+    let identifier = to_identifier(riot_id)?;
+    let summoner_id = resolve_id(&state.db, &identifier).await?;
+    let live_game = match state.live_game_cache.get_game_data(summoner_id).await {
+        Some(cached) => Some(cached.as_ref().clone()),
+        None => {
+            let puuid = Puuid::new(
+                find_summoner_puuid_by_id(&state.db, summoner_id)
+                    .await?
+                    .as_str(),
+            );
+            match get_live_game_data(&state.db, &state.riot_api, puuid, identifier.platform_route)
+                .await?
+            {
+                Some((summoner_ids, live_game)) => {
+                    state
+                        .live_game_cache
+                        .set_game_data(live_game.game_id, summoner_ids, live_game.clone())
+                        .await;
+                    Some(live_game)
+                }
+                None => None,
+            }
+        }
+    };
+    Ok(Json(match live_game {
+        Some(live_game) => Some(
+            add_encounters(&state.db, live_game, summoner_id)
+                .await?
+                .into(),
+        ),
+        None => None,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/matches/{riot_match_id}",
+    tag = "matches",
+    params(("riot_match_id" = String, Path, description = "Riot match id, e.g. `EUW1_7000000000`")),
+    responses(
+        (status = 200, body = Vec<ApiMatchDetailsParticipant>),
+        (status = 404, body = ApiError)
+    )
+)]
+pub async fn get_match(
+    State(state): State<AppState>,
+    Path(riot_match_id): Path<String>,
+) -> ApiResult<Vec<ApiMatchDetailsParticipant>> {
+    let match_id = sqlx::query_scalar::<_, i32>(
+        "SELECT id FROM lol_matches WHERE match_id = $1 AND updated AND NOT trashed",
+    )
+    .bind(riot_match_id.as_str())
+    .fetch_optional(&state.db)
+    .await
+    .map_err(AppError::from)?
+    .ok_or(AppError::NotFound)?;
+    let participants = get_match_participants_details(&state.db, match_id, None).await?;
+    Ok(Json(participants.into_iter().map(Into::into).collect()))
+}
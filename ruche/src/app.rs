@@ -1,3 +1,7 @@
+use crate::views::champion_stats_page::{ChampionStatsRoute, ChampionsStatsRoute};
+use crate::views::compare_page::CompareRoute;
+use crate::views::leaderboard_page::{ChampionLeaderboardRoute, LeaderboardRoute};
+use crate::views::match_page::MatchPageRoute;
 use crate::views::summoner_page::SummonerPageRoute;
 use crate::views::summoner_search_page::SummonerSearchPage;
 use bitcode::{Decode, Encode};
@@ -108,6 +112,24 @@ pub fn App() -> impl IntoView {
                           view={Lazy::<SummonerEncounterRoute>::new()}
                         />
                       </ParentRoute>
+                    <Route
+                        path=path!("matches/:platform_route/:riot_match_id")
+                        view={Lazy::<MatchPageRoute>::new()}
+                    />
+                    <Route path=path!("compare") view={Lazy::<CompareRoute>::new()} />
+                    <Route path=path!("champions") view={Lazy::<ChampionsStatsRoute>::new()} />
+                    <Route
+                        path=path!("champions/:champion_id")
+                        view={Lazy::<ChampionStatsRoute>::new()}
+                    />
+                    <Route
+                        path=path!("leaderboards/champion/:champion_id")
+                        view={Lazy::<ChampionLeaderboardRoute>::new()}
+                    />
+                    <Route
+                        path=path!("leaderboards/:platform_route")
+                        view={Lazy::<LeaderboardRoute>::new()}
+                    />
 
                 </Routes>
             </main>
@@ -115,6 +137,12 @@ pub fn App() -> impl IntoView {
     }
 }
 
+#[derive(Params, Debug, PartialEq, Clone)]
+pub struct MatchRouteParams {
+    pub platform_route: Option<PlatformRoute>,
+    pub riot_match_id: Option<String>,
+}
+
 #[derive(Params, Debug, PartialEq, Clone)]
 pub struct SummonerRouteParams {
     pub platform_route: Option<PlatformRoute>,
@@ -202,6 +230,33 @@ pub fn to_encounter_identifier_memo(
     })
 }
 
+#[derive(Params, Debug, PartialEq, Clone)]
+pub struct LeaderboardRouteParams {
+    pub platform_route: Option<PlatformRoute>,
+}
+
+#[derive(Params, Debug, PartialEq, Clone)]
+pub struct ChampionLeaderboardRouteParams {
+    pub champion_id: Option<u16>,
+}
+
+#[derive(Params, Debug, PartialEq, Clone)]
+pub struct ChampionStatsRouteParams {
+    pub champion_id: Option<u16>,
+}
+
+#[derive(Params, Debug, PartialEq, Clone)]
+pub struct CompareQuery {
+    /// Comma separated `PLATFORM:slug`, see `compare_url`.
+    pub players: Option<String>,
+}
+
+#[derive(Params, Debug, PartialEq, Clone)]
+pub struct SummonerMatchesQuery {
+    /// Advanced match filters, see `BackEndMatchFiltersSearch::advanced_to_query`.
+    pub adv: Option<String>,
+}
+
 #[derive(Params, Debug, PartialEq, Clone)]
 pub struct SummonerSearchQuery {
     pub game_name: Option<String>,
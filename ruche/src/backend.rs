@@ -5,9 +5,17 @@ pub mod server_fns;
 #[cfg(feature = "ssr")]
 pub mod tasks;
 
+#[cfg(feature = "ssr")]
+pub mod ingestion_queue;
 #[cfg(feature = "ssr")]
 pub mod live_game_cache;
 #[cfg(feature = "ssr")]
+pub mod post_game_queue;
+#[cfg(feature = "ssr")]
+pub mod riot_budget;
+#[cfg(feature = "ssr")]
+pub mod riot_cache;
+#[cfg(feature = "ssr")]
 pub mod task_director;
 
 pub type ServerResult<T> = Result<T, ServerFnError>;
@@ -0,0 +1,115 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Mutex;
+
+/// Number of matches taken from one requester per round, so the first page
+/// of every summoner is ingested before anyone's older history.
+pub const INGESTION_PAGE_SIZE: usize = 20;
+
+/// Matches requested through the update button, processed before the global
+/// `updated = false` backlog and scheduled round-robin across summoners.
+#[derive(Default)]
+pub struct IngestionQueue {
+    inner: Mutex<IngestionQueueInner>,
+}
+
+#[derive(Default)]
+struct IngestionQueueInner {
+    order: VecDeque<i32>,
+    requests: HashMap<i32, IngestionRequest>,
+}
+
+struct IngestionRequest {
+    match_ids: VecDeque<i32>,
+    queued: HashSet<i32>,
+    done: u32,
+    total: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IngestionProgress {
+    pub done: u32,
+    pub total: u32,
+}
+
+impl IngestionProgress {
+    pub fn is_finished(&self) -> bool {
+        self.done >= self.total
+    }
+}
+
+impl IngestionQueue {
+    /// Queues `match_ids` (newest first) for `summoner_id`; returns the progress after the push.
+    pub fn push(&self, summoner_id: i32, match_ids: Vec<i32>) -> IngestionProgress {
+        let mut inner = self.inner.lock().unwrap();
+        let inner = &mut *inner;
+        if !inner.requests.contains_key(&summoner_id) {
+            inner.order.push_back(summoner_id);
+        }
+        let request = inner
+            .requests
+            .entry(summoner_id)
+            .or_insert_with(|| IngestionRequest {
+                match_ids: VecDeque::new(),
+                queued: HashSet::new(),
+                done: 0,
+                total: 0,
+            });
+        for id in match_ids {
+            if request.queued.insert(id) {
+                request.match_ids.push_back(id);
+                request.total += 1;
+            }
+        }
+        IngestionProgress {
+            done: request.done,
+            total: request.total,
+        }
+    }
+
+    /// Takes up to `limit` match ids, at most `INGESTION_PAGE_SIZE` per summoner per round.
+    pub fn next_batch(&self, limit: usize) -> Vec<(i32, Vec<i32>)> {
+        let mut inner = self.inner.lock().unwrap();
+        let inner = &mut *inner;
+        let mut batch: Vec<(i32, Vec<i32>)> = Vec::new();
+        let mut taken = 0;
+        let mut visited = 0;
+        while taken < limit && visited < inner.order.len() {
+            let Some(summoner_id) = inner.order.pop_front() else {
+                break;
+            };
+            visited += 1;
+            if let Some(request) = inner.requests.get_mut(&summoner_id) {
+                let take = INGESTION_PAGE_SIZE
+                    .min(limit - taken)
+                    .min(request.match_ids.len());
+                let ids = request.match_ids.drain(..take).collect::<Vec<_>>();
+                taken += ids.len();
+                if !ids.is_empty() {
+                    batch.push((summoner_id, ids));
+                }
+            }
+            inner.order.push_back(summoner_id);
+        }
+        batch
+    }
+
+    /// Records `count` processed matches for `summoner_id`, dropping the request once finished.
+    pub fn complete(&self, summoner_id: i32, count: usize) -> Option<IngestionProgress> {
+        let mut inner = self.inner.lock().unwrap();
+        let request = inner.requests.get_mut(&summoner_id)?;
+        request.done = (request.done + count as u32).min(request.total);
+        let progress = IngestionProgress {
+            done: request.done,
+            total: request.total,
+        };
+        if progress.is_finished() && request.match_ids.is_empty() {
+            inner.requests.remove(&summoner_id);
+            inner.order.retain(|id| *id != summoner_id);
+        }
+        Some(progress)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.lock().unwrap().requests.is_empty()
+    }
+}
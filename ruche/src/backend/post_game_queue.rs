@@ -0,0 +1,70 @@
+use crate::utils::RiotMatchId;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio::time::{Duration, Instant};
+
+/// Riot usually publishes a match a few minutes after the nexus falls.
+const FIRST_CHECK_DELAY: Duration = Duration::from_secs(60);
+const RETRY_DELAY: Duration = Duration::from_secs(60);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(10 * 60);
+/// Remakes and custom games are never published; give up after about an hour.
+const MAX_ATTEMPTS: u32 = 12;
+
+/// Live games that just ended for a tracked summoner, waiting for match-v5 to
+/// publish them so they can be queued for ingestion.
+#[derive(Default)]
+pub struct PostGameQueue {
+    pending: Mutex<HashMap<RiotMatchId, PendingPostGame>>,
+}
+
+struct PendingPostGame {
+    summoner_ids: Vec<i32>,
+    attempts: u32,
+    next_check: Instant,
+}
+
+impl PostGameQueue {
+    /// Waits for `match_id` on behalf of `summoner_id`; several summoners of the
+    /// same game share one entry.
+    pub fn schedule(&self, match_id: RiotMatchId, summoner_id: i32) {
+        let mut pending = self.pending.lock().unwrap();
+        let entry = pending.entry(match_id).or_insert_with(|| PendingPostGame {
+            summoner_ids: Vec::new(),
+            attempts: 0,
+            next_check: Instant::now() + FIRST_CHECK_DELAY,
+        });
+        if !entry.summoner_ids.contains(&summoner_id) {
+            entry.summoner_ids.push(summoner_id);
+        }
+    }
+
+    /// Games whose next check is due, with the summoners waiting on them.
+    pub fn due(&self) -> Vec<(RiotMatchId, Vec<i32>)> {
+        let now = Instant::now();
+        self.pending
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, game)| game.next_check <= now)
+            .map(|(match_id, game)| (*match_id, game.summoner_ids.clone()))
+            .collect()
+    }
+
+    /// Not published yet: backs off linearly, dropping the game after `MAX_ATTEMPTS`.
+    pub fn retry(&self, match_id: RiotMatchId) {
+        let mut pending = self.pending.lock().unwrap();
+        let Some(game) = pending.get_mut(&match_id) else {
+            return;
+        };
+        game.attempts += 1;
+        if game.attempts >= MAX_ATTEMPTS {
+            pending.remove(&match_id);
+            return;
+        }
+        game.next_check = Instant::now() + (RETRY_DELAY * game.attempts).min(MAX_RETRY_DELAY);
+    }
+
+    pub fn finish(&self, match_id: RiotMatchId) {
+        self.pending.lock().unwrap().remove(&match_id);
+    }
+}
@@ -0,0 +1,170 @@
+use dashmap::DashMap;
+use serde::Serialize;
+use std::fmt::Display;
+use std::sync::{Arc, Mutex};
+use tokio::sync::watch;
+use tokio::time::{Duration, Instant};
+
+/// Who is waiting on a Riot call: a user in front of a page or a background task.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RiotCallPriority {
+    Interactive,
+    Background,
+}
+
+/// Priority of one call several callers wait on. It only goes up: a user joining a
+/// background fetch must not wait behind the background share.
+#[derive(Clone)]
+pub struct SharedPriority(Arc<watch::Sender<RiotCallPriority>>);
+
+impl SharedPriority {
+    pub fn new(priority: RiotCallPriority) -> Self {
+        Self(Arc::new(watch::Sender::new(priority)))
+    }
+
+    pub fn raise(&self, priority: RiotCallPriority) {
+        if priority == RiotCallPriority::Interactive {
+            self.0
+                .send_if_modified(|current| std::mem::replace(current, priority) != priority);
+        }
+    }
+
+    pub fn same(&self, other: &SharedPriority) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Per-route application limit budgeting in front of the shared `RiotApi`.
+///
+/// Background calls may only use `1 - interactive_share` of each window, so a
+/// backfill never starves searches and live lookups. Interactive calls can use
+/// the whole window.
+pub struct RiotBudget {
+    limit: u32,
+    window: Duration,
+    interactive_share: f64,
+    routes: DashMap<String, Arc<Mutex<RouteWindow>>>,
+}
+
+struct RouteWindow {
+    started_at: Instant,
+    interactive: u32,
+    background: u32,
+}
+
+impl Default for RouteWindow {
+    fn default() -> Self {
+        Self {
+            started_at: Instant::now(),
+            interactive: 0,
+            background: 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RouteBudgetUsage {
+    pub route: String,
+    pub interactive_used: u32,
+    pub background_used: u32,
+    pub background_limit: u32,
+    pub limit: u32,
+    pub resets_in_ms: u64,
+}
+
+impl RiotBudget {
+    pub fn new(limit: u32, window: Duration, interactive_share: f64) -> Self {
+        Self {
+            limit,
+            window,
+            interactive_share: interactive_share.clamp(0.0, 1.0),
+            routes: DashMap::new(),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let limit = dotenv::var("RIOT_API_RATE_LIMIT")
+            .unwrap_or_else(|_| "100".to_string())
+            .parse::<u32>()
+            .unwrap_or(100);
+        let window_secs = dotenv::var("RIOT_API_RATE_WINDOW_SECS")
+            .unwrap_or_else(|_| "120".to_string())
+            .parse::<u64>()
+            .unwrap_or(120);
+        let interactive_share = dotenv::var("RIOT_API_INTERACTIVE_SHARE")
+            .unwrap_or_else(|_| "0.3".to_string())
+            .parse::<f64>()
+            .unwrap_or(0.3);
+        Self::new(limit, Duration::from_secs(window_secs), interactive_share)
+    }
+
+    fn background_limit(&self) -> u32 {
+        (self.limit as f64 * (1.0 - self.interactive_share)).floor() as u32
+    }
+
+    /// Waits until `route` has budget left for a call of `priority`, then consumes one slot.
+    pub async fn acquire(&self, route: impl Display, priority: RiotCallPriority) {
+        self.acquire_shared(route, &SharedPriority::new(priority)).await
+    }
+
+    /// `acquire` for a coalesced call: a raised priority applies at once, even mid-wait.
+    pub async fn acquire_shared(&self, route: impl Display, priority: &SharedPriority) {
+        let window = self
+            .routes
+            .entry(route.to_string())
+            .or_default()
+            .clone();
+        let mut raised = priority.0.subscribe();
+        loop {
+            let priority = *raised.borrow_and_update();
+            let wait = {
+                let mut w = window.lock().unwrap();
+                if w.started_at.elapsed() >= self.window {
+                    *w = RouteWindow::default();
+                }
+                let used = w.interactive + w.background;
+                let allowed = match priority {
+                    RiotCallPriority::Interactive => used < self.limit,
+                    RiotCallPriority::Background => {
+                        used < self.limit && w.background < self.background_limit()
+                    }
+                };
+                if allowed {
+                    match priority {
+                        RiotCallPriority::Interactive => w.interactive += 1,
+                        RiotCallPriority::Background => w.background += 1,
+                    }
+                    return;
+                }
+                self.window.saturating_sub(w.started_at.elapsed())
+            };
+            tokio::select! {
+                _ = tokio::time::sleep(wait.max(Duration::from_millis(10))) => {}
+                _ = raised.changed() => {}
+            }
+        }
+    }
+
+    pub fn usage(&self) -> Vec<RouteBudgetUsage> {
+        let background_limit = self.background_limit();
+        let mut usage = self
+            .routes
+            .iter()
+            .map(|entry| {
+                let w = entry.value().lock().unwrap();
+                let expired = w.started_at.elapsed() >= self.window;
+                RouteBudgetUsage {
+                    route: entry.key().clone(),
+                    interactive_used: if expired { 0 } else { w.interactive },
+                    background_used: if expired { 0 } else { w.background },
+                    background_limit,
+                    limit: self.limit,
+                    resets_in_ms: self.window.saturating_sub(w.started_at.elapsed()).as_millis()
+                        as u64,
+                }
+            })
+            .collect::<Vec<_>>();
+        usage.sort_by(|a, b| a.route.cmp(&b.route));
+        usage
+    }
+}
@@ -0,0 +1,273 @@
+use crate::backend::riot_budget::{RiotCallPriority, SharedPriority};
+use crate::backend::ssr::{AppError, AppResult};
+use crate::ssr::RIOT_BUDGET;
+use dashmap::DashMap;
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+use riven::consts::Champion;
+use riven::consts::{PlatformRoute, QueueType, RegionalRoute, Tier};
+use riven::models::account_v1::Account;
+use riven::models::champion_mastery_v4::ChampionMastery;
+use riven::models::league_v4::{LeagueEntry, LeagueList};
+use riven::models::match_v5::Timeline;
+use riven::models::spectator_v5::CurrentGameInfo;
+use riven::models::summoner_v4::Summoner;
+use riven::RiotApi;
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::Arc;
+use std::time::Duration;
+
+// Single-flight + TTL caches in front of the Riot endpoints several code paths share.
+// Concurrent misses on the same key wait on one request, only that request spends
+// `RIOT_BUDGET` at the highest priority among them, and errors are never cached.
+
+const ACCOUNT_BY_PUUID_TTL: Duration = Duration::from_secs(5 * 60);
+const ACCOUNT_BY_RIOT_ID_TTL: Duration = Duration::from_secs(60);
+const SUMMONER_BY_PUUID_TTL: Duration = Duration::from_secs(60);
+const TIMELINE_TTL: Duration = Duration::from_secs(10 * 60);
+const CURRENT_GAME_TTL: Duration = Duration::from_secs(10);
+const LEAGUE_ENTRIES_TTL: Duration = Duration::from_secs(5 * 60);
+const CHAMPION_MASTERY_TTL: Duration = Duration::from_secs(10 * 60);
+const APEX_LEAGUE_TTL: Duration = Duration::from_secs(10 * 60);
+
+struct RiotCacheEntries<K, V> {
+    values: Cache<K, Arc<V>>,
+    // priority of the fetch in progress per key, raised by the callers joining it
+    pending: DashMap<K, SharedPriority>,
+}
+
+type RiotCache<K, V> = Lazy<RiotCacheEntries<K, V>>;
+
+fn build_cache<K, V>(max_capacity: u64, ttl: Duration) -> RiotCacheEntries<K, V>
+where
+    K: Hash + Eq + Send + Sync + 'static,
+    V: Send + Sync + 'static,
+{
+    RiotCacheEntries {
+        values: Cache::builder()
+            .max_capacity(max_capacity)
+            .time_to_live(ttl)
+            .build(),
+        pending: DashMap::new(),
+    }
+}
+
+static ACCOUNT_BY_PUUID: RiotCache<(RegionalRoute, String), Account> =
+    Lazy::new(|| build_cache(50_000, ACCOUNT_BY_PUUID_TTL));
+static ACCOUNT_BY_RIOT_ID: RiotCache<(RegionalRoute, String, String), Option<Account>> =
+    Lazy::new(|| build_cache(10_000, ACCOUNT_BY_RIOT_ID_TTL));
+static SUMMONER_BY_PUUID: RiotCache<(PlatformRoute, String), Option<Summoner>> =
+    Lazy::new(|| build_cache(10_000, SUMMONER_BY_PUUID_TTL));
+static TIMELINE: RiotCache<(RegionalRoute, String), Option<Timeline>> =
+    Lazy::new(|| build_cache(200, TIMELINE_TTL));
+static CURRENT_GAME_BY_PUUID: RiotCache<(PlatformRoute, String), Option<CurrentGameInfo>> =
+    Lazy::new(|| build_cache(50_000, CURRENT_GAME_TTL));
+static LEAGUE_ENTRIES_BY_PUUID: RiotCache<(PlatformRoute, String), Vec<LeagueEntry>> =
+    Lazy::new(|| build_cache(50_000, LEAGUE_ENTRIES_TTL));
+static CHAMPION_MASTERY_BY_PUUID: RiotCache<
+    (PlatformRoute, String, Champion),
+    Option<ChampionMastery>,
+> = Lazy::new(|| build_cache(50_000, CHAMPION_MASTERY_TTL));
+static APEX_LEAGUE: RiotCache<(PlatformRoute, QueueType, Tier), LeagueList> =
+    Lazy::new(|| build_cache(200, APEX_LEAGUE_TTL));
+
+async fn get_or_fetch<K, V, F, Fut>(
+    cache: &RiotCacheEntries<K, V>,
+    key: K,
+    budget_route: impl std::fmt::Display,
+    priority: RiotCallPriority,
+    fetch: F,
+) -> AppResult<V>
+where
+    K: Hash + Eq + Send + Sync + Clone + 'static,
+    V: Clone + Send + Sync + 'static,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<V, riven::RiotApiError>>,
+{
+    let shared_priority = cache
+        .pending
+        .entry(key.clone())
+        .or_insert_with(|| SharedPriority::new(priority))
+        .clone();
+    shared_priority.raise(priority);
+    let fetch_priority = shared_priority.clone();
+    let result = cache
+        .values
+        .try_get_with(key.clone(), async move {
+            RIOT_BUDGET
+                .acquire_shared(budget_route, &fetch_priority)
+                .await;
+            fetch().await.map(Arc::new).map_err(AppError::from)
+        })
+        .await;
+    cache
+        .pending
+        .remove_if(&key, |_, pending| pending.same(&shared_priority));
+    result
+        .map(|value| (*value).clone())
+        .map_err(|e| (*e).clone())
+}
+
+pub async fn account_by_puuid(
+    api: &RiotApi,
+    route: RegionalRoute,
+    puuid: &str,
+    priority: RiotCallPriority,
+) -> AppResult<Account> {
+    get_or_fetch(
+        &ACCOUNT_BY_PUUID,
+        (route, puuid.to_string()),
+        route,
+        priority,
+        || api.account_v1().get_by_puuid(route, puuid),
+    )
+    .await
+}
+
+pub async fn account_by_riot_id(
+    api: &RiotApi,
+    route: RegionalRoute,
+    game_name: &str,
+    tag_line: &str,
+    priority: RiotCallPriority,
+) -> AppResult<Option<Account>> {
+    get_or_fetch(
+        &ACCOUNT_BY_RIOT_ID,
+        (route, game_name.to_lowercase(), tag_line.to_lowercase()),
+        route,
+        priority,
+        || api.account_v1().get_by_riot_id(route, game_name, tag_line),
+    )
+    .await
+}
+
+pub async fn summoner_by_puuid(
+    api: &RiotApi,
+    route: PlatformRoute,
+    puuid: &str,
+    priority: RiotCallPriority,
+) -> AppResult<Option<Summoner>> {
+    get_or_fetch(
+        &SUMMONER_BY_PUUID,
+        (route, puuid.to_string()),
+        route,
+        priority,
+        || api.summoner_v4().get_by_puuid(route, puuid),
+    )
+    .await
+}
+
+pub async fn timeline_by_match_id(
+    api: &RiotApi,
+    route: RegionalRoute,
+    match_id: &str,
+    priority: RiotCallPriority,
+) -> AppResult<Option<Timeline>> {
+    get_or_fetch(
+        &TIMELINE,
+        (route, match_id.to_string()),
+        route,
+        priority,
+        || api.match_v5().get_timeline(route, match_id),
+    )
+    .await
+}
+
+pub async fn current_game_by_puuid(
+    api: &RiotApi,
+    route: PlatformRoute,
+    puuid: &str,
+    priority: RiotCallPriority,
+) -> AppResult<Option<CurrentGameInfo>> {
+    get_or_fetch(
+        &CURRENT_GAME_BY_PUUID,
+        (route, puuid.to_string()),
+        route,
+        priority,
+        || {
+            api.spectator_v5()
+                .get_current_game_info_by_puuid(route, puuid)
+        },
+    )
+    .await
+}
+
+pub async fn league_entries_by_puuid(
+    api: &RiotApi,
+    route: PlatformRoute,
+    puuid: &str,
+    priority: RiotCallPriority,
+) -> AppResult<Vec<LeagueEntry>> {
+    get_or_fetch(
+        &LEAGUE_ENTRIES_BY_PUUID,
+        (route, puuid.to_string()),
+        route,
+        priority,
+        || api.league_v4().get_league_entries_by_puuid(route, puuid),
+    )
+    .await
+}
+
+pub async fn champion_mastery_by_puuid(
+    api: &RiotApi,
+    route: PlatformRoute,
+    puuid: &str,
+    champion: Champion,
+    priority: RiotCallPriority,
+) -> AppResult<Option<ChampionMastery>> {
+    get_or_fetch(
+        &CHAMPION_MASTERY_BY_PUUID,
+        (route, puuid.to_string(), champion),
+        route,
+        priority,
+        || {
+            api.champion_mastery_v4()
+                .get_champion_mastery_by_puuid(route, puuid, champion)
+        },
+    )
+    .await
+}
+
+/// Drops cached account/summoner data for `puuid`, used before an explicit refresh.
+pub async fn invalidate_summoner(regional: RegionalRoute, platform: PlatformRoute, puuid: &str) {
+    ACCOUNT_BY_PUUID
+        .values
+        .invalidate(&(regional, puuid.to_string()))
+        .await;
+    SUMMONER_BY_PUUID
+        .values
+        .invalidate(&(platform, puuid.to_string()))
+        .await;
+}
+
+/// Master, Grandmaster or Challenger ladder of `queue`; other tiers are rejected.
+pub async fn apex_league(
+    api: &RiotApi,
+    route: PlatformRoute,
+    queue: QueueType,
+    tier: Tier,
+    priority: RiotCallPriority,
+) -> AppResult<LeagueList> {
+    if !matches!(tier, Tier::MASTER | Tier::GRANDMASTER | Tier::CHALLENGER) {
+        return Err(AppError::CustomError(format!(
+            "{} is not an apex tier",
+            tier
+        )));
+    }
+    get_or_fetch(
+        &APEX_LEAGUE,
+        (route, queue.clone(), tier),
+        route,
+        priority,
+        || async move {
+            let league_v4 = api.league_v4();
+            match tier {
+                Tier::CHALLENGER => league_v4.get_challenger_league(route, queue).await,
+                Tier::GRANDMASTER => league_v4.get_grandmaster_league(route, queue).await,
+                _ => league_v4.get_master_league(route, queue).await,
+            }
+        },
+    )
+    .await
+}
@@ -1,9 +1,18 @@
+pub mod get_champion_leaderboard;
 pub mod get_champions;
+pub mod get_compare;
 pub mod get_encounter;
 pub mod get_encounters;
+pub mod get_leaderboard;
 pub mod get_live_game;
+pub mod get_live_game_snapshot;
+pub mod get_match;
 pub mod get_match_details;
+pub mod get_match_patches;
 pub mod get_matches;
+pub mod get_patch_champion;
+pub mod get_patch_champions;
 pub mod get_summoner;
 pub mod search_summoner;
+pub mod update_sse_subscriptions;
 pub mod update_summoner;
@@ -0,0 +1,179 @@
+use crate::views::leaderboard_page::ChampionLeaderboardPage;
+use leptos::prelude::*;
+use leptos::server;
+use leptos::server_fn::codec::Bitcode;
+
+#[server(input=Bitcode,output=Bitcode)]
+pub async fn get_champion_leaderboard(
+    champion_id: u16,
+    page: Option<u16>,
+) -> Result<ChampionLeaderboardPage, ServerFnError> {
+    let state = expect_context::<crate::ssr::AppState>();
+    let db = state.db.clone();
+    ssr::inner_get_champion_leaderboard(&db, champion_id, page.unwrap_or(1))
+        .await
+        .map_err(|e| e.to_server_fn_error())
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use crate::backend::ssr::{AppError, AppResult, PlatformRouteDb};
+    use crate::utils::ProPlayerSlug;
+    use crate::views::leaderboard_page::{
+        ChampionLeaderboardEntry, ChampionLeaderboardPage, LeaderboardSummoner,
+        LEADERBOARD_PAGE_SIZE,
+    };
+    use crate::views::summoner_page::summoner_live_page::LiveGameParticipantLeague;
+    use common::consts::champion::Champion;
+    use common::consts::platform_route::PlatformRoute;
+    use common::consts::queue::Queue;
+    use common::consts::tier::Tier;
+    use moka::future::Cache;
+    use once_cell::sync::Lazy;
+    use sqlx::{FromRow, PgPool};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// (champion, page) -> page, cleared by `UpdateLeaderboardsTask`.
+    pub static CHAMPION_LEADERBOARD_CACHE: Lazy<Cache<(u16, u16), Arc<ChampionLeaderboardPage>>> =
+        Lazy::new(|| {
+            Cache::builder()
+                .max_capacity(2_000)
+                .time_to_live(Duration::from_secs(10 * 60))
+                .build()
+        });
+
+    pub async fn inner_get_champion_leaderboard(
+        db: &PgPool,
+        champion_id: u16,
+        page: u16,
+    ) -> AppResult<ChampionLeaderboardPage> {
+        if Champion::try_from(champion_id).is_err() {
+            return Err(AppError::NotFound);
+        }
+        let page = page.max(1);
+        CHAMPION_LEADERBOARD_CACHE
+            .try_get_with((champion_id, page), async move {
+                fetch_champion_leaderboard(db, champion_id, page)
+                    .await
+                    .map(Arc::new)
+            })
+            .await
+            .map(|leaderboard| (*leaderboard).clone())
+            .map_err(|e| (*e).clone())
+    }
+
+    async fn fetch_champion_leaderboard(
+        db: &PgPool,
+        champion_id: u16,
+        page: u16,
+    ) -> AppResult<ChampionLeaderboardPage> {
+        let offset = (page as i64 - 1) * LEADERBOARD_PAGE_SIZE;
+        let rows = sqlx::query_as::<_, ChampionLeaderboardEntryModel>(
+            r#"
+            SELECT cle.position,
+                   cle.total_matches,
+                   cle.total_wins,
+                   cle.avg_kills,
+                   cle.avg_deaths,
+                   cle.avg_assists,
+                   ss.game_name,
+                   ss.tag_line,
+                   ss.platform,
+                   ss.profile_icon_id,
+                   ss.pro_player_slug,
+                   le.tier,
+                   le.division,
+                   le.league_points,
+                   le.wins,
+                   le.losses,
+                   COUNT(*) OVER () AS total_count
+            FROM champion_leaderboard_entries as cle
+                     JOIN summoners as ss ON ss.id = cle.summoner_id
+                     LEFT JOIN league_entries as le
+                               ON le.puuid = ss.puuid
+                                   AND le.platform = ss.platform
+                                   AND le.queue_id = $2
+            WHERE cle.champion_id = $1
+            ORDER BY cle.position
+            LIMIT $3 OFFSET $4
+            "#,
+        )
+        .bind(champion_id as i32)
+        .bind(Queue::SummonersRift5v5RankedSolo.id() as i32)
+        .bind(LEADERBOARD_PAGE_SIZE)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        let total_pages = rows.first().map_or(0, |row| {
+            (row.total_count as f32 / LEADERBOARD_PAGE_SIZE as f32).ceil() as u16
+        });
+        let entries = rows
+            .into_iter()
+            .map(|row| {
+                let league = match (
+                    row.tier,
+                    row.division,
+                    row.league_points,
+                    row.wins,
+                    row.losses,
+                ) {
+                    (Some(tier), Some(division), Some(league_points), Some(wins), Some(losses)) => {
+                        Tier::try_from(tier as u8)
+                            .ok()
+                            .map(|tier| LiveGameParticipantLeague {
+                                tier,
+                                division: division as u8,
+                                league_points: league_points as u16,
+                                wins: wins as u16,
+                                losses: losses as u16,
+                            })
+                    }
+                    _ => None,
+                };
+                ChampionLeaderboardEntry {
+                    position: row.position as u16,
+                    summoner: LeaderboardSummoner {
+                        game_name: row.game_name,
+                        tag_line: row.tag_line,
+                        platform: PlatformRoute::from(row.platform),
+                        profile_icon_id: row.profile_icon_id as u16,
+                        pro_slug: row.pro_player_slug.map(|s| ProPlayerSlug::new(s.as_str())),
+                    },
+                    league,
+                    total_matches: row.total_matches as u16,
+                    total_wins: row.total_wins as u16,
+                    avg_kills: row.avg_kills,
+                    avg_deaths: row.avg_deaths,
+                    avg_assists: row.avg_assists,
+                }
+            })
+            .collect();
+        Ok(ChampionLeaderboardPage {
+            total_pages,
+            entries,
+        })
+    }
+
+    #[derive(FromRow)]
+    struct ChampionLeaderboardEntryModel {
+        pub position: i32,
+        pub total_matches: i32,
+        pub total_wins: i32,
+        pub avg_kills: f32,
+        pub avg_deaths: f32,
+        pub avg_assists: f32,
+        pub game_name: String,
+        pub tag_line: String,
+        pub platform: PlatformRouteDb,
+        pub profile_icon_id: i32,
+        pub pro_player_slug: Option<String>,
+        pub tier: Option<i16>,
+        pub division: Option<i16>,
+        pub league_points: Option<i32>,
+        pub wins: Option<i32>,
+        pub losses: Option<i32>,
+        pub total_count: i64,
+    }
+}
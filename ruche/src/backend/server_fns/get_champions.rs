@@ -24,69 +24,21 @@ pub async fn get_champions(
 
 #[cfg(feature = "ssr")]
 pub mod ssr {
+    use crate::backend::server_fns::get_matches::ssr::push_match_filters;
     use crate::backend::ssr::AppResult;
     use crate::views::summoner_page::summoner_champions_page::ChampionStats;
     use crate::views::BackEndMatchFiltersSearch;
     use bigdecimal::{BigDecimal, ToPrimitive};
-    use common::consts::queue::Queue;
     use itertools::Itertools;
-    use sqlx::{FromRow, PgPool, QueryBuilder};
+    use sqlx::{FromRow, PgPool, Postgres, QueryBuilder};
 
     pub async fn inner_get_champions(
         db: &PgPool,
         summoner_id: i32,
         filters: BackEndMatchFiltersSearch,
     ) -> AppResult<Vec<ChampionStats>> {
-        let start_date = filters.start_date_to_naive();
-        let end_date = filters.end_date_to_naive();
-
-        let mut query = QueryBuilder::new(
-            r#"
-             SELECT lmp.champion_id,
-               count(lmp.lol_match_id)                  as total_matches,
-               sum(CASE WHEN lmp.won THEN 1 ELSE 0 END) AS total_wins,
-               avg(lmp.kill_participation)              as avg_kill_participation,
-               avg(lmp.kills)                           as avg_kills,
-               avg(lmp.deaths)                          as avg_deaths,
-               avg(lmp.assists)                         as avg_assists,
-               avg(lmp.gold_earned)                     as avg_gold_earned,
-               avg(lmp.cs)                              as avg_cs,
-               avg(lmp.kda)                              as avg_kda,
-               avg(lmp.damage_dealt_to_champions)       as avg_damage_dealt_to_champions,
-               avg(lmp.damage_taken)                    as avg_damage_taken,
-               sum(lmp.double_kills)                    AS total_double_kills,
-               sum(lmp.triple_kills)                    AS total_triple_kills,
-               sum(lmp.quadra_kills)                    AS total_quadra_kills,
-               sum(lmp.penta_kills)                     AS total_penta_kills
-            FROM lol_match_participants as lmp
-                     left JOIN lol_matches lm ON lm.id = lmp.lol_match_id
-            WHERE lmp.summoner_id =
-        "#,
-        );
-
-        query.push_bind(summoner_id);
-        if let Some(champion_id) = filters.champion_id {
-            let sql_filter = " AND lmp.champion_id = ";
-            query.push(sql_filter);
-            query.push_bind(champion_id as i32);
-        }
-        if let Some(queue_id) = filters.queue_id {
-            let sql_filter = " AND lm.queue_id = ";
-            query.push(sql_filter);
-            query.push_bind(Queue::try_from(queue_id).unwrap().id() as i32);
-        }
-
-        if let Some(start_date) = start_date {
-            let sql_filter = " AND lm.match_end >= ";
-            query.push(sql_filter);
-            query.push_bind(start_date);
-        }
-        if let Some(end_date) = end_date {
-            let sql_filter = " AND lm.match_end <= ";
-            query.push(sql_filter);
-            query.push_bind(end_date);
-        }
-        query.push(" GROUP BY lmp.champion_id ORDER BY total_matches DESC");
+        let mut query = QueryBuilder::new("");
+        push_champions_query(&mut query, summoner_id, filters);
 
         Ok(query
             .build_query_as::<ChampionStatsModel>()
@@ -130,6 +82,41 @@ pub mod ssr {
             .collect_vec())
     }
 
+    /// Per-champion aggregate of `summoner_id`'s games, pushed onto `query` so the
+    /// export can wrap it.
+    pub fn push_champions_query(
+        query: &mut QueryBuilder<'_, Postgres>,
+        summoner_id: i32,
+        filters: BackEndMatchFiltersSearch,
+    ) {
+        query.push(
+            r#"
+             SELECT lmp.champion_id,
+               count(lmp.lol_match_id)                  as total_matches,
+               sum(CASE WHEN lmp.won THEN 1 ELSE 0 END) AS total_wins,
+               avg(lmp.kill_participation)              as avg_kill_participation,
+               avg(lmp.kills)                           as avg_kills,
+               avg(lmp.deaths)                          as avg_deaths,
+               avg(lmp.assists)                         as avg_assists,
+               avg(lmp.gold_earned)                     as avg_gold_earned,
+               avg(lmp.cs)                              as avg_cs,
+               avg(lmp.kda)                              as avg_kda,
+               avg(lmp.damage_dealt_to_champions)       as avg_damage_dealt_to_champions,
+               avg(lmp.damage_taken)                    as avg_damage_taken,
+               sum(lmp.double_kills)                    AS total_double_kills,
+               sum(lmp.triple_kills)                    AS total_triple_kills,
+               sum(lmp.quadra_kills)                    AS total_quadra_kills,
+               sum(lmp.penta_kills)                     AS total_penta_kills
+            FROM lol_match_participants as lmp
+                     left JOIN lol_matches lm ON lm.id = lmp.lol_match_id
+            WHERE lmp.summoner_id =
+        "#,
+        );
+
+        push_match_filters(query, summoner_id, &filters);
+        query.push(" GROUP BY lmp.champion_id ORDER BY total_matches DESC");
+    }
+
     #[derive(FromRow)]
     struct ChampionStatsModel {
         pub champion_id: i32,
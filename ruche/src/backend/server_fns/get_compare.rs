@@ -0,0 +1,187 @@
+use crate::app::SummonerIdentifier;
+use crate::views::compare_page::CompareResult;
+use crate::views::BackEndMatchFiltersSearch;
+use leptos::prelude::*;
+use leptos::server;
+use leptos::server_fn::codec::Bitcode;
+
+#[server(input=Bitcode,output=Bitcode)]
+pub async fn get_compare(
+    summoner_identifiers: Vec<SummonerIdentifier>,
+    filters: Option<BackEndMatchFiltersSearch>,
+) -> Result<CompareResult, ServerFnError> {
+    let state = expect_context::<crate::ssr::AppState>();
+    let db = state.db.clone();
+    ssr::inner_get_compare(&db, summoner_identifiers, filters.unwrap_or_default())
+        .await
+        .map_err(|e| e.to_server_fn_error())
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use crate::app::SummonerIdentifier;
+    use crate::backend::server_fns::get_champions::ssr::inner_get_champions;
+    use crate::backend::server_fns::get_matches::ssr::push_match_filters;
+    use crate::backend::server_fns::get_summoner::ssr::resolve_summoner_by_s_identifier;
+    use crate::backend::ssr::{AppError, AppResult};
+    use crate::views::compare_page::{
+        CompareResult, CompareRole, CompareSummoner, CompareSummonerStats, MAX_COMPARED_SUMMONERS,
+        TOP_COMPARED_CHAMPIONS,
+    };
+    use crate::views::summoner_page::Summoner;
+    use crate::views::BackEndMatchFiltersSearch;
+    use bigdecimal::{BigDecimal, ToPrimitive};
+    use common::consts::position::Position;
+    use itertools::Itertools;
+    use sqlx::{FromRow, PgPool, Postgres, QueryBuilder};
+
+    pub async fn inner_get_compare(
+        db: &PgPool,
+        summoner_identifiers: Vec<SummonerIdentifier>,
+        filters: BackEndMatchFiltersSearch,
+    ) -> AppResult<CompareResult> {
+        let mut result = CompareResult::default();
+        for summoner_identifier in summoner_identifiers
+            .into_iter()
+            .unique()
+            .take(MAX_COMPARED_SUMMONERS)
+        {
+            match resolve_summoner_by_s_identifier(db, &summoner_identifier).await {
+                Ok(summoner) => result
+                    .summoners
+                    .push(get_compare_summoner(db, summoner, filters).await?),
+                Err(AppError::CustomError(_)) => result.not_found.push(summoner_identifier),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(result)
+    }
+
+    async fn get_compare_summoner(
+        db: &PgPool,
+        summoner: Summoner,
+        filters: BackEndMatchFiltersSearch,
+    ) -> AppResult<CompareSummoner> {
+        let (stats, roles, recent_form, champions) = tokio::try_join!(
+            get_summoner_stats(db, summoner.id, filters),
+            get_summoner_roles(db, summoner.id, filters),
+            get_summoner_recent_form(db, summoner.id, filters),
+            inner_get_champions(db, summoner.id, filters),
+        )?;
+        Ok(CompareSummoner {
+            summoner,
+            stats,
+            roles,
+            recent_form,
+            champions: champions.into_iter().take(TOP_COMPARED_CHAMPIONS).collect(),
+        })
+    }
+
+    async fn get_summoner_stats(
+        db: &PgPool,
+        summoner_id: i32,
+        filters: BackEndMatchFiltersSearch,
+    ) -> AppResult<CompareSummonerStats> {
+        let mut query = QueryBuilder::new(
+            r#"
+            SELECT count(lmp.lol_match_id)                  as total_matches,
+                   sum(CASE WHEN lmp.won THEN 1 ELSE 0 END) as total_wins,
+                   avg(lmp.kills)                           as avg_kills,
+                   avg(lmp.deaths)                          as avg_deaths,
+                   avg(lmp.assists)                         as avg_assists,
+                   avg(lmp.kill_participation)              as avg_kill_participation,
+                   avg(lmp.cs_per_minute)                   as avg_cs_per_minute,
+                   avg(lmp.damage_dealt_to_champions)       as avg_damage_dealt_to_champions
+            FROM lol_match_participants as lmp
+                     JOIN lol_matches as lm ON lm.id = lmp.lol_match_id
+            WHERE lmp.summoner_id =
+            "#,
+        );
+        push_match_filters(&mut query, summoner_id, &filters);
+
+        let stats = query
+            .build_query_as::<CompareSummonerStatsModel>()
+            .fetch_one(db)
+            .await?;
+        let to_f32 =
+            |value: Option<BigDecimal>| value.and_then(|value| value.to_f32()).unwrap_or_default();
+        Ok(CompareSummonerStats {
+            total_matches: stats.total_matches as u16,
+            total_wins: stats.total_wins.unwrap_or_default() as u16,
+            avg_kills: to_f32(stats.avg_kills),
+            avg_deaths: to_f32(stats.avg_deaths),
+            avg_assists: to_f32(stats.avg_assists),
+            avg_kill_participation: to_f32(stats.avg_kill_participation).round() as u16,
+            avg_cs_per_minute: to_f32(stats.avg_cs_per_minute),
+            avg_damage_dealt_to_champions: to_f32(stats.avg_damage_dealt_to_champions) as u32,
+        })
+    }
+
+    /// Games per position, most played first; games without a position are left out.
+    async fn get_summoner_roles(
+        db: &PgPool,
+        summoner_id: i32,
+        filters: BackEndMatchFiltersSearch,
+    ) -> AppResult<Vec<CompareRole>> {
+        let mut query = QueryBuilder::new(
+            r#"
+            SELECT lmp.team_position,
+                   count(lmp.lol_match_id)                  as total_matches,
+                   sum(CASE WHEN lmp.won THEN 1 ELSE 0 END) as total_wins
+            FROM lol_match_participants as lmp
+                     JOIN lol_matches as lm ON lm.id = lmp.lol_match_id
+            WHERE lmp.team_position IS NOT NULL
+              AND lmp.summoner_id =
+            "#,
+        );
+        push_match_filters(&mut query, summoner_id, &filters);
+        query.push(" GROUP BY lmp.team_position ORDER BY total_matches DESC");
+
+        Ok(query
+            .build_query_as::<(String, i64, i64)>()
+            .fetch_all(db)
+            .await?
+            .into_iter()
+            .filter_map(|(team_position, total_matches, total_wins)| {
+                Some(CompareRole {
+                    position: Position::from_code(&team_position)?,
+                    total_matches: total_matches as u16,
+                    total_wins: total_wins as u16,
+                })
+            })
+            .collect())
+    }
+
+    /// Results of the last 10 filtered games, newest first.
+    async fn get_summoner_recent_form(
+        db: &PgPool,
+        summoner_id: i32,
+        filters: BackEndMatchFiltersSearch,
+    ) -> AppResult<Vec<bool>> {
+        let mut query = QueryBuilder::new(
+            r#"
+            SELECT lmp.won
+            FROM lol_match_participants as lmp
+                     JOIN lol_matches as lm ON lm.id = lmp.lol_match_id
+            WHERE lm.match_end IS NOT NULL
+              AND lmp.summoner_id =
+            "#,
+        );
+        push_match_filters(&mut query, summoner_id, &filters);
+        query.push(" ORDER BY lm.match_end DESC LIMIT 10");
+
+        Ok(query.build_query_scalar::<bool>().fetch_all(db).await?)
+    }
+
+    #[derive(FromRow)]
+    struct CompareSummonerStatsModel {
+        pub total_matches: i64,
+        pub total_wins: Option<i64>,
+        pub avg_kills: Option<BigDecimal>,
+        pub avg_deaths: Option<BigDecimal>,
+        pub avg_assists: Option<BigDecimal>,
+        pub avg_kill_participation: Option<BigDecimal>,
+        pub avg_cs_per_minute: Option<BigDecimal>,
+        pub avg_damage_dealt_to_champions: Option<BigDecimal>,
+    }
+}
@@ -166,6 +166,9 @@ pub mod ssr {
             stats_query.push_bind(Queue::from_id_or_custom(queue_id).id() as i32);
         }
 
+        filters.push_patch_filter(&mut query);
+        filters.push_patch_filter(&mut stats_query);
+
         if let Some(start_date) = start_date {
             let sql_filter = " AND lm.match_end >= ";
             query.push(sql_filter);
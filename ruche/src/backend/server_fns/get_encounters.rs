@@ -81,6 +81,7 @@ pub mod ssr {
             query.push_bind(champion_id as i32);
         }
         if filters.queue_id.is_some()
+            || filters.patch_from.is_some()
             || start_date.is_some()
             || end_date.is_some()
             || search_summoner.is_some()
@@ -100,6 +101,7 @@ pub mod ssr {
             query.push(sql_filter);
             query.push_bind(Queue::from_id_or_custom(queue_id).id() as i32);
         }
+        filters.push_patch_filter(&mut query);
 
         if let Some(start_date) = start_date {
             let sql_filter = " AND lm.match_end >= ";
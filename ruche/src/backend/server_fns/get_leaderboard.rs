@@ -0,0 +1,172 @@
+use crate::views::leaderboard_page::LeaderboardPage;
+use common::consts::platform_route::PlatformRoute;
+use leptos::prelude::*;
+use leptos::server;
+use leptos::server_fn::codec::Bitcode;
+
+#[server(input=Bitcode,output=Bitcode)]
+pub async fn get_leaderboard(
+    platform_route: PlatformRoute,
+    queue_id: u16,
+    stored_only: bool,
+    page: Option<u16>,
+) -> Result<LeaderboardPage, ServerFnError> {
+    let state = expect_context::<crate::ssr::AppState>();
+    let db = state.db.clone();
+    ssr::inner_get_leaderboard(
+        &db,
+        platform_route,
+        queue_id,
+        stored_only,
+        page.unwrap_or(1),
+    )
+    .await
+    .map_err(|e| e.to_server_fn_error())
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use crate::backend::ssr::{AppResult, PlatformRouteDb};
+    use crate::utils::ProPlayerSlug;
+    use crate::views::leaderboard_page::{
+        LeaderboardEntry, LeaderboardPage, LeaderboardSummoner, LEADERBOARD_PAGE_SIZE,
+        LEADERBOARD_QUEUES,
+    };
+    use crate::views::summoner_page::summoner_live_page::LiveGameParticipantLeague;
+    use common::consts::platform_route::PlatformRoute;
+    use common::consts::queue::Queue;
+    use common::consts::tier::Tier;
+    use moka::future::Cache;
+    use once_cell::sync::Lazy;
+    use sqlx::{FromRow, PgPool};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// (platform, queue, stored only, page) -> page, cleared by `UpdateLeaderboardsTask`.
+    pub static LEADERBOARD_CACHE: Lazy<
+        Cache<(PlatformRoute, u16, bool, u16), Arc<LeaderboardPage>>,
+    > = Lazy::new(|| {
+        Cache::builder()
+            .max_capacity(2_000)
+            .time_to_live(Duration::from_secs(10 * 60))
+            .build()
+    });
+
+    pub async fn inner_get_leaderboard(
+        db: &PgPool,
+        platform_route: PlatformRoute,
+        queue_id: u16,
+        stored_only: bool,
+        page: u16,
+    ) -> AppResult<LeaderboardPage> {
+        // unknown queues fall back to solo/duo so the cache key space stays small
+        let queue = LEADERBOARD_QUEUES
+            .into_iter()
+            .find(|queue| queue.id() == queue_id)
+            .unwrap_or(Queue::SummonersRift5v5RankedSolo);
+        let page = page.max(1);
+        LEADERBOARD_CACHE
+            .try_get_with(
+                (platform_route, queue.id(), stored_only, page),
+                async move {
+                    fetch_leaderboard(db, platform_route, queue, stored_only, page)
+                        .await
+                        .map(Arc::new)
+                },
+            )
+            .await
+            .map(|leaderboard| (*leaderboard).clone())
+            .map_err(|e| (*e).clone())
+    }
+
+    async fn fetch_leaderboard(
+        db: &PgPool,
+        platform_route: PlatformRoute,
+        queue: Queue,
+        stored_only: bool,
+        page: u16,
+    ) -> AppResult<LeaderboardPage> {
+        let offset = (page as i64 - 1) * LEADERBOARD_PAGE_SIZE;
+        let rows = sqlx::query_as::<_, LeaderboardEntryModel>(
+            r#"
+            SELECT le.tier,
+                   le.division,
+                   le.league_points,
+                   le.wins,
+                   le.losses,
+                   ss.game_name,
+                   ss.tag_line,
+                   ss.platform,
+                   ss.profile_icon_id,
+                   ss.pro_player_slug,
+                   COUNT(*) OVER () AS total_count
+            FROM league_entries as le
+                     LEFT JOIN summoners as ss ON ss.puuid = le.puuid
+            WHERE le.platform = $1
+              AND le.queue_id = $2
+              AND (NOT $3 OR ss.id IS NOT NULL)
+            ORDER BY le.ladder_score DESC, le.wins DESC
+            LIMIT $4 OFFSET $5
+            "#,
+        )
+        .bind(PlatformRouteDb::from(platform_route))
+        .bind(queue.id() as i32)
+        .bind(stored_only)
+        .bind(LEADERBOARD_PAGE_SIZE)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        let total_pages = rows.first().map_or(0, |row| {
+            (row.total_count as f32 / LEADERBOARD_PAGE_SIZE as f32).ceil() as u16
+        });
+        let entries = rows
+            .into_iter()
+            .enumerate()
+            .filter_map(|(idx, row)| {
+                let summoner = match (row.game_name, row.tag_line, row.platform) {
+                    (Some(game_name), Some(tag_line), Some(platform)) => {
+                        Some(LeaderboardSummoner {
+                            game_name,
+                            tag_line,
+                            platform: PlatformRoute::from(platform),
+                            profile_icon_id: row.profile_icon_id.unwrap_or_default() as u16,
+                            pro_slug: row.pro_player_slug.map(|s| ProPlayerSlug::new(s.as_str())),
+                        })
+                    }
+                    _ => None,
+                };
+                Some(LeaderboardEntry {
+                    position: (offset + idx as i64 + 1) as u32,
+                    league: LiveGameParticipantLeague {
+                        tier: Tier::try_from(row.tier as u8).ok()?,
+                        division: row.division as u8,
+                        league_points: row.league_points as u16,
+                        wins: row.wins as u16,
+                        losses: row.losses as u16,
+                    },
+                    summoner,
+                })
+            })
+            .collect();
+        Ok(LeaderboardPage {
+            total_pages,
+            entries,
+        })
+    }
+
+    #[derive(FromRow)]
+    struct LeaderboardEntryModel {
+        pub tier: i16,
+        pub division: i16,
+        pub league_points: i32,
+        pub wins: i32,
+        pub losses: i32,
+        pub game_name: Option<String>,
+        pub tag_line: Option<String>,
+        pub platform: Option<PlatformRouteDb>,
+        pub profile_icon_id: Option<i32>,
+        pub pro_player_slug: Option<String>,
+        pub total_count: i64,
+    }
+}
@@ -55,6 +55,8 @@ pub async fn get_live_game(
 
 #[cfg(feature = "ssr")]
 pub mod ssr {
+    use crate::backend::riot_budget::RiotCallPriority;
+    use crate::backend::riot_cache;
     use crate::backend::ssr::{AppResult, PlatformRouteDb};
     use crate::backend::tasks::update_matches::bulk_summoners::bulk_insert_summoners;
     use crate::backend::tasks::update_matches::{SummonerFull, TempSummoner};
@@ -63,20 +65,28 @@ pub mod ssr {
     use crate::ssr::RiotApiState;
     use crate::utils::{ProPlayerSlug, Puuid, RiotMatchId};
     use crate::views::summoner_page::summoner_live_page::{
-        LiveGame, LiveGameParticipant, LiveGameParticipantChampionStats,
-        LiveGameParticipantRankedStats,
+        LiveGame, LiveGameBan, LiveGameParticipant, LiveGameParticipantChampionStats,
+        LiveGameParticipantLeague, LiveGameParticipantMastery, LiveGameParticipantRankedStats,
+        LiveGamePhase,
     };
     use bigdecimal::{BigDecimal, ToPrimitive};
     use common::consts::map::Map;
     use common::consts::platform_route::PlatformRoute;
+    use common::consts::position::Position;
     use common::consts::queue::Queue;
+    use common::consts::tier::Tier;
     use itertools::Itertools;
+    use riven::consts::QueueType;
+    use riven::models::league_v4::LeagueEntry;
     use riven::models::spectator_v5::CurrentGameInfo;
     use riven::RiotApi;
     use sqlx::PgPool;
     use std::collections::{HashMap, HashSet};
     use std::sync::Arc;
 
+    const PREMADE_WINDOW_DAYS: i32 = 30;
+    const PREMADE_MIN_GAMES: i64 = 2;
+
     pub async fn add_encounters(
         db: &PgPool,
         mut game_data: LiveGame,
@@ -102,20 +112,30 @@ pub mod ssr {
         puuid: Puuid,
         platform: PlatformRoute,
     ) -> AppResult<Option<(Vec<i32>, LiveGame)>> {
-        let live_game = riot_api
-            .spectator_v5()
-            .get_current_game_info_by_puuid(platform.to_riven(), puuid.as_ref())
-            .await
-            .ok()
-            .flatten();
+        let live_game = riot_cache::current_game_by_puuid(
+            riot_api,
+            platform.to_riven(),
+            puuid.as_ref(),
+            RiotCallPriority::Interactive,
+        )
+        .await
+        .ok()
+        .flatten();
         if let Some(live_game) = live_game {
-            let (all_participants, live_game_stats) =
-                get_all_participants_live_game_stats(db, riot_api, vec![&live_game]).await?;
+            let (all_participants, live_game_stats, scouting) =
+                get_all_participants_live_game_stats(
+                    db,
+                    riot_api,
+                    vec![&live_game],
+                    RiotCallPriority::Interactive,
+                )
+                .await?;
             let (summoner_ids, live_game) = game_info_to_live_game(
                 RiotMatchId::get_live_version(live_game.platform_id.as_str(), live_game.game_id),
                 live_game,
                 &all_participants,
                 &live_game_stats,
+                &scouting,
             );
             Ok(Some((summoner_ids, live_game)))
         } else {
@@ -127,6 +147,7 @@ pub mod ssr {
         db: &PgPool,
         riot_api: &RiotApiState,
         puuids_details: &[(String, PlatformRoute, i64)],
+        priority: RiotCallPriority,
     ) -> AppResult<HashMap<String, SummonerFull>> {
         let futures = puuids_details
             .iter()
@@ -134,10 +155,13 @@ pub mod ssr {
                 let api = riot_api.clone();
                 async move {
                     let riven_pr = platform_route.to_riven();
-                    let account = api
-                        .account_v1()
-                        .get_by_puuid(riven_pr.to_regional(), puuid.as_str())
-                        .await;
+                    let account = riot_cache::account_by_puuid(
+                        &api,
+                        riven_pr.to_regional(),
+                        puuid.as_str(),
+                        priority,
+                    )
+                    .await;
                     (account, puuid, platform_route, *profile_icon_id)
                 }
             });
@@ -168,9 +192,11 @@ pub mod ssr {
         game_info: CurrentGameInfo,
         all_participants: &HashMap<String, SummonerFull>,
         live_game_stats: &HashMap<i32, HashMap<i32, ParticipantLiveStats>>,
+        scouting: &HashMap<i32, ParticipantScouting>,
     ) -> (Vec<i32>, LiveGame) {
         let mut participants = vec![];
         let default_hashmap = HashMap::new();
+        let default_scouting = ParticipantScouting::default();
         let mut summoner_ids = vec![];
         for participant in &game_info.participants {
             let participant_puuid = participant.puuid.clone();
@@ -217,6 +243,9 @@ pub mod ssr {
                     (primary as u16, sub_style as u16)
                 }
             };
+            let participant_scouting = scouting
+                .get(&summoner_detail.id)
+                .unwrap_or(&default_scouting);
             summoner_ids.push(summoner_detail.id);
             participants.push(LiveGameParticipant {
                 summoner_id: summoner_detail.id,
@@ -234,13 +263,32 @@ pub mod ssr {
                 champion_stats,
                 encounter_count: 0,
                 pro_player_slug: summoner_detail.pro_player_slug,
+                league: participant_scouting.league,
+                mastery: participant_scouting.mastery,
+                recent_form: participant_scouting.recent_form.clone(),
+                main_role: participant_scouting.main_role,
+                premade_group: None,
             })
         }
+        let groups = premade_groups(&participants, scouting);
+        for participant in participants.iter_mut() {
+            participant.premade_group = groups.get(&participant.summoner_id).copied();
+        }
+        let bans = game_bans(&game_info);
+        // spectator-v5 only lists games past champion select
+        let phase = if game_info.game_start_time <= 0 {
+            LiveGamePhase::Loading
+        } else {
+            LiveGamePhase::InProgress
+        };
         (
             summoner_ids,
             LiveGame {
                 game_id: riot_match_id,
-                game_length: game_info.game_length as u16,
+                game_length: game_info.game_length.max(0) as u16,
+                game_start_time: game_info.game_start_time.max(0),
+                phase,
+                bans,
                 game_map: Map::from_id(game_info.map_id.0).unwrap(),
                 queue: game_info
                     .game_queue_config_id
@@ -251,13 +299,45 @@ pub mod ssr {
         )
     }
 
+    fn game_bans(game_info: &CurrentGameInfo) -> Vec<LiveGameBan> {
+        game_info
+            .banned_champions
+            .iter()
+            .sorted_by_key(|ban| ban.pick_turn)
+            .map(|ban| LiveGameBan {
+                // -1 when the turn was skipped
+                champion_id: ban.champion_id.0.max(0) as u16,
+                team_id: ban.team_id as u16,
+            })
+            .collect()
+    }
+
+    /// Whether a fresh spectator snapshot of the same game differs from the cached
+    /// one in what the live page shows: start time, draft and picked champions.
+    pub fn live_game_changed(cached: &LiveGame, game_info: &CurrentGameInfo) -> bool {
+        let champions = game_info
+            .participants
+            .iter()
+            .filter(|p| p.puuid.as_deref().is_some_and(|puuid| !puuid.is_empty()))
+            .map(|p| p.champion_id.0.max(0) as u16);
+        cached.game_start_time != game_info.game_start_time.max(0)
+            || cached.bans != game_bans(game_info)
+            || !cached
+                .participants
+                .iter()
+                .map(|p| p.champion_id)
+                .eq(champions)
+    }
+
     pub async fn get_all_participants_live_game_stats(
         db: &PgPool,
         riot_api: &Arc<RiotApi>,
         live_games: Vec<&CurrentGameInfo>,
+        priority: RiotCallPriority,
     ) -> AppResult<(
         HashMap<String, SummonerFull>,
         HashMap<i32, HashMap<i32, ParticipantLiveStats>>,
+        HashMap<i32, ParticipantScouting>,
     )> {
         let participant_puuids_info = live_games
             .iter()
@@ -307,7 +387,8 @@ pub mod ssr {
             })
             .collect::<Vec<(String, PlatformRoute, i64)>>();
         let inserted_summoners =
-            find_and_insert_new_summoners(db, riot_api, &puuids_platform_not_found).await?;
+            find_and_insert_new_summoners(db, riot_api, &puuids_platform_not_found, priority)
+                .await?;
         let all_participants = inserted_summoners
             .into_iter()
             .chain(summoner_details.into_iter())
@@ -316,10 +397,212 @@ pub mod ssr {
             .values()
             .map(|x| x.id)
             .collect::<Vec<i32>>();
-        Ok((
-            all_participants,
-            get_summoners_live_stats(db, &all_summoner_ids).await?,
+        let picks = live_games
+            .iter()
+            .flat_map(|game_info| {
+                game_info.participants.iter().filter_map(|participant| {
+                    let puuid = participant.puuid.as_deref()?;
+                    let summoner = all_participants.get(puuid)?;
+                    Some((
+                        summoner.id,
+                        puuid.to_string(),
+                        summoner.platform,
+                        participant.champion_id,
+                    ))
+                })
+            })
+            .collect::<Vec<_>>();
+        let (live_stats, recent_games, duo_games, riot_scouting) = tokio::join!(
+            get_summoners_live_stats(db, &all_summoner_ids),
+            get_summoners_recent_games(db, &all_summoner_ids),
+            get_summoners_duo_games(db, &all_summoner_ids),
+            fetch_participants_scouting(riot_api, picks, priority),
+        );
+        let mut scouting = riot_scouting;
+        for (summoner_id, (recent_form, main_role)) in recent_games? {
+            let entry = scouting.entry(summoner_id).or_default();
+            entry.recent_form = recent_form;
+            entry.main_role = main_role;
+        }
+        for (summoner_id, other_id) in duo_games? {
+            scouting
+                .entry(summoner_id)
+                .or_default()
+                .played_with
+                .push(other_id);
+            scouting
+                .entry(other_id)
+                .or_default()
+                .played_with
+                .push(summoner_id);
+        }
+        Ok((all_participants, live_stats?, scouting))
+    }
+
+    /// Ranked solo/duo entry out of a player's league entries.
+    pub fn solo_queue_league(entries: Vec<LeagueEntry>) -> Option<LiveGameParticipantLeague> {
+        entries
+            .into_iter()
+            .find(|entry| entry.queue_type == QueueType::RANKED_SOLO_5x5)
+            .and_then(|entry| {
+                Some(LiveGameParticipantLeague {
+                    tier: Tier::from_code(&entry.tier?.to_string())?,
+                    division: entry
+                        .rank
+                        .and_then(|rank| Tier::division_from_code(&rank.to_string()))
+                        .unwrap_or_default(),
+                    league_points: entry.league_points.max(0) as u16,
+                    wins: entry.wins.max(0) as u16,
+                    losses: entry.losses.max(0) as u16,
+                })
+            })
+    }
+
+    /// League entry and mastery on the picked champion, one budgeted call each per
+    /// participant, all in parallel. Failed calls just leave the field empty.
+    async fn fetch_participants_scouting(
+        riot_api: &Arc<RiotApi>,
+        picks: Vec<(i32, String, PlatformRoute, riven::consts::Champion)>,
+        priority: RiotCallPriority,
+    ) -> HashMap<i32, ParticipantScouting> {
+        futures::future::join_all(picks.into_iter().map(
+            |(summoner_id, puuid, platform, champion)| async move {
+                let route = platform.to_riven();
+                let (entries, mastery) = tokio::join!(
+                    riot_cache::league_entries_by_puuid(riot_api, route, &puuid, priority),
+                    riot_cache::champion_mastery_by_puuid(
+                        riot_api, route, &puuid, champion, priority
+                    ),
+                );
+                let league = entries.ok().and_then(solo_queue_league);
+                let mastery = mastery
+                    .ok()
+                    .flatten()
+                    .map(|mastery| LiveGameParticipantMastery {
+                        level: mastery.champion_level.max(0) as u16,
+                        points: mastery.champion_points.max(0) as u32,
+                    });
+                (
+                    summoner_id,
+                    ParticipantScouting {
+                        league,
+                        mastery,
+                        ..ParticipantScouting::default()
+                    },
+                )
+            },
         ))
+        .await
+        .into_iter()
+        .collect()
+    }
+
+    /// Pairs of summoners (smaller id first) that played on the same team at least
+    /// `PREMADE_MIN_GAMES` times over the last `PREMADE_WINDOW_DAYS` days.
+    pub async fn get_summoners_duo_games(
+        db: &PgPool,
+        summoner_ids: &[i32],
+    ) -> AppResult<Vec<(i32, i32)>> {
+        Ok(sqlx::query_as::<_, (i32, i32)>(
+            r#"
+            SELECT
+                a.summoner_id,
+                b.summoner_id
+            FROM lol_match_participants as a
+                JOIN lol_match_participants as b
+                    ON a.lol_match_id = b.lol_match_id AND a.team_id = b.team_id AND a.summoner_id < b.summoner_id
+                JOIN lol_matches as lm on a.lol_match_id = lm.id
+            WHERE a.summoner_id = ANY($1) and b.summoner_id = ANY($1)
+                and lm.match_end >= NOW() - make_interval(days => $2)
+            GROUP BY a.summoner_id, b.summoner_id
+            HAVING COUNT(*) >= $3
+            "#,
+        )
+        .bind(summoner_ids)
+        .bind(PREMADE_WINDOW_DAYS)
+        .bind(PREMADE_MIN_GAMES)
+        .fetch_all(db)
+        .await?)
+    }
+
+    /// Groups teammates linked by `played_with` (transitively) and numbers the groups
+    /// from 1; solo players keep `None`.
+    fn premade_groups(
+        participants: &[LiveGameParticipant],
+        scouting: &HashMap<i32, ParticipantScouting>,
+    ) -> HashMap<i32, u8> {
+        let mut groups: HashMap<i32, u8> = HashMap::new();
+        let mut next_group = 1u8;
+        for participant in participants {
+            if groups.contains_key(&participant.summoner_id) {
+                continue;
+            }
+            let mut members = vec![participant.summoner_id];
+            let mut idx = 0;
+            while let Some(&summoner_id) = members.get(idx) {
+                idx += 1;
+                let Some(played_with) = scouting.get(&summoner_id).map(|s| &s.played_with) else {
+                    continue;
+                };
+                for other in participants.iter().filter(|other| {
+                    other.team_id == participant.team_id
+                        && played_with.contains(&other.summoner_id)
+                        && !members.contains(&other.summoner_id)
+                }) {
+                    members.push(other.summoner_id);
+                }
+            }
+            if members.len() > 1 {
+                for summoner_id in members {
+                    groups.insert(summoner_id, next_group);
+                }
+                next_group += 1;
+            }
+        }
+        groups
+    }
+
+    /// Last 10 results (newest first) and most played position over the last 50
+    /// stored games of each summoner.
+    pub async fn get_summoners_recent_games(
+        db: &PgPool,
+        summoner_ids: &[i32],
+    ) -> AppResult<HashMap<i32, (Vec<bool>, Option<Position>)>> {
+        Ok(sqlx::query_as::<_, (i32, Vec<bool>, Option<String>)>(
+            r#"
+            SELECT
+                summoner_id,
+                (array_agg(won ORDER BY match_end DESC))[1:10] as recent_form,
+                mode() WITHIN GROUP (ORDER BY team_position) as main_role
+            FROM (
+                SELECT
+                    lmp.summoner_id,
+                    lmp.won,
+                    lmp.team_position,
+                    lm.match_end,
+                    row_number() OVER (PARTITION BY lmp.summoner_id ORDER BY lm.match_end DESC) as rn
+                FROM lol_match_participants as lmp
+                    JOIN lol_matches as lm on lmp.lol_match_id = lm.id
+                WHERE lmp.summoner_id = ANY($1) and lm.trashed = false and lm.match_end IS NOT NULL
+            ) as recent
+            WHERE rn <= 50
+            GROUP BY summoner_id
+            "#,
+        )
+        .bind(summoner_ids)
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .map(|(summoner_id, recent_form, main_role)| {
+            (
+                summoner_id,
+                (
+                    recent_form,
+                    main_role.as_deref().and_then(Position::from_code),
+                ),
+            )
+        })
+        .collect())
     }
 
     pub async fn get_summoners_live_stats(
@@ -395,6 +678,16 @@ pub mod ssr {
         .collect::<HashMap<String, SummonerFull>>())
     }
 
+    #[derive(Clone, Default)]
+    pub struct ParticipantScouting {
+        pub league: Option<LiveGameParticipantLeague>,
+        pub mastery: Option<LiveGameParticipantMastery>,
+        pub recent_form: Vec<bool>,
+        pub main_role: Option<Position>,
+        /// Other live participants seen on the same team recently.
+        pub played_with: Vec<i32>,
+    }
+
     #[derive(sqlx::FromRow)]
     pub struct ParticipantLiveStats {
         pub summoner_id: i32,
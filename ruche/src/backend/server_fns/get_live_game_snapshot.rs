@@ -0,0 +1,58 @@
+use crate::utils::RiotMatchId;
+use crate::views::summoner_page::summoner_live_page::LiveGame;
+use leptos::prelude::*;
+use leptos::server;
+use leptos::server_fn::codec::Bitcode;
+
+/// Live game as last seen before it ended, if one of its players was watched live.
+#[server(input=Bitcode,output=Bitcode)]
+pub async fn get_live_game_snapshot(
+    riot_match_id: RiotMatchId,
+) -> Result<Option<LiveGame>, ServerFnError> {
+    let state = expect_context::<crate::ssr::AppState>();
+    let db = state.db.clone();
+    Ok(ssr::find_live_game_snapshot(&db, riot_match_id).await?)
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use crate::backend::ssr::AppResult;
+    use crate::utils::RiotMatchId;
+    use crate::views::summoner_page::summoner_live_page::LiveGame;
+    use sqlx::PgPool;
+
+    pub async fn find_live_game_snapshot(
+        db: &PgPool,
+        riot_match_id: RiotMatchId,
+    ) -> AppResult<Option<LiveGame>> {
+        Ok(sqlx::query_scalar::<_, Vec<u8>>(
+            "SELECT snapshot FROM live_game_snapshots WHERE match_id = $1",
+        )
+        .bind(riot_match_id.to_string())
+        .fetch_optional(db)
+        .await?
+        // a snapshot from an older `LiveGame` layout is just ignored
+        .and_then(|bytes| bitcode::decode::<LiveGame>(&bytes).ok()))
+    }
+
+    /// Stores the final snapshot of each ended game; a later one replaces it.
+    pub async fn save_live_game_snapshots(db: &PgPool, games: &[LiveGame]) -> AppResult<()> {
+        if games.is_empty() {
+            return Ok(());
+        }
+        let (match_ids, snapshots): (Vec<String>, Vec<Vec<u8>>) = games
+            .iter()
+            .map(|game| (game.game_id.to_string(), bitcode::encode(game)))
+            .unzip();
+        sqlx::query(
+            "INSERT INTO live_game_snapshots (match_id, snapshot)
+            SELECT * FROM UNNEST($1::VARCHAR(17)[], $2::BYTEA[])
+            ON CONFLICT (match_id) DO UPDATE SET snapshot = EXCLUDED.snapshot, captured_at = NOW()",
+        )
+        .bind(&match_ids)
+        .bind(&snapshots)
+        .execute(db)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+use crate::utils::RiotMatchId;
+use crate::views::match_page::MatchPage;
+use common::consts::platform_route::PlatformRoute;
+use leptos::prelude::*;
+use leptos::server;
+use leptos::server_fn::codec::Bitcode;
+
+/// Header of the standalone match page; the details themselves come from `get_match_details`.
+#[server(input=Bitcode,output=Bitcode)]
+pub async fn get_match(
+    platform: PlatformRoute,
+    riot_match_id: RiotMatchId,
+) -> Result<MatchPage, ServerFnError> {
+    let state = expect_context::<crate::ssr::AppState>();
+    let db = state.db.clone();
+    match ssr::find_match_page(&db, platform, riot_match_id).await? {
+        Some(match_page) => Ok(match_page),
+        None => {
+            let response = expect_context::<leptos_axum::ResponseOptions>();
+            response.set_status(http::StatusCode::NOT_FOUND);
+            Err(ServerFnError::new("match not found"))
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use crate::backend::ssr::{format_duration_since, AppResult, PlatformRouteDb};
+    use crate::utils::{DurationSince, RiotMatchId};
+    use crate::views::match_page::{MatchPage, MatchPageParticipant};
+    use chrono::NaiveDateTime;
+    use common::consts::platform_route::PlatformRoute;
+    use common::consts::queue::Queue;
+    use sqlx::{FromRow, PgPool};
+
+    pub async fn find_match_page(
+        db: &PgPool,
+        platform: PlatformRoute,
+        riot_match_id: RiotMatchId,
+    ) -> AppResult<Option<MatchPage>> {
+        let Some(lol_match) = sqlx::query_as::<_, MatchPageModel>(
+            r#"
+            SELECT lm.id,
+                   lm.queue_id,
+                   lm.match_duration,
+                   lm.match_end,
+                   lm.version,
+                   EXISTS(SELECT 1 FROM live_game_snapshots as lgs WHERE lgs.match_id = lm.match_id)
+                       AS has_live_snapshot
+            FROM lol_matches as lm
+            WHERE lm.match_id = $1
+              AND lm.platform = $2
+              AND lm.updated
+              AND NOT lm.trashed
+            "#,
+        )
+        .bind(riot_match_id.as_ref())
+        .bind(PlatformRouteDb::from(platform))
+        .fetch_optional(db)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let participants = sqlx::query_as::<_, MatchPageParticipantModel>(
+            r#"
+            SELECT lmp.champion_id,
+                   lmp.team_id,
+                   lmp.won,
+                   lmp.kills,
+                   lmp.deaths,
+                   lmp.assists,
+                   ss.game_name,
+                   ss.tag_line
+            FROM lol_match_participants as lmp
+                     JOIN summoners as ss ON ss.id = lmp.summoner_id
+            WHERE lmp.lol_match_id = $1
+            ORDER BY lmp.team_id, lmp.id
+            "#,
+        )
+        .bind(lol_match.id)
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .map(|participant| MatchPageParticipant {
+            champion_id: participant.champion_id as u16,
+            team_id: participant.team_id as u16,
+            won: participant.won,
+            kills: participant.kills as u16,
+            deaths: participant.deaths as u16,
+            assists: participant.assists as u16,
+            game_name: participant.game_name,
+            tag_line: participant.tag_line,
+        })
+        .collect::<Vec<_>>();
+
+        Ok(Some(MatchPage {
+            match_id: lol_match.id,
+            riot_match_id,
+            platform,
+            queue: Queue::from_id_or_custom(lol_match.queue_id.unwrap_or_default() as u16),
+            match_duration: lol_match.match_duration,
+            match_ended_since: lol_match
+                .match_end
+                .map(format_duration_since)
+                .unwrap_or_else(|| DurationSince::new("")),
+            version: lol_match.version.unwrap_or_default(),
+            has_live_snapshot: lol_match.has_live_snapshot,
+            participants,
+        }))
+    }
+
+    #[derive(FromRow)]
+    struct MatchPageModel {
+        id: i32,
+        queue_id: Option<i32>,
+        match_duration: Option<i32>,
+        match_end: Option<NaiveDateTime>,
+        version: Option<String>,
+        has_live_snapshot: bool,
+    }
+
+    #[derive(FromRow)]
+    struct MatchPageParticipantModel {
+        champion_id: i32,
+        team_id: i32,
+        won: bool,
+        kills: i32,
+        deaths: i32,
+        assists: i32,
+        game_name: String,
+        tag_line: String,
+    }
+}
@@ -1,3 +1,5 @@
+use crate::backend::riot_budget::RiotCallPriority;
+use crate::backend::riot_cache;
 use crate::backend::ssr::{AppError, AppResult};
 use crate::ssr::RiotApiState;
 use crate::views::components::match_details::{ItemEvent, ItemEventType, Skill};
@@ -17,11 +19,14 @@ pub async fn update_match_timeline(
 ) -> AppResult<()> {
     // Fetch the match timeline
     let riven_pr = platform_route.to_riven();
-    let timeline = api
-        .match_v5()
-        .get_timeline(riven_pr.to_regional(), riot_match_id)
-        .await?
-        .ok_or_else(|| AppError::CustomError("Timeline not found".into()))?;
+    let timeline = riot_cache::timeline_by_match_id(
+        &api,
+        riven_pr.to_regional(),
+        riot_match_id,
+        RiotCallPriority::Interactive,
+    )
+    .await?
+    .ok_or_else(|| AppError::CustomError("Timeline not found".into()))?;
 
     let puuids_summoner_ids =
         find_summoner_ids_by_puuids(db, &timeline.metadata.participants).await?;
@@ -0,0 +1,60 @@
+use crate::views::Patch;
+use leptos::prelude::*;
+use leptos::server;
+use leptos::server_fn::codec::Bitcode;
+
+#[server(input=Bitcode,output=Bitcode)]
+pub async fn get_match_patches() -> Result<Vec<Patch>, ServerFnError> {
+    let state = expect_context::<crate::ssr::AppState>();
+    let db = state.db.clone();
+    ssr::inner_get_match_patches(&db)
+        .await
+        .map_err(|e| e.to_server_fn_error())
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use crate::backend::ssr::AppResult;
+    use crate::views::Patch;
+    use moka::future::Cache;
+    use once_cell::sync::Lazy;
+    use sqlx::PgPool;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// A new patch only shows up once its first match is stored, an hour late is fine.
+    pub static MATCH_PATCHES_CACHE: Lazy<Cache<(), Arc<Vec<Patch>>>> = Lazy::new(|| {
+        Cache::builder()
+            .max_capacity(1)
+            .time_to_live(Duration::from_secs(60 * 60))
+            .build()
+    });
+
+    /// Patches of the stored matches, newest first.
+    pub async fn inner_get_match_patches(db: &PgPool) -> AppResult<Vec<Patch>> {
+        MATCH_PATCHES_CACHE
+            .try_get_with((), async move {
+                let mut patches = sqlx::query_scalar::<_, String>(
+                    r#"
+                    SELECT version
+                    FROM lol_matches
+                    WHERE version IS NOT NULL
+                      AND updated
+                      AND NOT trashed
+                    GROUP BY version
+                    "#,
+                )
+                .fetch_all(db)
+                .await?
+                .iter()
+                .filter_map(|version| Patch::parse(version))
+                .collect::<Vec<_>>();
+                patches.sort_unstable_by(|a, b| b.cmp(a));
+                patches.dedup();
+                Ok(Arc::new(patches))
+            })
+            .await
+            .map(|patches| (*patches).clone())
+            .map_err(|e| (*e).clone())
+    }
+}
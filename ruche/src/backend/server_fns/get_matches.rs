@@ -1,5 +1,5 @@
 use crate::app::SummonerIdentifier;
-use crate::views::summoner_page::summoner_matches_page::GetSummonerMatchesResult;
+use crate::views::summoner_page::summoner_matches_page::{GetSummonerMatchesResult, MatchesCursor};
 use crate::views::BackEndMatchFiltersSearch;
 use leptos::prelude::*;
 use leptos::server_fn::codec::Bitcode;
@@ -8,20 +8,29 @@ use leptos::server_fn::codec::Bitcode;
 pub async fn get_matches(
     summoner_identifier: SummonerIdentifier,
     filters: Option<BackEndMatchFiltersSearch>,
+    only_match_ids: Option<Vec<i32>>,
+    cursor: Option<MatchesCursor>,
 ) -> Result<GetSummonerMatchesResult, ServerFnError> {
     let state = expect_context::<crate::ssr::AppState>();
     let db = state.db.clone();
 
-    ssr::fetch_matches(&db, summoner_identifier, filters.unwrap_or_default())
-        .await
+    ssr::fetch_matches(
+        &db,
+        summoner_identifier,
+        filters.unwrap_or_default(),
+        only_match_ids,
+        cursor,
+    )
+    .await
         .map_err(|e| e.to_server_fn_error())
 }
 
 #[cfg(feature = "ssr")]
 pub mod ssr {
-    use crate::backend::ssr::{format_duration_since, AppResult, PlatformRouteDb};
+    use crate::backend::ssr::{format_duration_since, AppError, AppResult, PlatformRouteDb};
     use crate::views::summoner_page::summoner_matches_page::{
-        GetSummonerMatchesResult, MatchesResultInfo, SummonerMatch, SummonerMatchParticipant,
+        GetSummonerMatchesResult, MatchesCursor, MatchesResultInfo, SummonerMatch,
+        SummonerMatchParticipant,
     };
     use crate::views::BackEndMatchFiltersSearch;
     use bigdecimal::{BigDecimal, ToPrimitive};
@@ -32,13 +41,95 @@ pub mod ssr {
     use crate::app::SummonerIdentifier;
     use crate::utils::{DurationSince, ProPlayerSlug, RiotMatchId};
     use common::consts::platform_route::PlatformRoute;
-    use sqlx::{FromRow, PgPool, QueryBuilder};
-    use std::collections::HashMap;
+    use moka::future::Cache;
+    use once_cell::sync::Lazy;
+    use sqlx::{FromRow, PgPool, Postgres, QueryBuilder};
+    use std::collections::{HashMap, HashSet};
+    use std::sync::Arc;
+    use std::time::Duration;
 
+    /// Item, rune, summoner spells, teammate/opponent champion and duo predicates,
+    /// `lmp` being the summoner's participant row.
+    fn push_advanced_filters(
+        query: &mut QueryBuilder<'_, Postgres>,
+        filters: &BackEndMatchFiltersSearch,
+    ) {
+        if let Some(item_id) = filters.item_id {
+            query.push(" AND ");
+            query.push_bind(item_id as i64);
+            query.push(
+                " IN (lmp.item0_id, lmp.item1_id, lmp.item2_id, lmp.item3_id, lmp.item4_id, lmp.item5_id, lmp.item6_id)",
+            );
+        }
+        if let Some(perk_id) = filters.perk_id {
+            query.push(" AND ");
+            query.push_bind(perk_id as i32);
+            query.push(
+                " IN (lmp.perk_primary_selection_id, lmp.perk_primary_selection1_id, lmp.perk_primary_selection2_id, lmp.perk_primary_selection3_id, lmp.perk_sub_selection1_id, lmp.perk_sub_selection2_id)",
+            );
+        }
+        for summoner_spell_id in [filters.summoner_spell1_id, filters.summoner_spell2_id]
+            .into_iter()
+            .flatten()
+        {
+            query.push(" AND ");
+            query.push_bind(summoner_spell_id as i32);
+            query.push(" IN (lmp.summoner_spell1_id, lmp.summoner_spell2_id)");
+        }
+        if let Some(champion_id) = filters.teammate_champion_id {
+            query.push(
+                " AND EXISTS(SELECT 1 FROM lol_match_participants as lmp_t WHERE lmp_t.lol_match_id = lmp.lol_match_id AND lmp_t.team_id = lmp.team_id AND lmp_t.summoner_id != lmp.summoner_id AND lmp_t.champion_id = ",
+            );
+            query.push_bind(champion_id as i32);
+            query.push(")");
+        }
+        if let Some(summoner_id) = filters.teammate_summoner_id {
+            query.push(
+                " AND EXISTS(SELECT 1 FROM lol_match_participants as lmp_d WHERE lmp_d.lol_match_id = lmp.lol_match_id AND lmp_d.team_id = lmp.team_id AND lmp_d.summoner_id = ",
+            );
+            query.push_bind(summoner_id);
+            query.push(")");
+        }
+        if let Some(champion_id) = filters.opponent_champion_id {
+            query.push(
+                " AND EXISTS(SELECT 1 FROM lol_match_participants as lmp_o WHERE lmp_o.lol_match_id = lmp.lol_match_id AND lmp_o.team_id != lmp.team_id AND lmp_o.champion_id = ",
+            );
+            query.push_bind(champion_id as i32);
+            query.push(")");
+        }
+    }
+
+    /// (summoner, filters without page) -> statistics, shared by every page and
+    /// cursor of a filter set. The entries of a summoner are dropped when its
+    /// `NewMatches` hub update is applied, on every instance with `SSE_PG_NOTIFY`.
+    pub static MATCHES_STATISTICS_CACHE: Lazy<
+        Cache<(i32, BackEndMatchFiltersSearch), Arc<MatchesResultInfo>>,
+    > = Lazy::new(|| {
+        Cache::builder()
+            .max_capacity(20_000)
+            .time_to_live(Duration::from_secs(10 * 60))
+            .support_invalidation_closures()
+            .build()
+    });
+
+    pub fn invalidate_matches_statistics(summoner_ids: HashSet<i32>) {
+        if summoner_ids.is_empty() {
+            return;
+        }
+        let _ = MATCHES_STATISTICS_CACHE
+            .invalidate_entries_if(move |(summoner_id, _), _| summoner_ids.contains(summoner_id));
+    }
+
+    /// Rows are ordered by `(match_end, lol_match_id)` of the participant row, descending.
+    /// `cursor` continues after a previous result (keyset, `page` is ignored),
+    /// `only_match_ids` restricts the returned rows (not the statistics) to those
+    /// `lol_matches.id`, used to prepend matches announced over SSE.
     pub async fn fetch_matches(
         db: &PgPool,
         summoner_identifier: SummonerIdentifier,
         filters: BackEndMatchFiltersSearch,
+        only_match_ids: Option<Vec<i32>>,
+        cursor: Option<MatchesCursor>,
     ) -> AppResult<GetSummonerMatchesResult> {
         let summoner_id =
             crate::backend::server_fns::get_summoner::ssr::resolve_id_by_s_identifier(
@@ -50,24 +141,6 @@ pub mod ssr {
         let page = filters.page.unwrap_or(1) as i32;
         let offset = (page.max(1) - 1) * per_page;
 
-        let start_date = filters.start_date_to_naive();
-        let end_date = filters.end_date_to_naive();
-
-        let mut statistics_query = QueryBuilder::new(
-            r#"
-            SELECT
-               count(*) as total_matches,
-               sum(CASE WHEN lmp.won THEN 1 ELSE 0 END) as total_wins,
-               avg(lmp.kills)                           as avg_kills,
-               avg(lmp.deaths)                          as avg_deaths,
-               avg(lmp.assists)                         as avg_assists,
-               avg(lmp.kda)                             as avg_kda,
-               avg(lmp.kill_participation)              as avg_kill_participation
-            FROM lol_match_participants as lmp
-                     left JOIN lol_matches as lm ON lm.id = lmp.lol_match_id
-            WHERE lmp.summoner_id =
-        "#,
-        );
         let mut participants_query = QueryBuilder::new(
             r#"
             SELECT lmp.id,
@@ -93,94 +166,79 @@ pub mod ssr {
                    lmp.item4_id,
                    lmp.item5_id,
                    lmp.item6_id,
+                   lmp.match_end,
                    lm.match_id       AS riot_match_id,
                    lm.platform       AS platform,
                    lm.queue_id       AS lol_match_queue_id,
                    lm.match_end      AS lol_match_match_end,
-                   lm.match_duration AS lol_match_match_duration
+                   lm.match_duration AS lol_match_match_duration,
+                   EXISTS(SELECT 1 FROM live_game_snapshots as lgs WHERE lgs.match_id = lm.match_id)
+                                     AS has_live_snapshot
             FROM lol_match_participants as lmp
                        JOIN lol_matches as lm
                                 ON lm.id = lmp.lol_match_id
             WHERE lmp.summoner_id =
         "#,
         );
-        statistics_query.push_bind(summoner_id);
-        participants_query.push_bind(summoner_id);
+        push_match_filters(&mut participants_query, summoner_id, &filters);
 
-        if let Some(champion_id) = filters.champion_id {
-            let sql_filter = " AND lmp.champion_id = ";
-            statistics_query.push(sql_filter);
-            statistics_query.push_bind(champion_id as i32);
-            participants_query.push(sql_filter);
-            participants_query.push_bind(champion_id as i32);
+        if let Some(only_match_ids) = only_match_ids.as_ref() {
+            participants_query.push(" AND lm.id = ANY(");
+            participants_query.push_bind(only_match_ids.clone());
+            participants_query.push(")");
+        } else if let Some(cursor) = cursor {
+            participants_query.push(format!(" AND {} < (", MATCHES_ORDER_KEY));
+            participants_query.push_bind(cursor.match_end());
+            participants_query.push(", ");
+            participants_query.push_bind(cursor.lol_match_id());
+            participants_query.push(")");
         }
-        if let Some(queue_id) = filters.queue_id {
-            let sql_filter = " AND lm.queue_id = ";
-            let queue = Queue::from_id_or_custom(queue_id).id() as i32;
-            statistics_query.push(sql_filter);
-            statistics_query.push_bind(queue);
-            participants_query.push(sql_filter);
-            participants_query.push_bind(queue);
+        participants_query.push(format!(
+            " ORDER BY {} DESC LIMIT {}",
+            MATCHES_ORDER_KEY, per_page
+        ));
+        if only_match_ids.is_none() && cursor.is_none() {
+            participants_query.push(" OFFSET ");
+            participants_query.push_bind(offset);
         }
 
-        if let Some(start_date) = start_date {
-            let sql_filter = " AND lm.match_end >= ";
-            statistics_query.push(sql_filter);
-            statistics_query.push_bind(start_date);
-            participants_query.push(sql_filter);
-            participants_query.push_bind(start_date);
-        }
-        if let Some(end_date) = end_date {
-            let sql_filter = " AND lm.match_end <= ";
-            statistics_query.push(sql_filter);
-            statistics_query.push_bind(end_date);
-            participants_query.push(sql_filter);
-            participants_query.push_bind(end_date);
-        }
-
-        participants_query.push(" ORDER BY lm.match_end DESC LIMIT 20 OFFSET ");
-        participants_query.push_bind(offset);
-        let (matches_statistics, matches_participants) = tokio::join!(
-            statistics_query
-                .build_query_as::<MatchesResultInfoModel>()
-                .fetch_one(db),
-            participants_query
-                .build_query_as::<SummonerMatchModel>()
-                .fetch_all(db),
+        let statistics_key = (
+            summoner_id,
+            BackEndMatchFiltersSearch {
+                page: None,
+                ..filters
+            },
         );
-        let matches_statistics = matches_statistics?;
-        let matches_participants = matches_participants?;
-        let total_matches = matches_statistics.total_matches.unwrap_or_default() as u16;
-        let total_pages = (total_matches as f32 / per_page as f32).ceil() as u16;
-
-        let matches_result_info = {
-            MatchesResultInfo {
-                total_matches,
-                total_wins: matches_statistics.total_wins.unwrap_or_default() as u16,
-                avg_kills: matches_statistics
-                    .avg_kills
-                    .unwrap_or_default()
-                    .to_f32()
-                    .unwrap_or_default(),
-                avg_deaths: matches_statistics
-                    .avg_deaths
-                    .unwrap_or_default()
-                    .to_f32()
-                    .unwrap_or_default(),
-                avg_assists: matches_statistics
-                    .avg_assists
-                    .unwrap_or_default()
-                    .to_f32()
-                    .unwrap_or_default(),
-                avg_kill_participation: (matches_statistics
-                    .avg_kill_participation
-                    .unwrap_or_default()
-                    .to_f32()
-                    .unwrap_or_default()
-                    * 100.0)
-                    .round() as u16,
+        let matches_result_info = async {
+            if only_match_ids.is_some() {
+                // new matches were just stored, the cached numbers are behind
+                let info = Arc::new(fetch_matches_statistics(db, summoner_id, &filters).await?);
+                MATCHES_STATISTICS_CACHE
+                    .insert(statistics_key, info.clone())
+                    .await;
+                Ok(info)
+            } else {
+                MATCHES_STATISTICS_CACHE
+                    .try_get_with(statistics_key, async {
+                        fetch_matches_statistics(db, summoner_id, &filters)
+                            .await
+                            .map(Arc::new)
+                    })
+                    .await
+                    .map_err(|e| (*e).clone())
             }
         };
+        let matches_participants = async {
+            participants_query
+                .build_query_as::<SummonerMatchModel>()
+                .fetch_all(db)
+                .await
+                .map_err(AppError::from)
+        };
+        let (matches_result_info, matches_participants) =
+            tokio::try_join!(matches_result_info, matches_participants)?;
+        let matches_result_info = (*matches_result_info).clone();
+        let total_pages = matches_result_info.total_matches.div_ceil(per_page as u32) as u16;
 
         let matches_ids: Vec<_> = matches_participants
             .iter()
@@ -224,6 +282,8 @@ pub mod ssr {
                     item4_id: row.item4_id.unwrap_or_default() as u32,
                     item5_id: row.item5_id.unwrap_or_default() as u32,
                     item6_id: row.item6_id.unwrap_or_default() as u32,
+                    has_live_snapshot: row.has_live_snapshot,
+                    position: MatchesCursor::new(row.match_end, row.lol_match_id),
                     participants: vec![],
                 }
             })
@@ -290,10 +350,101 @@ pub mod ssr {
                 match_.participants = participants.clone();
             }
         }
+        // a short chunk is the end of the history
+        let next_cursor = matches
+            .last()
+            .filter(|_| only_match_ids.is_none() && matches.len() == per_page as usize)
+            .map(|match_| match_.position);
         Ok(GetSummonerMatchesResult {
             matches,
             total_pages,
             matches_result_info,
+            next_cursor,
+        })
+    }
+
+    /// Sort key of the match history, compared as a row for the keyset. Served by
+    /// `idx_lmp_summoner_match_end`, so a page stops after its rows whatever its depth.
+    const MATCHES_ORDER_KEY: &str = "(lmp.match_end, lmp.lol_match_id)";
+
+    /// Pushes the bound `summoner_id` after a trailing `lmp.summoner_id =`, then every
+    /// filter except the page, `lmp` / `lm` being the participant row and its match.
+    pub fn push_match_filters(
+        query: &mut QueryBuilder<'_, Postgres>,
+        summoner_id: i32,
+        filters: &BackEndMatchFiltersSearch,
+    ) {
+        query.push_bind(summoner_id);
+        if let Some(champion_id) = filters.champion_id {
+            query.push(" AND lmp.champion_id = ");
+            query.push_bind(champion_id as i32);
+        }
+        if let Some(queue_id) = filters.queue_id {
+            query.push(" AND lm.queue_id = ");
+            query.push_bind(Queue::from_id_or_custom(queue_id).id() as i32);
+        }
+        filters.push_patch_filter(query);
+        push_advanced_filters(query, filters);
+        if let Some(start_date) = filters.start_date_to_naive() {
+            query.push(" AND lm.match_end >= ");
+            query.push_bind(start_date);
+        }
+        if let Some(end_date) = filters.end_date_to_naive() {
+            query.push(" AND lm.match_end <= ");
+            query.push_bind(end_date);
+        }
+    }
+
+    async fn fetch_matches_statistics(
+        db: &PgPool,
+        summoner_id: i32,
+        filters: &BackEndMatchFiltersSearch,
+    ) -> AppResult<MatchesResultInfo> {
+        let mut statistics_query = QueryBuilder::new(
+            r#"
+            SELECT
+               count(*) as total_matches,
+               sum(CASE WHEN lmp.won THEN 1 ELSE 0 END) as total_wins,
+               avg(lmp.kills)                           as avg_kills,
+               avg(lmp.deaths)                          as avg_deaths,
+               avg(lmp.assists)                         as avg_assists,
+               avg(lmp.kda)                             as avg_kda,
+               avg(lmp.kill_participation)              as avg_kill_participation
+            FROM lol_match_participants as lmp
+                     left JOIN lol_matches as lm ON lm.id = lmp.lol_match_id
+            WHERE lmp.summoner_id =
+        "#,
+        );
+        push_match_filters(&mut statistics_query, summoner_id, filters);
+        let matches_statistics = statistics_query
+            .build_query_as::<MatchesResultInfoModel>()
+            .fetch_one(db)
+            .await?;
+        Ok(MatchesResultInfo {
+            total_matches: matches_statistics.total_matches.unwrap_or_default() as u32,
+            total_wins: matches_statistics.total_wins.unwrap_or_default() as u32,
+            avg_kills: matches_statistics
+                .avg_kills
+                .unwrap_or_default()
+                .to_f32()
+                .unwrap_or_default(),
+            avg_deaths: matches_statistics
+                .avg_deaths
+                .unwrap_or_default()
+                .to_f32()
+                .unwrap_or_default(),
+            avg_assists: matches_statistics
+                .avg_assists
+                .unwrap_or_default()
+                .to_f32()
+                .unwrap_or_default(),
+            avg_kill_participation: (matches_statistics
+                .avg_kill_participation
+                .unwrap_or_default()
+                .to_f32()
+                .unwrap_or_default()
+                * 100.0)
+                .round() as u16,
         })
     }
 
@@ -390,9 +541,11 @@ pub mod ssr {
         pub item4_id: Option<i64>,
         pub item5_id: Option<i64>,
         pub item6_id: Option<i64>,
+        pub match_end: NaiveDateTime,
         pub lol_match_queue_id: Option<i32>,
         pub lol_match_match_end: Option<NaiveDateTime>,
         pub lol_match_match_duration: Option<i32>,
+        pub has_live_snapshot: bool,
     }
 
     #[derive(FromRow)]
@@ -0,0 +1,119 @@
+use crate::views::champion_stats_page::PatchChampion;
+use leptos::prelude::*;
+use leptos::server;
+use leptos::server_fn::codec::Bitcode;
+
+#[server(input=Bitcode,output=Bitcode)]
+pub async fn get_patch_champion(
+    champion_id: u16,
+    version: Option<String>,
+    queue_id: u16,
+) -> Result<PatchChampion, ServerFnError> {
+    let state = expect_context::<crate::ssr::AppState>();
+    let db = state.db.clone();
+    ssr::inner_get_patch_champion(&db, champion_id, version, queue_id)
+        .await
+        .map_err(|e| e.to_server_fn_error())
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use crate::backend::server_fns::get_patch_champions::ssr::{
+        champion_stats_queue, get_champion_patch_stats, get_patches, resolve_patch,
+    };
+    use crate::backend::ssr::{AppError, AppResult};
+    use crate::views::champion_stats_page::{ChampionBuild, ChampionBuildKind, PatchChampion};
+    use common::consts::champion::Champion;
+    use moka::future::Cache;
+    use once_cell::sync::Lazy;
+    use sqlx::{FromRow, PgPool};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// (champion, version, queue) -> champion page, cleared by `UpdateChampionStatsTask`.
+    pub static PATCH_CHAMPION_CACHE: Lazy<Cache<(u16, String, u16), Arc<PatchChampion>>> =
+        Lazy::new(|| {
+            Cache::builder()
+                .max_capacity(5_000)
+                .time_to_live(Duration::from_secs(30 * 60))
+                .build()
+        });
+
+    pub async fn inner_get_patch_champion(
+        db: &PgPool,
+        champion_id: u16,
+        version: Option<String>,
+        queue_id: u16,
+    ) -> AppResult<PatchChampion> {
+        if Champion::try_from(champion_id).is_err() {
+            return Err(AppError::NotFound);
+        }
+        let queue = champion_stats_queue(queue_id);
+        let patches = get_patches(db).await?;
+        let Some(version) = resolve_patch(&patches, version) else {
+            return Ok(PatchChampion::default());
+        };
+        PATCH_CHAMPION_CACHE
+            .try_get_with((champion_id, version.clone(), queue.id()), async move {
+                let (stats, builds) = tokio::try_join!(
+                    get_champion_patch_stats(db, &version, queue, Some(champion_id)),
+                    get_champion_patch_builds(db, &version, queue.id(), champion_id),
+                )?;
+                Ok(Arc::new(PatchChampion {
+                    patches,
+                    version,
+                    stats: stats.into_iter().next(),
+                    builds,
+                }))
+            })
+            .await
+            .map(|patch_champion| (*patch_champion).clone())
+            .map_err(|e| (*e).clone())
+    }
+
+    async fn get_champion_patch_builds(
+        db: &PgPool,
+        version: &str,
+        queue_id: u16,
+        champion_id: u16,
+    ) -> AppResult<Vec<ChampionBuild>> {
+        Ok(sqlx::query_as::<_, ChampionBuildModel>(
+            r#"
+            SELECT kind, build, total_matches, total_wins
+            FROM champion_patch_builds
+            WHERE version = $1
+              AND queue_id = $2
+              AND champion_id = $3
+            ORDER BY kind, total_matches DESC
+            "#,
+        )
+        .bind(version)
+        .bind(queue_id as i32)
+        .bind(champion_id as i32)
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .filter_map(|row| {
+            Some(ChampionBuild {
+                kind: ChampionBuildKind::from_id(row.kind as u8)?,
+                build: row
+                    .build
+                    .into_iter()
+                    .map(|id| id.unwrap_or_default().max(0) as u32)
+                    .collect(),
+                total_matches: row.total_matches as u32,
+                total_wins: row.total_wins as u32,
+            })
+        })
+        .collect())
+    }
+
+    #[derive(FromRow)]
+    struct ChampionBuildModel {
+        pub kind: i16,
+        /// Rune pages of old games can hold NULL shards.
+        pub build: Vec<Option<i32>>,
+        pub total_matches: i32,
+        pub total_wins: i32,
+    }
+}
@@ -0,0 +1,160 @@
+use crate::views::champion_stats_page::PatchChampions;
+use leptos::prelude::*;
+use leptos::server;
+use leptos::server_fn::codec::Bitcode;
+
+#[server(input=Bitcode,output=Bitcode)]
+pub async fn get_patch_champions(
+    version: Option<String>,
+    queue_id: u16,
+) -> Result<PatchChampions, ServerFnError> {
+    let state = expect_context::<crate::ssr::AppState>();
+    let db = state.db.clone();
+    ssr::inner_get_patch_champions(&db, version, queue_id)
+        .await
+        .map_err(|e| e.to_server_fn_error())
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use crate::backend::ssr::AppResult;
+    use crate::views::champion_stats_page::{
+        ChampionPatchStats, PatchChampions, CHAMPION_STATS_QUEUES,
+    };
+    use common::consts::queue::Queue;
+    use moka::future::Cache;
+    use once_cell::sync::Lazy;
+    use sqlx::{FromRow, PgPool};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// (version, queue) -> every champion of the patch, cleared by `UpdateChampionStatsTask`.
+    pub static PATCH_CHAMPIONS_CACHE: Lazy<Cache<(String, u16), Arc<PatchChampions>>> =
+        Lazy::new(|| {
+            Cache::builder()
+                .max_capacity(200)
+                .time_to_live(Duration::from_secs(30 * 60))
+                .build()
+        });
+
+    pub async fn inner_get_patch_champions(
+        db: &PgPool,
+        version: Option<String>,
+        queue_id: u16,
+    ) -> AppResult<PatchChampions> {
+        let queue = champion_stats_queue(queue_id);
+        let patches = get_patches(db).await?;
+        let Some(version) = resolve_patch(&patches, version) else {
+            return Ok(PatchChampions::default());
+        };
+        PATCH_CHAMPIONS_CACHE
+            .try_get_with((version.clone(), queue.id()), async move {
+                let champions = get_champion_patch_stats(db, &version, queue, None).await?;
+                Ok(Arc::new(PatchChampions {
+                    patches,
+                    version,
+                    champions,
+                }))
+            })
+            .await
+            .map(|patch_champions| (*patch_champions).clone())
+            .map_err(|e| (*e).clone())
+    }
+
+    /// Unknown queues fall back to solo/duo so the cache key space stays small.
+    pub fn champion_stats_queue(queue_id: u16) -> Queue {
+        CHAMPION_STATS_QUEUES
+            .into_iter()
+            .find(|queue| queue.id() == queue_id)
+            .unwrap_or(Queue::SummonersRift5v5RankedSolo)
+    }
+
+    /// Aggregated patches, newest first.
+    pub async fn get_patches(db: &PgPool) -> AppResult<Vec<String>> {
+        Ok(sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT version
+            FROM patch_queue_stats
+            GROUP BY version
+            ORDER BY string_to_array(version, '.')::INT[] DESC
+            "#,
+        )
+        .fetch_all(db)
+        .await?)
+    }
+
+    /// The requested patch when it was aggregated, the latest one otherwise.
+    pub fn resolve_patch(patches: &[String], version: Option<String>) -> Option<String> {
+        version
+            .filter(|version| patches.contains(version))
+            .or_else(|| patches.first().cloned())
+    }
+
+    /// Pick and ban rates are relative to the stored matches of the patch and queue.
+    pub async fn get_champion_patch_stats(
+        db: &PgPool,
+        version: &str,
+        queue: Queue,
+        champion_id: Option<u16>,
+    ) -> AppResult<Vec<ChampionPatchStats>> {
+        Ok(sqlx::query_as::<_, ChampionPatchStatsModel>(
+            r#"
+            SELECT cps.champion_id,
+                   cps.total_matches,
+                   cps.total_wins,
+                   cps.total_bans,
+                   cps.avg_kills,
+                   cps.avg_deaths,
+                   cps.avg_assists,
+                   pqs.total_matches           AS patch_total_matches,
+                   pqs.total_matches_with_bans AS patch_total_matches_with_bans
+            FROM champion_patch_stats as cps
+                     JOIN patch_queue_stats as pqs
+                          ON pqs.version = cps.version AND pqs.queue_id = cps.queue_id
+            WHERE cps.version = $1
+              AND cps.queue_id = $2
+              AND ($3::INT IS NULL OR cps.champion_id = $3)
+            ORDER BY cps.total_matches DESC
+            "#,
+        )
+        .bind(version)
+        .bind(queue.id() as i32)
+        .bind(champion_id.map(|champion_id| champion_id as i32))
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .map(|row| {
+            let rate = |count: i32, total: i32| {
+                if total > 0 {
+                    count as f32 * 100.0 / total as f32
+                } else {
+                    0.0
+                }
+            };
+            ChampionPatchStats {
+                champion_id: row.champion_id as u16,
+                total_matches: row.total_matches as u32,
+                total_wins: row.total_wins as u32,
+                pick_rate: rate(row.total_matches, row.patch_total_matches),
+                ban_rate: rate(row.total_bans, row.patch_total_matches_with_bans),
+                avg_kills: row.avg_kills,
+                avg_deaths: row.avg_deaths,
+                avg_assists: row.avg_assists,
+            }
+        })
+        .collect())
+    }
+
+    #[derive(FromRow)]
+    struct ChampionPatchStatsModel {
+        pub champion_id: i32,
+        pub total_matches: i32,
+        pub total_wins: i32,
+        pub total_bans: i32,
+        pub avg_kills: f32,
+        pub avg_deaths: f32,
+        pub avg_assists: f32,
+        pub patch_total_matches: i32,
+        pub patch_total_matches_with_bans: i32,
+    }
+}
@@ -1,6 +1,6 @@
 use crate::app::SummonerIdentifier;
 #[cfg(feature = "ssr")]
-use crate::utils::summoner_not_found_url;
+use crate::utils::{summoner_not_found_url, summoner_url};
 use crate::views::summoner_page::Summoner;
 use leptos::prelude::*;
 use leptos::server;
@@ -10,14 +10,56 @@ use leptos::server_fn::codec::Bitcode;
 pub async fn get_summoner(
     summoner_identifier: SummonerIdentifier,
 ) -> Result<Summoner, ServerFnError> {
-    use crate::backend::server_fns::get_summoner::ssr::resolve_summoner_by_s_identifier;
+    use crate::backend::server_fns::get_summoner::ssr::{
+        find_renamed_summoner, resolve_summoner_by_s_identifier,
+    };
+    use crate::ssr::S_IDENTIFIER_TO_ID;
     //log!("Server::Fetching summoner: {}", summoner_slug);
     let state = expect_context::<crate::ssr::AppState>();
     let db = state.db.clone();
 
+    let redirect_permanently = |summoner: &Summoner| {
+        leptos_axum::redirect(
+            summoner_url(
+                summoner.platform.code(),
+                summoner.game_name.as_str(),
+                summoner.tag_line.as_str(),
+            )
+            .as_str(),
+        );
+        // `redirect` sets a 302 for an HTML render only, upgrade that one: a server fn
+        // fetch must keep leptos' redirect header for the client router
+        let is_html_render = use_context::<http::request::Parts>().is_some_and(|parts| {
+            parts
+                .headers
+                .get(http::header::ACCEPT)
+                .and_then(|accept| accept.to_str().ok())
+                .is_some_and(|accept| accept.contains("text/html"))
+        });
+        if is_html_render {
+            let response = expect_context::<leptos_axum::ResponseOptions>();
+            response.set_status(http::StatusCode::MOVED_PERMANENTLY);
+        }
+    };
+
     match resolve_summoner_by_s_identifier(&db, &summoner_identifier).await {
-        Ok(summoner) => Ok(summoner),
+        Ok(summoner) => {
+            // slug cached before a rename: send it to the current Riot ID
+            if summoner.game_name != summoner_identifier.game_name
+                || !summoner
+                    .tag_line
+                    .eq_ignore_ascii_case(summoner_identifier.tag_line.as_str())
+            {
+                S_IDENTIFIER_TO_ID.invalidate(&summoner_identifier).await;
+                redirect_permanently(&summoner);
+            }
+            Ok(summoner)
+        }
         Err(e) => {
+            if let Ok(Some(summoner)) = find_renamed_summoner(&db, &summoner_identifier).await {
+                redirect_permanently(&summoner);
+                return Ok(summoner);
+            }
             leptos_axum::redirect(
                 summoner_not_found_url(
                     summoner_identifier.platform_route.code(),
@@ -46,13 +88,63 @@ pub mod ssr {
         db: &PgPool,
         summoner_identifier: &SummonerIdentifier,
     ) -> AppResult<Summoner> {
-        if let Ok(id) = resolve_id_by_s_identifier(db, summoner_identifier).await {
-            Ok(find_summoner_by_id(db, id).await?)
-        } else {
-            Err(AppError::CustomError("summoner not found".to_string()))
+        // an unknown summoner is a `CustomError`, db errors pass through
+        let id = resolve_id_by_s_identifier(db, summoner_identifier).await?;
+        let mut summoner = find_summoner_by_id(db, id).await?;
+        summoner.previous_names = find_previous_names(db, id).await?;
+        Ok(summoner)
+    }
+
+    /// Summoner that used to be known as `s_identifier`, most recent rename first.
+    pub async fn find_renamed_summoner(
+        db: &PgPool,
+        s_identifier: &SummonerIdentifier,
+    ) -> AppResult<Option<Summoner>> {
+        let summoner_id = sqlx::query_as::<_, Id>(
+            r#"
+            SELECT
+               snh.summoner_id    as id
+            FROM summoner_name_history as snh
+            WHERE snh.platform = $1 and snh.game_name = $2
+              AND lower(snh.tag_line) = lower($3)
+            ORDER BY snh.changed_at DESC
+            LIMIT 1
+  "#,
+        )
+        .bind(PlatformRouteDb::from(s_identifier.platform_route))
+        .bind(s_identifier.game_name.as_str())
+        .bind(s_identifier.tag_line.as_str())
+        .fetch_optional(db)
+        .await?
+        .map(|id| id.id);
+        match summoner_id {
+            Some(summoner_id) => Ok(Some(find_summoner_by_id(db, summoner_id).await?)),
+            None => Ok(None),
         }
     }
 
+    pub async fn find_previous_names(
+        db: &PgPool,
+        summoner_id: i32,
+    ) -> AppResult<Vec<(String, String)>> {
+        Ok(sqlx::query_as::<_, (String, String)>(
+            r#"
+            SELECT
+               snh.game_name,
+               snh.tag_line
+            FROM summoner_name_history as snh
+            JOIN summoners as ss ON ss.id = snh.summoner_id
+            WHERE snh.summoner_id = $1
+              AND NOT (snh.game_name = ss.game_name AND snh.tag_line = ss.tag_line)
+            ORDER BY snh.changed_at DESC
+            LIMIT 10
+  "#,
+        )
+        .bind(summoner_id)
+        .fetch_all(db)
+        .await?)
+    }
+
     pub async fn resolve_id_by_s_identifier(
         db: &PgPool,
         s_identifier: &SummonerIdentifier,
@@ -119,6 +211,7 @@ pub mod ssr {
             summoner_level: summoner_db.summoner_level as u16,
             profile_icon_id: summoner_db.profile_icon_id as u16,
             pro_slug: summoner_db.pro_slug.map(|s| ProPlayerSlug::new(s.as_str())),
+            previous_names: Vec::new(),
         })?)
     }
 
@@ -12,6 +12,8 @@ pub async fn search_summoner(
     tag_line: String,
 ) -> Result<Option<String>, ServerFnError> {
     use crate::app::SummonerIdentifier;
+    use crate::backend::riot_budget::RiotCallPriority;
+    use crate::backend::riot_cache;
     use crate::backend::server_fns::get_summoner::ssr::{
         find_summoner_by_id, resolve_id_by_s_identifier,
     };
@@ -45,20 +47,23 @@ pub async fn search_summoner(
                 identifier.tag_line.as_ref(),
             );
             let riot_api = state.riot_api.clone();
-            match riot_api
-                .account_v1()
-                .get_by_riot_id(
-                    riven_pr.to_regional(),
-                    identifier.game_name.as_ref(),
-                    identifier.tag_line.as_ref(),
-                )
-                .await
+            match riot_cache::account_by_riot_id(
+                &riot_api,
+                riven_pr.to_regional(),
+                identifier.game_name.as_ref(),
+                identifier.tag_line.as_ref(),
+                RiotCallPriority::Interactive,
+            )
+            .await
             {
                 Ok(Some(account)) => {
-                    match riot_api
-                        .summoner_v4()
-                        .get_by_puuid(riven_pr, account.puuid.as_str())
-                        .await
+                    match riot_cache::summoner_by_puuid(
+                        &riot_api,
+                        riven_pr,
+                        account.puuid.as_str(),
+                        RiotCallPriority::Interactive,
+                    )
+                    .await
                     {
                         Ok(Some(summoner_data)) => {
                             let redirect_url = summoner_url(
@@ -168,7 +173,7 @@ pub mod ssr {
         summoner: riven::models::summoner_v4::Summoner,
     ) -> AppResult<()> {
         sqlx::query(
-            "UPDATE summoners SET game_name = $1, tag_line = $2, puuid = $3, summoner_level = $4, profile_icon_id = $5, platform = $6, updated_at = NOW() WHERE id = $8"
+            "UPDATE summoners SET game_name = $1, tag_line = $2, puuid = $3, summoner_level = $4, profile_icon_id = $5, platform = $6, updated_at = $7 WHERE id = $8"
         )
             .bind(account.game_name.unwrap_or_default().trim())
             .bind(account.tag_line.unwrap_or_default().trim())
@@ -0,0 +1,60 @@
+use common::consts::platform_route::PlatformRoute;
+use leptos::prelude::*;
+use leptos::server;
+use leptos::server_fn::codec::Bitcode;
+
+/// Adds/removes summoner topics on a `/sse/stream` connection, identified by the
+/// id of its `SseMultiplexEvent::Connected` message.
+#[server(input=Bitcode,output=Bitcode)]
+pub async fn update_sse_subscriptions(
+    connection_id: u64,
+    add: Vec<(PlatformRoute, i32)>,
+    remove: Vec<i32>,
+) -> Result<(), ServerFnError> {
+    use crate::sse::SubscriptionChange;
+    let state = expect_context::<crate::ssr::AppState>();
+    let hub = state.hub.clone();
+    // every added topic may cost a spectator call: unknown summoners are dropped
+    let add = if add.is_empty() {
+        add
+    } else {
+        ssr::retain_known_summoners(&state.db, add).await?
+    };
+    let mut connected = true;
+    if !remove.is_empty() {
+        connected &= hub.change_subscriptions(connection_id, SubscriptionChange::Remove(remove));
+    }
+    if !add.is_empty() {
+        connected &= hub.change_subscriptions(connection_id, SubscriptionChange::Add(add));
+    }
+    if connected {
+        Ok(())
+    } else {
+        Err(ServerFnError::new("Unknown SSE connection"))
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use crate::backend::ssr::AppResult;
+    use common::consts::platform_route::PlatformRoute;
+    use sqlx::PgPool;
+    use std::collections::HashSet;
+
+    pub async fn retain_known_summoners(
+        db: &PgPool,
+        topics: Vec<(PlatformRoute, i32)>,
+    ) -> AppResult<Vec<(PlatformRoute, i32)>> {
+        let ids = topics.iter().map(|(_, id)| *id).collect::<Vec<_>>();
+        let known = sqlx::query_scalar::<_, i32>("SELECT id FROM summoners WHERE id = ANY($1)")
+            .bind(&ids)
+            .fetch_all(db)
+            .await?
+            .into_iter()
+            .collect::<HashSet<_>>();
+        Ok(topics
+            .into_iter()
+            .filter(|(_, id)| known.contains(id))
+            .collect())
+    }
+}
@@ -4,6 +4,7 @@ use crate::backend::server_fns::get_encounter::ssr::find_summoner_puuid_by_id;
 use crate::backend::server_fns::search_summoner::ssr::insert_or_update_account_and_summoner;
 #[cfg(feature = "ssr")]
 use crate::utils::summoner_url;
+use common::consts::match_type::MatchType;
 use common::consts::platform_route::PlatformRoute;
 #[cfg(feature = "ssr")]
 use leptos::logging::log;
@@ -19,21 +20,36 @@ pub async fn update_summoner(
     game_name: String,
     tag_line: String,
     platform_route: PlatformRoute,
+    queue_id: Option<u16>,
+    match_type: Option<MatchType>,
 ) -> Result<Option<(u16, u16)>, ServerFnError> {
     use crate::app::SummonerIdentifier;
+    use crate::backend::riot_budget::RiotCallPriority;
+    use crate::backend::riot_cache;
     use crate::ssr::S_IDENTIFIER_TO_ID;
     let state = expect_context::<crate::ssr::AppState>();
     let riot_api = state.riot_api.clone();
     let max_matches = state.max_matches;
+    let ingestion_queue = state.ingestion_queue.clone();
+    let hub = state.hub.clone();
     let db = state.db.clone();
     let puuid = find_summoner_puuid_by_id(&db, summoner_id).await?;
+    let riven_pr = platform_route.to_riven();
+    // an explicit refresh must not be served from the cache, concurrent clicks still coalesce
+    riot_cache::invalidate_summoner(riven_pr.to_regional(), riven_pr, puuid.as_str()).await;
     let (account, summoner) = tokio::join!(
-        riot_api
-            .account_v1()
-            .get_by_puuid(platform_route.to_riven().to_regional(), puuid.as_str()),
-        riot_api
-            .summoner_v4()
-            .get_by_puuid(platform_route.to_riven(), puuid.as_str())
+        riot_cache::account_by_puuid(
+            &riot_api,
+            riven_pr.to_regional(),
+            puuid.as_str(),
+            RiotCallPriority::Interactive,
+        ),
+        riot_cache::summoner_by_puuid(
+            &riot_api,
+            riven_pr,
+            puuid.as_str(),
+            RiotCallPriority::Interactive,
+        )
     );
     if let (Ok(account), Ok(Some(summoner))) = (account, summoner) {
         let inner_db = db.clone();
@@ -51,16 +67,34 @@ pub async fn update_summoner(
         };
         S_IDENTIFIER_TO_ID.invalidate(&acc_identifier).await;
 
+        let (profile_game_name, profile_tag_line) = (
+            acc_game_name.trim().to_string(),
+            acc_tag_line.trim().to_string(),
+        );
         tokio::spawn(async move {
             insert_or_update_account_and_summoner(&db, platform_route, account, summoner)
                 .await
                 .unwrap();
+            hub.profile_changed(
+                summoner_id,
+                profile_game_name,
+                profile_tag_line,
+                lvl_profile_icon_id.0,
+                lvl_profile_icon_id.1,
+            );
             match ssr::update_summoner_default_matches(
                 inner_db,
                 riot_api,
+                summoner_id,
                 puuid,
                 platform_route.to_riven(),
                 max_matches,
+                ssr::MatchIdsSyncFilter {
+                    queue_id,
+                    match_type,
+                },
+                ingestion_queue,
+                hub,
             )
             .await
             {
@@ -92,22 +126,70 @@ pub async fn update_summoner(
 
 #[cfg(feature = "ssr")]
 pub mod ssr {
+    use crate::backend::ingestion_queue::IngestionQueue;
+    use crate::backend::riot_budget::RiotCallPriority;
     use crate::backend::ssr::{AppResult, Id, PlatformRouteDb};
-    use crate::ssr::RiotApiState;
+    use crate::sse::Hub;
+    use crate::ssr::{RiotApiState, RIOT_BUDGET};
+    use chrono::{NaiveDateTime, Utc};
+    use common::consts::match_type::MatchType;
+    use common::consts::queue::Queue;
     use leptos::logging::log;
     use riven::consts::RegionalRoute;
     use riven::RiotApi;
-    use std::collections::HashSet;
+    use std::collections::{HashMap, HashSet};
+    use std::sync::Arc;
+
+    /// Games still running at the previous sync start before its cursor but end after it,
+    /// so the next `startTime` is moved back by the longest expected game.
+    const SYNC_OVERLAP_SECS: i64 = 3600;
+
+    /// Optional `queue`/`type` filters of a match-ids sync; each combination keeps its own cursor.
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct MatchIdsSyncFilter {
+        pub queue_id: Option<u16>,
+        pub match_type: Option<MatchType>,
+    }
+
+    impl MatchIdsSyncFilter {
+        fn queue(&self) -> Option<riven::consts::Queue> {
+            self.queue_id.map(|id| Queue::from_id_or_custom(id).to_riven())
+        }
+
+        fn db_queue_id(&self) -> i32 {
+            self.queue_id.unwrap_or_default() as i32
+        }
+
+        fn db_match_type(&self) -> &'static str {
+            self.match_type.map(|t| t.api_code()).unwrap_or_default()
+        }
+    }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn update_summoner_default_matches(
         db: sqlx::PgPool,
         api: RiotApiState,
+        summoner_id: i32,
         puuid: String,
         platform: riven::consts::PlatformRoute,
         max_matches: usize,
+        filter: MatchIdsSyncFilter,
+        ingestion_queue: Arc<IngestionQueue>,
+        hub: Arc<Hub>,
     ) -> AppResult<()> {
-        let match_ids =
-            fetch_all_match_ids(&api, platform.to_regional(), &puuid, max_matches).await?;
+        let sync_started_at = Utc::now().naive_utc();
+        let start_time = find_synced_until(&db, summoner_id, filter)
+            .await?
+            .map(|synced_until| synced_until.and_utc().timestamp() - SYNC_OVERLAP_SECS);
+        let match_ids = fetch_all_match_ids(
+            &api,
+            platform.to_regional(),
+            &puuid,
+            max_matches,
+            filter,
+            start_time,
+        )
+        .await?;
 
         // Fetch existing match IDs from the database
         let existing_match_ids: HashSet<String> =
@@ -120,8 +202,9 @@ pub mod ssr {
 
         // Filter out matches that are already saved
         let new_riot_match_ids: Vec<String> = match_ids
-            .into_iter()
-            .filter(|id| !existing_match_ids.contains(id))
+            .iter()
+            .filter(|id| !existing_match_ids.contains(*id))
+            .cloned()
             .collect();
 
         log!(
@@ -131,10 +214,75 @@ pub mod ssr {
         );
         //let t = std::time::Instant::now();
         if !new_riot_match_ids.is_empty() {
-            bulk_insert_default_match(&db, &new_riot_match_ids).await
-        } else {
-            Ok(())
+            bulk_insert_default_match(&db, &new_riot_match_ids).await?;
         }
+
+        // Queue every match of this summoner still waiting for details, newest first,
+        // so the update task serves them before the global backlog.
+        let not_updated = find_not_updated_match_ids(&db, &match_ids).await?;
+        let priority_ids = match_ids
+            .iter()
+            .filter_map(|match_id| not_updated.get(match_id).copied())
+            .collect::<Vec<_>>();
+        if !priority_ids.is_empty() {
+            let progress = ingestion_queue.push(summoner_id, priority_ids);
+            hub.set_ingestion_progress(summoner_id, progress.done, progress.total);
+        }
+        set_synced_until(&db, summoner_id, filter, sync_started_at).await
+    }
+
+    /// Newest cursor covering `filter`; an unfiltered sync also covers every filtered one.
+    async fn find_synced_until(
+        db: &sqlx::PgPool,
+        summoner_id: i32,
+        filter: MatchIdsSyncFilter,
+    ) -> AppResult<Option<NaiveDateTime>> {
+        Ok(sqlx::query_scalar::<_, Option<NaiveDateTime>>(
+            "SELECT MAX(synced_until) FROM summoner_match_syncs
+            WHERE summoner_id = $1
+              AND ((queue_id = $2 AND match_type = $3) OR (queue_id = 0 AND match_type = ''))",
+        )
+        .bind(summoner_id)
+        .bind(filter.db_queue_id())
+        .bind(filter.db_match_type())
+        .fetch_one(db)
+        .await?)
+    }
+
+    async fn set_synced_until(
+        db: &sqlx::PgPool,
+        summoner_id: i32,
+        filter: MatchIdsSyncFilter,
+        synced_until: NaiveDateTime,
+    ) -> AppResult<()> {
+        sqlx::query(
+            "INSERT INTO summoner_match_syncs (summoner_id, queue_id, match_type, synced_until)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (summoner_id, queue_id, match_type)
+            DO UPDATE SET synced_until = GREATEST(summoner_match_syncs.synced_until, EXCLUDED.synced_until)",
+        )
+        .bind(summoner_id)
+        .bind(filter.db_queue_id())
+        .bind(filter.db_match_type())
+        .bind(synced_until)
+        .execute(db)
+        .await?;
+        Ok(())
+    }
+
+    async fn find_not_updated_match_ids(
+        db: &sqlx::PgPool,
+        match_ids: &[String],
+    ) -> AppResult<HashMap<String, i32>> {
+        Ok(sqlx::query_as::<_, (i32, String)>(
+            "SELECT id, match_id FROM lol_matches WHERE match_id = ANY($1) AND updated = false",
+        )
+        .bind(match_ids)
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .map(|(id, match_id)| (match_id, id))
+        .collect())
     }
 
     async fn fetch_all_match_ids(
@@ -142,8 +290,14 @@ pub mod ssr {
         region: RegionalRoute,
         puuid: &str,
         max_matches: usize,
+        filter: MatchIdsSyncFilter,
+        start_time: Option<i64>,
     ) -> AppResult<Vec<String>> {
-        let max_fetch_limit = max_matches.min(100);
+        let max_fetch_limit = if max_matches == 0 {
+            100
+        } else {
+            max_matches.min(100)
+        };
         let mut matches_list = Vec::new();
         let mut begin_index = 0;
 
@@ -154,17 +308,21 @@ pub mod ssr {
                 puuid,
                 Some(max_fetch_limit as i32),
                 Some(begin_index),
+                filter,
+                start_time,
             )
             .await?;
-            if fetched_matches.is_empty() {
-                break;
-            }
-            begin_index += fetched_matches.len() as i32;
+            let fetched_len = fetched_matches.len();
+            begin_index += fetched_len as i32;
             matches_list.extend(fetched_matches);
             if max_matches != 0 && matches_list.len() >= max_matches {
                 matches_list.truncate(max_matches);
                 break;
             }
+            // a short page is the last one, no need to ask for an empty one
+            if fetched_len < max_fetch_limit {
+                break;
+            }
         }
 
         Ok(matches_list)
@@ -176,9 +334,23 @@ pub mod ssr {
         puuid: &str,
         count: Option<i32>,
         start: Option<i32>,
+        filter: MatchIdsSyncFilter,
+        start_time: Option<i64>,
     ) -> AppResult<Vec<String>> {
+        RIOT_BUDGET
+            .acquire(region, RiotCallPriority::Interactive)
+            .await;
         api.match_v5()
-            .get_match_ids_by_puuid(region, puuid, count, None, None, None, start, None)
+            .get_match_ids_by_puuid(
+                region,
+                puuid,
+                count,
+                None,
+                filter.queue(),
+                start_time,
+                start,
+                filter.match_type.map(|t| t.api_code()),
+            )
             .await
             .map_err(|e| e.into())
     }
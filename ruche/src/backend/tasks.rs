@@ -4,6 +4,8 @@ use tokio::time::Instant;
 pub mod daily_sql_clean;
 pub mod generate_sitemap;
 pub mod handle_live_game_cache;
+pub mod update_champion_stats;
+pub mod update_leaderboards;
 pub mod update_matches;
 pub mod update_pro_players;
 
@@ -47,6 +47,9 @@ impl Task for DailySqlCleanTask {
             for row in table_info {
                 log!("{}", row);
             }
+
+            let evicted = crate::og_image::evict_stale_summoner_cards().await;
+            log!("Daily Clean Task: {} stale summoner preview cards deleted", evicted);
         })
     }
 
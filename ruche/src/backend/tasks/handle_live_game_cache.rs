@@ -1,13 +1,21 @@
+use crate::backend::ingestion_queue::IngestionQueue;
 use crate::backend::live_game_cache::LiveGameCache;
+use crate::backend::post_game_queue::PostGameQueue;
+use crate::backend::riot_budget::RiotCallPriority;
+use crate::backend::riot_cache;
 use crate::backend::server_fns::get_live_game::ssr::{
-    game_info_to_live_game, get_all_participants_live_game_stats,
+    game_info_to_live_game, get_all_participants_live_game_stats, live_game_changed,
 };
+use crate::backend::server_fns::get_live_game_snapshot::ssr::save_live_game_snapshots;
+use crate::backend::server_fns::update_summoner::ssr::bulk_insert_default_match;
 use crate::backend::ssr::{AppResult, PlatformRouteDb};
 use crate::backend::task_director::Task;
 use crate::sse::Hub;
+use crate::ssr::RIOT_BUDGET;
 use crate::utils::{Puuid, RiotMatchId};
 use common::consts::platform_route::PlatformRoute;
 use itertools::Itertools;
+use leptos::logging::log;
 use riven::models::spectator_v5::CurrentGameInfo;
 use riven::RiotApi;
 use sqlx::PgPool;
@@ -23,6 +31,8 @@ pub struct HandleLiveGameCacheTask {
     pub riot_api: Arc<RiotApi>,
     pub cache: Arc<LiveGameCache>,
     pub hub: Arc<Hub>,
+    pub ingestion_queue: Arc<IngestionQueue>,
+    pub post_game_queue: Arc<PostGameQueue>,
     pub update_interval: Duration,
     pub next_run: Instant,
     pub running: Arc<AtomicBool>,
@@ -34,6 +44,7 @@ impl HandleLiveGameCacheTask {
         riot_api: Arc<RiotApi>,
         cache: Arc<LiveGameCache>,
         hub: Arc<Hub>,
+        ingestion_queue: Arc<IngestionQueue>,
         update_interval: Duration,
     ) -> Self {
         Self {
@@ -41,6 +52,8 @@ impl HandleLiveGameCacheTask {
             riot_api,
             cache,
             hub,
+            ingestion_queue,
+            post_game_queue: Arc::new(PostGameQueue::default()),
             update_interval,
             next_run: Instant::now() + update_interval,
             running: Arc::new(AtomicBool::new(false)),
@@ -54,6 +67,8 @@ impl Task for HandleLiveGameCacheTask {
         let riot_api = self.riot_api.clone();
         let cache = self.cache.clone();
         let hub = self.hub.clone();
+        let ingestion_queue = self.ingestion_queue.clone();
+        let post_game_queue = self.post_game_queue.clone();
 
         Box::pin(async move {
             // Clés “actives” = toutes celles connues du hub
@@ -80,22 +95,44 @@ impl Task for HandleLiveGameCacheTask {
                 fetch_all_game_info(&db, &riot_api, &ig_ids, &none_ids).await;
 
             // Transitions
+            let mut updated_sids: Vec<(i32, RiotMatchId)> = Vec::new();
+            // dernier état connu des parties terminées, gardé pour la page du match
+            let mut finished_games = HashMap::new();
             for (sid, new_mid) in summoner_match_id.drain() {
                 let prev_mid = cache.summoner_to_match.get(&sid).await;
                 match (prev_mid, new_mid) {
                     (Some(p), Some(n)) if p == n => {
-                        match_id_game_info.remove(&n);
+                        // même partie : on ne la reconstruit que si phase / timer / draft ont bougé
+                        let changed =
+                            match (cache.get_game_data(sid).await, match_id_game_info.get(&n)) {
+                                (Some(cached), Some(gi)) => live_game_changed(&cached, gi),
+                                _ => false,
+                            };
+                        if changed {
+                            updated_sids.push((sid, n));
+                        } else {
+                            // tous les sids de la partie partagent le même résultat
+                            match_id_game_info.remove(&n);
+                        }
                     }
-                    (Some(_), None) => {
+                    (Some(p), None) => {
+                        if let Some(game) = cache.get_game_data(sid).await {
+                            finished_games.entry(p).or_insert(game);
+                        }
                         cache.clear_game_data(sid).await;
-                        hub.set_live_none(sid);
+                        hub.live_game_ended(sid);
+                        post_game_queue.schedule(p, sid);
                     }
-                    (None, Some(_)) => {
-                        hub.bump_live_epoch(sid);
+                    (None, Some(n)) => {
+                        hub.live_game_started(sid, n);
                     }
                     (Some(p), Some(n)) if p != n => {
+                        if let Some(game) = cache.get_game_data(sid).await {
+                            finished_games.entry(p).or_insert(game);
+                        }
                         cache.clear_game_data(sid).await;
-                        hub.bump_live_epoch(sid);
+                        hub.live_game_started(sid, n);
+                        post_game_queue.schedule(p, sid);
                     }
                     _ => {}
                 }
@@ -103,20 +140,45 @@ impl Task for HandleLiveGameCacheTask {
 
             // Mise à jour du cache pour les matchs restants
             if !match_id_game_info.is_empty() {
-                let (all_participants, live_game_stats) = get_all_participants_live_game_stats(
-                    &db,
-                    &riot_api,
-                    match_id_game_info.values().collect::<Vec<_>>(),
-                )
-                .await
-                .unwrap();
+                let (all_participants, live_game_stats, scouting) =
+                    get_all_participants_live_game_stats(
+                        &db,
+                        &riot_api,
+                        match_id_game_info.values().collect::<Vec<_>>(),
+                        RiotCallPriority::Background,
+                    )
+                    .await
+                    .unwrap();
 
                 for (mid, gi) in match_id_game_info {
-                    let (summoner_ids, live) =
-                        game_info_to_live_game(mid, gi, &all_participants, &live_game_stats);
+                    let (summoner_ids, live) = game_info_to_live_game(
+                        mid,
+                        gi,
+                        &all_participants,
+                        &live_game_stats,
+                        &scouting,
+                    );
                     cache.set_game_data(mid, summoner_ids, live).await;
                 }
             }
+            for (sid, mid) in updated_sids {
+                hub.live_game_updated(sid, mid);
+            }
+
+            let finished_games = finished_games
+                .into_values()
+                .map(|game| game.as_ref().clone())
+                .collect::<Vec<_>>();
+            if let Err(e) = save_live_game_snapshots(&db, &finished_games).await {
+                log!("Error saving live game snapshots: {:?}", e);
+            }
+
+            // Parties terminées : ingestion dès que match-v5 les publie
+            if let Err(e) =
+                queue_finished_games(&db, &riot_api, &post_game_queue, &ingestion_queue, &hub).await
+            {
+                log!("Error queuing finished live games: {:?}", e);
+            }
         })
     }
 
@@ -138,6 +200,8 @@ impl Task for HandleLiveGameCacheTask {
             riot_api: self.riot_api.clone(),
             cache: self.cache.clone(),
             hub: self.hub.clone(),
+            ingestion_queue: self.ingestion_queue.clone(),
+            post_game_queue: self.post_game_queue.clone(),
             update_interval: self.update_interval,
             next_run: self.next_run,
             running: self.running.clone(),
@@ -151,6 +215,90 @@ impl Task for HandleLiveGameCacheTask {
     }
 }
 
+/// Checks the due finished games against the last match ids of one of their
+/// summoners; published ones are inserted and queued for priority ingestion, so
+/// `UpdateMatchesTask` pushes them as `NewMatches` once ingested.
+async fn queue_finished_games(
+    db: &PgPool,
+    riot_api: &Arc<RiotApi>,
+    post_game_queue: &PostGameQueue,
+    ingestion_queue: &IngestionQueue,
+    hub: &Hub,
+) -> AppResult<()> {
+    let due = post_game_queue.due();
+    if due.is_empty() {
+        return Ok(());
+    }
+    let summoner_ids = due
+        .iter()
+        .filter_map(|(_, sids)| sids.first().copied())
+        .collect::<Vec<_>>();
+    let puuids = fetch_summoner_puuids_by_ids(db, &summoner_ids).await?;
+    for (match_id, sids) in due {
+        let Some((puuid, platform)) = sids.first().and_then(|sid| puuids.get(sid)) else {
+            post_game_queue.finish(match_id);
+            continue;
+        };
+        let region = platform.to_riven().to_regional();
+        RIOT_BUDGET
+            .acquire(region, RiotCallPriority::Background)
+            .await;
+        let published = riot_api
+            .match_v5()
+            .get_match_ids_by_puuid(
+                region,
+                puuid.as_ref(),
+                Some(5),
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .map(|ids| ids.iter().any(|id| id == match_id.as_ref()))
+            .unwrap_or(false);
+        if !published {
+            post_game_queue.retry(match_id);
+            continue;
+        }
+        let match_id_str = match_id.to_string();
+        // une erreur sur une partie ne bloque pas les suivantes : elle est retentée
+        if let Err(e) = bulk_insert_default_match(db, std::slice::from_ref(&match_id_str)).await {
+            log!(
+                "Error inserting finished live game {}: {:?}",
+                match_id_str,
+                e
+            );
+            post_game_queue.retry(match_id);
+            continue;
+        }
+        let lol_match_id = match sqlx::query_scalar::<_, i32>(
+            "SELECT id FROM lol_matches WHERE match_id = $1 AND updated = false",
+        )
+        .bind(&match_id_str)
+        .fetch_optional(db)
+        .await
+        {
+            Ok(lol_match_id) => lol_match_id,
+            Err(e) => {
+                log!("Error reading finished live game {}: {:?}", match_id_str, e);
+                post_game_queue.retry(match_id);
+                continue;
+            }
+        };
+        // already ingested by a manual update otherwise
+        if let Some(lol_match_id) = lol_match_id {
+            for sid in sids {
+                let progress = ingestion_queue.push(sid, vec![lol_match_id]);
+                hub.set_ingestion_progress(sid, progress.done, progress.total);
+            }
+        }
+        post_game_queue.finish(match_id);
+    }
+    Ok(())
+}
+
 pub async fn fetch_all_game_info(
     db: &PgPool,
     riot_api: &Arc<RiotApi>,
@@ -186,15 +334,15 @@ pub async fn fetch_all_game_info(
                     async move {
                         (
                             *id,
-                            riot_api
-                                .spectator_v5()
-                                .get_current_game_info_by_puuid(
-                                    platform_.to_riven(),
-                                    puuid_.as_ref(),
-                                )
-                                .await
-                                .ok()
-                                .flatten(),
+                            riot_cache::current_game_by_puuid(
+                                &riot_api,
+                                platform_.to_riven(),
+                                puuid_.as_ref(),
+                                RiotCallPriority::Background,
+                            )
+                            .await
+                            .ok()
+                            .flatten(),
                         )
                     }
                 })
@@ -262,15 +410,15 @@ pub async fn fetch_all_game_info(
                     async move {
                         (
                             *id,
-                            riot_api
-                                .spectator_v5()
-                                .get_current_game_info_by_puuid(
-                                    platform_.to_riven(),
-                                    puuid_.as_ref(),
-                                )
-                                .await
-                                .ok()
-                                .flatten(),
+                            riot_cache::current_game_by_puuid(
+                                &riot_api,
+                                platform_.to_riven(),
+                                puuid_.as_ref(),
+                                RiotCallPriority::Background,
+                            )
+                            .await
+                            .ok()
+                            .flatten(),
                         )
                     }
                 })
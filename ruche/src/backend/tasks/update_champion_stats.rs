@@ -0,0 +1,347 @@
+use crate::backend::server_fns::get_patch_champion::ssr::PATCH_CHAMPION_CACHE;
+use crate::backend::server_fns::get_patch_champions::ssr::PATCH_CHAMPIONS_CACHE;
+use crate::backend::ssr::AppResult;
+use crate::backend::task_director::Task;
+use crate::views::champion_stats_page::{
+    ChampionBuildKind, CHAMPION_BUILDS_PER_KIND, CHAMPION_STATS_PATCHES, CHAMPION_STATS_QUEUES,
+};
+use leptos::logging::log;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::time::{Duration, Instant};
+
+pub struct UpdateChampionStatsTask {
+    pub db: PgPool,
+    pub update_interval: Duration,
+    pub next_run: Instant,
+    pub running: Arc<AtomicBool>,
+}
+
+impl UpdateChampionStatsTask {
+    pub fn new(db: PgPool, update_interval: Duration, on_startup: bool) -> Self {
+        let next_run = if on_startup {
+            Instant::now()
+        } else {
+            Instant::now() + update_interval
+        };
+        Self {
+            db,
+            update_interval,
+            next_run,
+            running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl Task for UpdateChampionStatsTask {
+    fn execute(&self) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>> {
+        let db = self.db.clone();
+        Box::pin(async move {
+            let start = Instant::now();
+            if let Err(e) = update_champion_stats(&db).await {
+                log!("Failed to update champion stats: {:?}", e);
+            }
+            PATCH_CHAMPIONS_CACHE.invalidate_all();
+            PATCH_CHAMPION_CACHE.invalidate_all();
+            log!("Champion stats updated in {:?}", start.elapsed());
+        })
+    }
+
+    fn next_execution(&self) -> Instant {
+        self.next_run
+    }
+
+    fn update_schedule(&mut self) {
+        self.next_run = Instant::now() + self.update_interval;
+    }
+
+    fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    fn set_running(&self, running: bool) {
+        self.running.store(running, Ordering::SeqCst);
+    }
+
+    fn clone_box(&self) -> Box<dyn Task> {
+        Box::new(Self {
+            db: self.db.clone(),
+            update_interval: self.update_interval,
+            next_run: self.next_run,
+            running: self.running.clone(),
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "UpdateChampionStatsTask"
+    }
+
+    fn allow_concurrent(&self) -> bool {
+        false // Do not allow concurrent executions
+    }
+}
+
+/// Rebuilds the aggregates of the latest patches; older patches keep their last build.
+pub async fn update_champion_stats(db: &PgPool) -> AppResult<()> {
+    let queue_ids = CHAMPION_STATS_QUEUES
+        .iter()
+        .map(|queue| queue.id() as i32)
+        .collect::<Vec<_>>();
+    // versions are "major.minor" strings, the latest played ones are the current patches
+    let versions = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT lm.version
+        FROM lol_matches as lm
+        WHERE lm.version IS NOT NULL
+          AND lm.queue_id = ANY($1)
+          AND NOT lm.trashed
+          AND lm.updated
+        GROUP BY lm.version
+        ORDER BY max(lm.match_end) DESC
+        LIMIT $2
+        "#,
+    )
+    .bind(&queue_ids)
+    .bind(CHAMPION_STATS_PATCHES)
+    .fetch_all(db)
+    .await?;
+
+    for version in versions {
+        // swapped in one transaction so readers never see a half built patch
+        let mut tx = db.begin().await?;
+        for table in [
+            "patch_queue_stats",
+            "champion_patch_stats",
+            "champion_patch_builds",
+        ] {
+            sqlx::query(&format!("DELETE FROM {} WHERE version = $1", table))
+                .bind(&version)
+                .execute(&mut *tx)
+                .await?;
+        }
+        insert_patch_queue_stats(&mut tx, &version, &queue_ids).await?;
+        insert_champion_patch_stats(&mut tx, &version, &queue_ids).await?;
+        for kind in ChampionBuildKind::ALL {
+            insert_champion_patch_builds(&mut tx, &version, &queue_ids, kind).await?;
+        }
+        tx.commit().await?;
+    }
+    Ok(())
+}
+
+async fn insert_patch_queue_stats(
+    tx: &mut Transaction<'_, Postgres>,
+    version: &str,
+    queue_ids: &[i32],
+) -> AppResult<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO patch_queue_stats (version, queue_id, total_matches, total_matches_with_bans)
+        SELECT lm.version,
+               lm.queue_id,
+               count(*)::INT,
+               count(*) FILTER (WHERE EXISTS (SELECT 1
+                                              FROM lol_match_bans as lmb
+                                              WHERE lmb.lol_match_id = lm.id))::INT
+        FROM lol_matches as lm
+        WHERE lm.version = $1
+          AND lm.queue_id = ANY($2)
+          AND lm.updated
+          AND NOT lm.trashed
+        GROUP BY lm.version, lm.queue_id
+        "#,
+    )
+    .bind(version)
+    .bind(queue_ids)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+async fn insert_champion_patch_stats(
+    tx: &mut Transaction<'_, Postgres>,
+    version: &str,
+    queue_ids: &[i32],
+) -> AppResult<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO champion_patch_stats (version, queue_id, champion_id, total_matches, total_wins,
+                                          total_bans, avg_kills, avg_deaths, avg_assists)
+        SELECT $1,
+               coalesce(picks.queue_id, bans.queue_id),
+               coalesce(picks.champion_id, bans.champion_id),
+               coalesce(picks.total_matches, 0),
+               coalesce(picks.total_wins, 0),
+               coalesce(bans.total_bans, 0),
+               coalesce(picks.avg_kills, 0),
+               coalesce(picks.avg_deaths, 0),
+               coalesce(picks.avg_assists, 0)
+        FROM (SELECT lm.queue_id,
+                     lmp.champion_id,
+                     count(*)::INT                                 AS total_matches,
+                     sum(CASE WHEN lmp.won THEN 1 ELSE 0 END)::INT AS total_wins,
+                     avg(lmp.kills)::REAL                          AS avg_kills,
+                     avg(lmp.deaths)::REAL                         AS avg_deaths,
+                     avg(lmp.assists)::REAL                        AS avg_assists
+              FROM lol_match_participants as lmp
+                       JOIN lol_matches as lm ON lm.id = lmp.lol_match_id
+              WHERE lm.version = $1
+                AND lm.queue_id = ANY($2)
+                AND NOT lm.trashed
+              GROUP BY lm.queue_id, lmp.champion_id) AS picks
+                 FULL OUTER JOIN
+             (SELECT lm.queue_id,
+                     lmb.champion_id,
+                     count(DISTINCT lmb.lol_match_id)::INT AS total_bans
+              FROM lol_match_bans as lmb
+                       JOIN lol_matches as lm ON lm.id = lmb.lol_match_id
+              WHERE lm.version = $1
+                AND lm.queue_id = ANY($2)
+                AND NOT lm.trashed
+              GROUP BY lm.queue_id, lmb.champion_id) AS bans
+             ON bans.queue_id = picks.queue_id AND bans.champion_id = picks.champion_id
+        "#,
+    )
+    .bind(version)
+    .bind(queue_ids)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Keeps the `CHAMPION_BUILDS_PER_KIND` most played builds of each champion and queue.
+async fn insert_champion_patch_builds(
+    tx: &mut Transaction<'_, Postgres>,
+    version: &str,
+    queue_ids: &[i32],
+    kind: ChampionBuildKind,
+) -> AppResult<()> {
+    let sql = format!(
+        r#"
+        INSERT INTO champion_patch_builds (version, queue_id, champion_id, kind, build, total_matches,
+                                           total_wins)
+        SELECT $1, queue_id, champion_id, $3, build, total_matches, total_wins
+        FROM (SELECT builds.queue_id,
+                     builds.champion_id,
+                     builds.build,
+                     count(*)::INT                                    AS total_matches,
+                     sum(CASE WHEN builds.won THEN 1 ELSE 0 END)::INT AS total_wins,
+                     row_number() OVER (
+                         PARTITION BY builds.queue_id, builds.champion_id
+                         ORDER BY count(*) DESC
+                         )                                            AS rank
+              FROM ({}) AS builds
+              WHERE cardinality(builds.build) >= $4
+              GROUP BY builds.queue_id, builds.champion_id, builds.build) AS ranked
+        WHERE rank <= $5
+        "#,
+        build_source_sql(kind)
+    );
+    sqlx::query(&sql)
+        .bind(version)
+        .bind(queue_ids)
+        .bind(kind.id() as i16)
+        .bind(build_min_len(kind))
+        .bind(CHAMPION_BUILDS_PER_KIND)
+        .execute(&mut **tx)
+        .await?;
+    Ok(())
+}
+
+/// One row per participant of the patch: `queue_id, champion_id, won, build`.
+fn build_source_sql(kind: ChampionBuildKind) -> &'static str {
+    match kind {
+        // first purchase of each finished item, in order; only games with a stored timeline
+        ChampionBuildKind::CoreItems => {
+            r#"
+            SELECT lm.queue_id,
+                   lmp.champion_id,
+                   lmp.won,
+                   ARRAY(SELECT (ev ->> 'item_id')::INT AS item_id
+                         FROM jsonb_array_elements(lmt.items_event_timeline)
+                                  WITH ORDINALITY AS frames(frame, frame_idx),
+                              jsonb_array_elements(frames.frame -> 1)
+                                  WITH ORDINALITY AS events(ev, ev_idx)
+                         WHERE ev ->> 'event_type' = 'Purchased'
+                           AND (ev ->> 'item_id')::BIGINT IN (lmp.item0_id, lmp.item1_id, lmp.item2_id,
+                                                             lmp.item3_id, lmp.item4_id, lmp.item5_id)
+                         GROUP BY 1
+                         ORDER BY min(frames.frame_idx * 1000 + events.ev_idx)
+                         LIMIT 3) AS build
+            FROM lol_match_participants as lmp
+                     JOIN lol_matches as lm ON lm.id = lmp.lol_match_id
+                     JOIN lol_match_timelines as lmt
+                          ON lmt.lol_match_id = lmp.lol_match_id AND lmt.summoner_id = lmp.summoner_id
+            WHERE lm.version = $1
+              AND lm.queue_id = ANY($2)
+              AND NOT lm.trashed
+            "#
+        }
+        // end of game inventory without the trinket, as a set
+        ChampionBuildKind::FinalItems => {
+            r#"
+            SELECT lm.queue_id,
+                   lmp.champion_id,
+                   lmp.won,
+                   ARRAY(SELECT item::INT
+                         FROM unnest(ARRAY [lmp.item0_id, lmp.item1_id, lmp.item2_id,
+                                         lmp.item3_id, lmp.item4_id, lmp.item5_id]) AS item
+                         WHERE item <> 0
+                         ORDER BY item) AS build
+            FROM lol_match_participants as lmp
+                     JOIN lol_matches as lm ON lm.id = lmp.lol_match_id
+            WHERE lm.version = $1
+              AND lm.queue_id = ANY($2)
+              AND NOT lm.trashed
+            "#
+        }
+        ChampionBuildKind::Runes => {
+            r#"
+            SELECT lm.queue_id,
+                   lmp.champion_id,
+                   lmp.won,
+                   ARRAY [lmp.perk_primary_style_id, lmp.perk_primary_selection_id,
+                       lmp.perk_primary_selection1_id, lmp.perk_primary_selection2_id,
+                       lmp.perk_primary_selection3_id, lmp.perk_sub_style_id,
+                       lmp.perk_sub_selection1_id, lmp.perk_sub_selection2_id,
+                       lmp.perk_offense_id, lmp.perk_flex_id, lmp.perk_defense_id] AS build
+            FROM lol_match_participants as lmp
+                     JOIN lol_matches as lm ON lm.id = lmp.lol_match_id
+            WHERE lm.version = $1
+              AND lm.queue_id = ANY($2)
+              AND NOT lm.trashed
+              AND lmp.perk_primary_selection_id IS NOT NULL
+              AND lmp.perk_primary_selection_id <> 0
+            "#
+        }
+        // the order of the two spells does not matter
+        ChampionBuildKind::SummonerSpells => {
+            r#"
+            SELECT lm.queue_id,
+                   lmp.champion_id,
+                   lmp.won,
+                   ARRAY [least(lmp.summoner_spell1_id, lmp.summoner_spell2_id),
+                       greatest(lmp.summoner_spell1_id, lmp.summoner_spell2_id)] AS build
+            FROM lol_match_participants as lmp
+                     JOIN lol_matches as lm ON lm.id = lmp.lol_match_id
+            WHERE lm.version = $1
+              AND lm.queue_id = ANY($2)
+              AND NOT lm.trashed
+              AND lmp.summoner_spell1_id IS NOT NULL
+              AND lmp.summoner_spell2_id IS NOT NULL
+            "#
+        }
+    }
+}
+
+fn build_min_len(kind: ChampionBuildKind) -> i32 {
+    match kind {
+        ChampionBuildKind::CoreItems => 3,
+        ChampionBuildKind::FinalItems => 1,
+        ChampionBuildKind::Runes => 11,
+        ChampionBuildKind::SummonerSpells => 2,
+    }
+}
@@ -0,0 +1,416 @@
+use crate::backend::riot_budget::RiotCallPriority;
+use crate::backend::riot_cache;
+use crate::backend::server_fns::get_champion_leaderboard::ssr::CHAMPION_LEADERBOARD_CACHE;
+use crate::backend::server_fns::get_leaderboard::ssr::LEADERBOARD_CACHE;
+use crate::backend::ssr::{AppResult, PlatformRouteDb};
+use crate::backend::task_director::Task;
+use crate::ssr::RiotApiState;
+use crate::views::leaderboard_page::{
+    CHAMPION_LEADERBOARD_MIN_MATCHES, CHAMPION_LEADERBOARD_SIZE, LEADERBOARD_QUEUES,
+};
+use chrono::Utc;
+use common::consts::platform_route::{PlatformRoute, PLATFORM_ROUTE_OPTIONS};
+use common::consts::queue::Queue;
+use common::consts::tier::Tier;
+use futures::{stream, StreamExt};
+use itertools::Itertools;
+use leptos::logging::log;
+use riven::consts::QueueType;
+use riven::models::league_v4::LeagueEntry;
+use sqlx::PgPool;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::time::{Duration, Instant};
+
+/// Stored summoners whose league entries are refreshed per run.
+const STORED_SUMMONERS_PER_RUN: i64 = 500;
+/// Only summoners with a ranked game in this window are refreshed.
+const STORED_SUMMONERS_ACTIVE_DAYS: i32 = 14;
+
+pub struct UpdateLeaderboardsTask {
+    pub db: PgPool,
+    pub api: RiotApiState,
+    pub update_interval: Duration,
+    pub next_run: Instant,
+    pub running: Arc<AtomicBool>,
+}
+
+impl UpdateLeaderboardsTask {
+    pub fn new(db: PgPool, api: RiotApiState, update_interval: Duration, on_startup: bool) -> Self {
+        let next_run = if on_startup {
+            Instant::now()
+        } else {
+            Instant::now() + update_interval
+        };
+        Self {
+            db,
+            api,
+            update_interval,
+            next_run,
+            running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl Task for UpdateLeaderboardsTask {
+    fn execute(&self) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>> {
+        let db = self.db.clone();
+        let api = self.api.clone();
+        Box::pin(async move {
+            let start = Instant::now();
+            if let Err(e) = update_leaderboards(&db, &api).await {
+                log!("Failed to update leaderboards: {:?}", e);
+            }
+            LEADERBOARD_CACHE.invalidate_all();
+            CHAMPION_LEADERBOARD_CACHE.invalidate_all();
+            log!("Leaderboards updated in {:?}", start.elapsed());
+        })
+    }
+
+    fn next_execution(&self) -> Instant {
+        self.next_run
+    }
+
+    fn update_schedule(&mut self) {
+        self.next_run = Instant::now() + self.update_interval;
+    }
+
+    fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    fn set_running(&self, running: bool) {
+        self.running.store(running, Ordering::SeqCst);
+    }
+
+    fn clone_box(&self) -> Box<dyn Task> {
+        Box::new(Self {
+            db: self.db.clone(),
+            api: self.api.clone(),
+            update_interval: self.update_interval,
+            next_run: self.next_run,
+            running: self.running.clone(),
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "UpdateLeaderboardsTask"
+    }
+
+    fn allow_concurrent(&self) -> bool {
+        false // Do not allow concurrent executions
+    }
+}
+
+pub async fn update_leaderboards(db: &PgPool, api: &RiotApiState) -> AppResult<()> {
+    for platform in PLATFORM_ROUTE_OPTIONS {
+        for queue in LEADERBOARD_QUEUES {
+            // one failing platform must not hold back the others
+            if let Err(e) = update_apex_ladder(db, api, platform, queue).await {
+                log!(
+                    "Failed to update {} {} ladder: {:?}",
+                    platform.code(),
+                    queue.id(),
+                    e
+                );
+            }
+        }
+    }
+    refresh_stored_summoners(db, api).await?;
+    rebuild_champion_leaderboards(db).await
+}
+
+struct LeagueEntryRow {
+    puuid: String,
+    tier: Tier,
+    division: u8,
+    league_points: i32,
+    wins: i32,
+    losses: i32,
+}
+
+impl LeagueEntryRow {
+    fn from_league_entry(puuid: &str, entry: LeagueEntry) -> Option<(Queue, Self)> {
+        let queue = queue_from_type(&entry.queue_type)?;
+        Some((
+            queue,
+            Self {
+                tier: Tier::from_code(&entry.tier?.to_string())?,
+                division: entry
+                    .rank
+                    .and_then(|rank| Tier::division_from_code(&rank.to_string()))
+                    .unwrap_or_default(),
+                puuid: puuid.to_string(),
+                league_points: entry.league_points,
+                wins: entry.wins,
+                losses: entry.losses,
+            },
+        ))
+    }
+
+    /// Every tier on one scale: 400 points per tier below Master (100 per division),
+    /// plain LP from Master up since apex tiers are cut by LP.
+    fn ladder_score(&self) -> i32 {
+        if self.tier.is_apex() {
+            Tier::Master.id() as i32 * 400 + self.league_points
+        } else {
+            self.tier.id() as i32 * 400
+                + (4 - self.division.clamp(1, 4) as i32) * 100
+                + self.league_points
+        }
+    }
+}
+
+fn queue_type(queue: Queue) -> QueueType {
+    match queue {
+        Queue::SummonersRift5v5RankedFlex => QueueType::RANKED_FLEX_SR,
+        _ => QueueType::RANKED_SOLO_5x5,
+    }
+}
+
+fn queue_from_type(queue_type: &QueueType) -> Option<Queue> {
+    LEADERBOARD_QUEUES
+        .into_iter()
+        .find(|queue| &self::queue_type(*queue) == queue_type)
+}
+
+/// Challenger, Grandmaster and Master of one platform and queue; players that
+/// dropped out of the apex tiers since the last run are removed.
+async fn update_apex_ladder(
+    db: &PgPool,
+    api: &RiotApiState,
+    platform: PlatformRoute,
+    queue: Queue,
+) -> AppResult<()> {
+    let started_at = Utc::now().naive_utc();
+    let mut rows = Vec::new();
+    for tier in [
+        riven::consts::Tier::CHALLENGER,
+        riven::consts::Tier::GRANDMASTER,
+        riven::consts::Tier::MASTER,
+    ] {
+        let league = riot_cache::apex_league(
+            api,
+            platform.to_riven(),
+            queue_type(queue),
+            tier,
+            RiotCallPriority::Background,
+        )
+        .await?;
+        let Some(tier) = Tier::from_code(&tier.to_string()) else {
+            continue;
+        };
+        rows.extend(league.entries.into_iter().map(|item| LeagueEntryRow {
+            puuid: item.puuid,
+            tier,
+            division: 0,
+            league_points: item.league_points,
+            wins: item.wins,
+            losses: item.losses,
+        }));
+    }
+    upsert_league_entries(db, platform, queue, &rows).await?;
+    sqlx::query(
+        r#"
+        DELETE FROM league_entries
+        WHERE platform = $1
+          AND queue_id = $2
+          AND tier >= $3
+          AND updated_at < $4
+        "#,
+    )
+    .bind(PlatformRouteDb::from(platform))
+    .bind(queue.id() as i32)
+    .bind(Tier::Master.id() as i16)
+    .bind(started_at)
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// League entries of the stored summoners who played ranked lately, least recently
+/// checked first, so our own players below Master get on the boards too.
+async fn refresh_stored_summoners(db: &PgPool, api: &RiotApiState) -> AppResult<()> {
+    let started_at = Utc::now().naive_utc();
+    let summoners = sqlx::query_as::<_, (i32, String, PlatformRouteDb)>(
+        r#"
+        SELECT ss.id, ss.puuid, ss.platform
+        FROM summoners as ss
+        WHERE EXISTS (SELECT 1
+                      FROM lol_match_participants as lmp
+                               JOIN lol_matches as lm ON lm.id = lmp.lol_match_id
+                      WHERE lmp.summoner_id = ss.id
+                        AND lm.queue_id = ANY($1)
+                        AND lm.match_end > NOW() - make_interval(days => $2))
+        ORDER BY ss.league_checked_at NULLS FIRST
+        LIMIT $3
+        "#,
+    )
+    .bind(
+        LEADERBOARD_QUEUES
+            .iter()
+            .map(|queue| queue.id() as i32)
+            .collect::<Vec<_>>(),
+    )
+    .bind(STORED_SUMMONERS_ACTIVE_DAYS)
+    .bind(STORED_SUMMONERS_PER_RUN)
+    .fetch_all(db)
+    .await?;
+
+    let fetched = stream::iter(
+        summoners
+            .into_iter()
+            .map(|(id, puuid, platform)| async move {
+                let platform = PlatformRoute::from(platform);
+                riot_cache::league_entries_by_puuid(
+                    api,
+                    platform.to_riven(),
+                    &puuid,
+                    RiotCallPriority::Background,
+                )
+                .await
+                .map(|entries| (id, puuid, platform, entries))
+            }),
+    )
+    .buffer_unordered(8)
+    .filter_map(|result| async move { result.ok() })
+    .collect::<Vec<_>>()
+    .await;
+
+    let mut checked_ids = Vec::with_capacity(fetched.len());
+    let mut checked_puuids = Vec::with_capacity(fetched.len());
+    let mut rows_by_key = std::collections::HashMap::<(PlatformRoute, Queue), Vec<_>>::new();
+    for (id, puuid, platform, entries) in fetched {
+        for (queue, row) in entries
+            .into_iter()
+            .filter_map(|entry| LeagueEntryRow::from_league_entry(&puuid, entry))
+        {
+            rows_by_key.entry((platform, queue)).or_default().push(row);
+        }
+        checked_ids.push(id);
+        checked_puuids.push(puuid);
+    }
+    for ((platform, queue), rows) in rows_by_key {
+        upsert_league_entries(db, platform, queue, &rows).await?;
+    }
+    // whatever was not just written is a queue they no longer have a rank in
+    sqlx::query("DELETE FROM league_entries WHERE puuid = ANY($1) AND updated_at < $2")
+        .bind(&checked_puuids)
+        .bind(started_at)
+        .execute(db)
+        .await?;
+    sqlx::query("UPDATE summoners SET league_checked_at = NOW() WHERE id = ANY($1)")
+        .bind(&checked_ids)
+        .execute(db)
+        .await?;
+    Ok(())
+}
+
+async fn upsert_league_entries(
+    db: &PgPool,
+    platform: PlatformRoute,
+    queue: Queue,
+    rows: &[LeagueEntryRow],
+) -> AppResult<()> {
+    if rows.is_empty() {
+        return Ok(());
+    }
+    let (puuids, tiers, divisions, league_points, wins, losses, ladder_scores): (
+        Vec<_>,
+        Vec<_>,
+        Vec<_>,
+        Vec<_>,
+        Vec<_>,
+        Vec<_>,
+        Vec<_>,
+    ) = rows
+        .iter()
+        .map(|row| {
+            (
+                row.puuid.clone(),
+                row.tier.id() as i16,
+                row.division as i16,
+                row.league_points,
+                row.wins,
+                row.losses,
+                row.ladder_score(),
+            )
+        })
+        .multiunzip();
+    sqlx::query(
+        r#"
+        INSERT INTO league_entries (platform, queue_id, puuid, tier, division, league_points, wins,
+                                    losses, ladder_score, updated_at)
+        SELECT $1, $2, data.*, $10
+        FROM UNNEST($3::VARCHAR[], $4::SMALLINT[], $5::SMALLINT[], $6::INT[], $7::INT[], $8::INT[],
+                    $9::INT[]) AS data
+        ON CONFLICT (platform, queue_id, puuid) DO UPDATE
+            SET tier          = EXCLUDED.tier,
+                division      = EXCLUDED.division,
+                league_points = EXCLUDED.league_points,
+                wins          = EXCLUDED.wins,
+                losses        = EXCLUDED.losses,
+                ladder_score  = EXCLUDED.ladder_score,
+                updated_at    = EXCLUDED.updated_at
+        "#,
+    )
+    .bind(PlatformRouteDb::from(platform))
+    .bind(queue.id() as i32)
+    .bind(&puuids)
+    .bind(&tiers)
+    .bind(&divisions)
+    .bind(&league_points)
+    .bind(&wins)
+    .bind(&losses)
+    .bind(&ladder_scores)
+    // compared with `Utc::now()` by the stale row cleanups
+    .bind(Utc::now().naive_utc())
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// Best win rate per champion in ranked solo/duo among summoners with enough games,
+/// swapped in one transaction so readers never see a half built board.
+async fn rebuild_champion_leaderboards(db: &PgPool) -> AppResult<()> {
+    let mut tx = db.begin().await?;
+    sqlx::query("DELETE FROM champion_leaderboard_entries")
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(
+        r#"
+        INSERT INTO champion_leaderboard_entries (champion_id, position, summoner_id, total_matches,
+                                                  total_wins, avg_kills, avg_deaths, avg_assists)
+        SELECT champion_id, position, summoner_id, total_matches, total_wins, avg_kills, avg_deaths,
+               avg_assists
+        FROM (SELECT stats.*,
+                     row_number() OVER (
+                         PARTITION BY stats.champion_id
+                         ORDER BY stats.total_wins::REAL / stats.total_matches DESC, stats.total_matches DESC
+                         ) AS position
+              FROM (SELECT lmp.champion_id,
+                           lmp.summoner_id,
+                           count(*)::INT                                 AS total_matches,
+                           sum(CASE WHEN lmp.won THEN 1 ELSE 0 END)::INT AS total_wins,
+                           avg(lmp.kills)::REAL                          AS avg_kills,
+                           avg(lmp.deaths)::REAL                         AS avg_deaths,
+                           avg(lmp.assists)::REAL                        AS avg_assists
+                    FROM lol_match_participants as lmp
+                             JOIN lol_matches as lm ON lm.id = lmp.lol_match_id
+                    WHERE lm.queue_id = $1
+                      AND NOT lm.trashed
+                    GROUP BY lmp.champion_id, lmp.summoner_id
+                    HAVING count(*) >= $2) AS stats) AS ranked
+        WHERE position <= $3
+        "#,
+    )
+    .bind(Queue::SummonersRift5v5RankedSolo.id() as i32)
+    .bind(CHAMPION_LEADERBOARD_MIN_MATCHES)
+    .bind(CHAMPION_LEADERBOARD_SIZE)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(())
+}
@@ -2,16 +2,19 @@ pub mod bulk_lol_match_participants;
 pub mod bulk_lol_matches;
 pub mod bulk_summoners;
 
+use crate::backend::ingestion_queue::IngestionQueue;
+use crate::backend::riot_budget::RiotCallPriority;
+use crate::backend::riot_cache;
 use crate::backend::ssr::{AppError, AppResult, PlatformRouteDb};
 use crate::backend::task_director::Task;
 use crate::backend::tasks::update_matches::bulk_lol_match_participants::bulk_insert_lol_match_participants;
 use crate::backend::tasks::update_matches::bulk_lol_matches::{
-    bulk_trashed_matches, bulk_update_matches,
+    bulk_insert_match_bans, bulk_trashed_matches, bulk_update_matches,
 };
 use crate::backend::tasks::update_matches::bulk_summoners::{
     bulk_insert_summoners, bulk_update_summoners,
 };
-use crate::ssr::RiotApiState;
+use crate::ssr::{RiotApiState, RIOT_BUDGET};
 use crate::utils::ProPlayerSlug;
 use crate::DB_CHUNK_SIZE;
 use chrono::NaiveDateTime;
@@ -23,7 +26,7 @@ use leptos::logging::log;
 use riven::consts::Champion;
 use sqlx::types::chrono::{DateTime, Utc};
 use sqlx::{FromRow, PgPool};
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
 use std::str::FromStr;
@@ -36,6 +39,7 @@ pub struct UpdateMatchesTask {
     api: RiotApiState,
     update_interval: Duration,
     hub: Arc<crate::sse::Hub>,
+    ingestion_queue: Arc<IngestionQueue>,
     next_run: Instant,
     running: Arc<AtomicBool>,
 }
@@ -46,6 +50,7 @@ impl UpdateMatchesTask {
         api: RiotApiState,
         update_interval: Duration,
         hub: Arc<crate::sse::Hub>,
+        ingestion_queue: Arc<IngestionQueue>,
     ) -> Self {
         let next_run = Instant::now() + update_interval;
         Self {
@@ -53,6 +58,7 @@ impl UpdateMatchesTask {
             api,
             update_interval,
             hub,
+            ingestion_queue,
             next_run,
             running: Arc::new(AtomicBool::new(false)),
         }
@@ -64,15 +70,46 @@ impl Task for UpdateMatchesTask {
         let db = self.db.clone();
         let api = self.api.clone();
         let hub = self.hub.clone();
+        let ingestion_queue = self.ingestion_queue.clone();
         Box::pin(async move {
-            while let Ok(matches) = get_not_updated_match(&db, 100).await {
+            loop {
+                // Matches requested from a summoner page go first, the global backlog after.
+                let priority_batch = ingestion_queue.next_batch(100);
+                let matches = if !priority_batch.is_empty() {
+                    let ids = priority_batch
+                        .iter()
+                        .flat_map(|(_, ids)| ids.iter().copied())
+                        .collect_vec();
+                    get_not_updated_match_by_ids(&db, &ids)
+                        .await
+                        .unwrap_or_else(|e| {
+                            log!("Error fetching priority matches: {:?}", e);
+                            vec![]
+                        })
+                } else {
+                    match get_not_updated_match(&db, 100).await {
+                        Ok(matches) => matches,
+                        Err(_) => break,
+                    }
+                };
                 let start = Instant::now();
                 let match_len = matches.len();
-                match update_matches_task(&db, &api, matches).await {
-                    Ok(summoner_ids) => {
+                let result = if matches.is_empty() {
+                    Ok(HashMap::new())
+                } else {
+                    update_matches_task(&db, &api, matches).await
+                };
+                for (summoner_id, ids) in priority_batch {
+                    if let Some(progress) = ingestion_queue.complete(summoner_id, ids.len()) {
+                        let total = if progress.is_finished() { 0 } else { progress.total };
+                        hub.set_ingestion_progress(summoner_id, progress.done, total);
+                    }
+                }
+                match result {
+                    Ok(new_matches) => {
                         let now = std::time::Instant::now();
-                        for id in summoner_ids {
-                            hub.bump_matches(id);
+                        for (summoner_id, match_ids) in new_matches {
+                            hub.push_new_matches(summoner_id, &match_ids);
                         }
                         log!(
                             "Updated {} matches in {:?} and bump in {:?}",
@@ -111,6 +148,7 @@ impl Task for UpdateMatchesTask {
             api: self.api.clone(),
             update_interval: self.update_interval,
             hub: self.hub.clone(),
+            ingestion_queue: self.ingestion_queue.clone(),
             next_run: self.next_run,
             running: self.running.clone(),
         })
@@ -125,15 +163,19 @@ impl Task for UpdateMatchesTask {
     }
 }
 
+/// Returns the newly ingested `lol_matches.id` per participating summoner.
 async fn update_matches_task(
     db: &PgPool,
     api: &RiotApiState,
     matches_to_update: Vec<LolMatchNotUpdated>,
-) -> AppResult<HashSet<i32>> {
+) -> AppResult<HashMap<i32, Vec<i32>>> {
     let match_data_futures = matches_to_update.iter().map(|match_| {
         let api = Arc::clone(api);
         let pt = consts::platform_route::PlatformRoute::from(match_.platform).to_riven();
         async move {
+            RIOT_BUDGET
+                .acquire(pt.to_regional(), RiotCallPriority::Background)
+                .await;
             api.match_v5()
                 .get_match(pt.to_regional(), &match_.match_id)
                 .await
@@ -239,9 +281,13 @@ async fn update_matches_task(
         async move {
             (
                 summoner,
-                api.account_v1()
-                    .get_by_puuid(pt.to_regional(), &puuid)
-                    .await,
+                riot_cache::account_by_puuid(
+                    &api,
+                    pt.to_regional(),
+                    &puuid,
+                    RiotCallPriority::Background,
+                )
+                .await,
             )
         }
     });
@@ -310,6 +356,12 @@ async fn update_matches_task(
                 .iter()
                 .map(|team| (team.team_id, team.objectives.champion.kills))
                 .collect();
+            // same value as `lol_matches.match_end`, see `bulk_update_matches`
+            let match_end = chrono::DateTime::from_timestamp_millis(
+                match_data.info.game_end_timestamp.unwrap_or_default(),
+            )
+            .unwrap_or_default()
+            .naive_utc();
 
             // Instead of returning an iterator, collect the results into a Vec
             match_data
@@ -348,6 +400,7 @@ async fn update_matches_task(
                         champion_id,
                         summoner_id,
                         lol_match_id: match_.id,
+                        match_end,
                         summoner_spell1_id: participant.summoner1_id,
                         summoner_spell2_id: participant.summoner2_id,
                         team_id: participant.team_id as i32,
@@ -425,6 +478,8 @@ async fn update_matches_task(
                         item4_id: participant.item4,
                         item5_id: participant.item5,
                         item6_id: participant.item6,
+                        team_position: (!participant.team_position.is_empty())
+                            .then(|| participant.team_position.clone()),
                     })
                 })
                 .collect::<Vec<_>>() // Collect the iterator into a Vec
@@ -435,10 +490,14 @@ async fn update_matches_task(
     for chunk in match_participants.chunks(DB_CHUNK_SIZE) {
         bulk_insert_lol_match_participants(db, chunk).await?;
     }
+    bulk_insert_match_bans(db, &match_datas).await?;
     // Bulk update matches
     bulk_update_matches(db, match_datas).await?;
     bulk_trashed_matches(db, trashed_matches).await?;
-    Ok(summoner_map.into_values().collect::<HashSet<i32>>())
+    Ok(match_participants
+        .iter()
+        .map(|p| (p.summoner_id, p.lol_match_id))
+        .into_group_map())
 }
 
 #[derive(Clone, Debug)]
@@ -468,6 +527,8 @@ pub struct TempParticipant {
     pub champion_id: i16,
     pub summoner_id: i32,
     pub lol_match_id: i32,
+    /// Copy of `lol_matches.match_end`, the history sort key.
+    pub match_end: NaiveDateTime,
     pub summoner_spell1_id: i32,
     pub summoner_spell2_id: i32,
     pub team_id: i32,
@@ -506,6 +567,23 @@ pub struct TempParticipant {
     pub item4_id: i32,
     pub item5_id: i32,
     pub item6_id: i32,
+    pub team_position: Option<String>,
+}
+
+pub async fn get_not_updated_match_by_ids(
+    db: &PgPool,
+    ids: &[i32],
+) -> AppResult<Vec<LolMatchNotUpdated>> {
+    Ok(sqlx::query_as::<_, LolMatchNotUpdated>(
+        r#"
+            SELECT id, match_id, platform, updated FROM lol_matches
+            WHERE id = ANY($1) AND updated = false
+            ORDER BY match_id DESC;
+        "#,
+    )
+    .bind(ids)
+    .fetch_all(db)
+    .await?)
 }
 
 pub async fn get_not_updated_match(db: &PgPool, limit: i32) -> AppResult<Vec<LolMatchNotUpdated>> {
@@ -563,6 +641,9 @@ pub async fn resolve_summoner_conflicts(db: &PgPool, api: &RiotApiState) -> AppR
             let platform_route = PlatformRoute::try_from(platform.as_str()).unwrap_or_default();
             let riven_ptr =
                 riven::consts::PlatformRoute::from_str(&platform_route.to_string()).unwrap();
+            RIOT_BUDGET
+                .acquire(riven_ptr.to_regional(), RiotCallPriority::Background)
+                .await;
             if let Ok(account) = api
                 .account_v1()
                 .get_by_puuid(riven_ptr.to_regional(), &record.puuid)
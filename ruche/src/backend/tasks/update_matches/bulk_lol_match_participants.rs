@@ -138,6 +138,7 @@ pub async fn bulk_insert_lol_match_participants(
         item4_ids,
         item5_ids,
         item6_ids,
+        team_positions,
     ): (
         Vec<_>,
         Vec<_>,
@@ -150,6 +151,7 @@ pub async fn bulk_insert_lol_match_participants(
         Vec<_>,
         Vec<_>,
         Vec<_>,
+        Vec<_>,
     ) = participants
         .iter()
         .map(|p| {
@@ -165,10 +167,13 @@ pub async fn bulk_insert_lol_match_participants(
                 p.item4_id,
                 p.item5_id,
                 p.item6_id,
+                p.team_position.clone(),
             )
         })
         .multiunzip();
 
+    let match_ends = participants.iter().map(|p| p.match_end).collect::<Vec<_>>();
+
     let sql = r#"
             INSERT INTO lol_match_participants (
                 champion_id,
@@ -211,7 +216,9 @@ pub async fn bulk_insert_lol_match_participants(
                 item3_id,
                 item4_id,
                 item5_id,
-                item6_id
+                item6_id,
+                team_position,
+                match_end
             )
             SELECT * FROM UNNEST (
                 $1::INT[],
@@ -254,7 +261,9 @@ pub async fn bulk_insert_lol_match_participants(
                 $38::INT[],
                 $39::INT[],
                 $40::INT[],
-                $41::INT[]
+                $41::INT[],
+                $42::VARCHAR[],
+                $43::TIMESTAMP[]
             );
         "#;
 
@@ -300,6 +309,8 @@ pub async fn bulk_insert_lol_match_participants(
         .bind(&item4_ids)
         .bind(&item5_ids)
         .bind(&item6_ids)
+        .bind(&team_positions)
+        .bind(&match_ends)
         .execute(db)
         .await?;
 
@@ -26,6 +26,40 @@ pub async fn bulk_trashed_matches(
     Ok(())
 }
 
+pub async fn bulk_insert_match_bans(
+    db: &PgPool,
+    matches: &[(Match, LolMatchNotUpdated)],
+) -> AppResult<()> {
+    let (lol_match_ids, team_ids, champion_ids): (Vec<i32>, Vec<i32>, Vec<i32>) = matches
+        .iter()
+        .flat_map(|(x, db_match)| {
+            x.info.teams.iter().flat_map(move |team| {
+                team.bans
+                    .iter()
+                    // -1 when the turn was skipped
+                    .filter(|ban| ban.champion_id.0 > 0)
+                    .map(move |ban| (db_match.id, team.team_id as i32, ban.champion_id.0 as i32))
+            })
+        })
+        .multiunzip();
+    if lol_match_ids.is_empty() {
+        return Ok(());
+    }
+
+    let sql = r"
+        INSERT INTO lol_match_bans (lol_match_id, team_id, champion_id)
+        SELECT * FROM UNNEST($1::INT[], $2::INT[], $3::INT[])
+        ON CONFLICT DO NOTHING;
+        ";
+    sqlx::query(sql)
+        .bind(lol_match_ids)
+        .bind(team_ids)
+        .bind(champion_ids)
+        .execute(db)
+        .await?;
+    Ok(())
+}
+
 pub async fn bulk_update_matches(
     db: &PgPool,
     matches: Vec<(Match, LolMatchNotUpdated)>,
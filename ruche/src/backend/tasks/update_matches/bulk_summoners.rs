@@ -26,7 +26,7 @@ pub async fn bulk_update_summoners(db: &sqlx::PgPool, summoners: &[TempSummoner]
                 unnest($4::INT[]) AS summoner_level,
                 unnest($5::INT[]) AS profile_icon_id,
                 unnest($6::TIMESTAMP[]) AS updated_at,
-                unnest($6::VARCHAR(78)[]) AS puuid
+                unnest($7::VARCHAR(78)[]) AS puuid
 
         ) AS data
         WHERE summoners.puuid = data.puuid;
@@ -1,3 +1,5 @@
+use crate::backend::riot_budget::RiotCallPriority;
+use crate::backend::riot_cache;
 use crate::backend::ssr::{AppError, AppResult, PlatformRouteDb};
 use crate::backend::task_director::Task;
 use crate::backend::tasks::calculate_next_run_to_fixed_start_hour;
@@ -142,14 +144,14 @@ pub async fn update_pro_player(db: &PgPool, api: RiotApiState) -> AppResult<()>
         let api = api.clone();
         let pt = pro_player_account.platform.to_riven();
         async move {
-            let response = api
-                .account_v1()
-                .get_by_riot_id(
-                    pt.to_regional(),
-                    pro_player_account.game_name.as_str(),
-                    pro_player_account.tag_line.as_str(),
-                )
-                .await;
+            let response = riot_cache::account_by_riot_id(
+                &api,
+                pt.to_regional(),
+                pro_player_account.game_name.as_str(),
+                pro_player_account.tag_line.as_str(),
+                RiotCallPriority::Background,
+            )
+            .await;
             match response {
                 Ok(Some(account)) => Ok(TempSummoner {
                     game_name: account.game_name.unwrap_or_default(),
@@ -0,0 +1,284 @@
+//! CSV / NDJSON downloads of a summoner's filtered match history and champion stats.
+//!
+//! Rows are turned into JSON by Postgres (`row_to_json`) and streamed out of sqlx one
+//! by one, so a full history never sits in memory and every column of the query ends
+//! up in the file without a hand-written mapping.
+use crate::api::ApiFilters;
+use crate::app::SummonerIdentifier;
+use crate::backend::server_fns::get_champions::ssr::push_champions_query;
+use crate::backend::server_fns::get_matches::ssr::push_match_filters;
+use crate::backend::server_fns::get_summoner::ssr::resolve_id_by_s_identifier;
+use crate::ssr::AppState;
+use crate::utils::parse_summoner_slug;
+use crate::views::BackEndMatchFiltersSearch;
+use axum::body::Body;
+use axum::extract::{Path, Query, State};
+use axum::response::{IntoResponse, Response};
+use common::consts::platform_route::PlatformRoute;
+use futures::StreamExt;
+use http::{header, HeaderValue, StatusCode};
+use leptos::logging::log;
+use serde::Deserialize;
+use sqlx::{Column, Executor, FromRow, PgPool, Postgres, QueryBuilder};
+
+#[derive(Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Ndjson,
+}
+
+impl ExportFormat {
+    fn content_type(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Ndjson => "ndjson",
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+pub struct ExportFormatQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+#[derive(FromRow)]
+struct ExportRow {
+    keys: Vec<String>,
+    cells: Vec<String>,
+    json: String,
+}
+
+pub async fn export_matches(
+    State(state): State<AppState>,
+    Path((platform_route, summoner_slug)): Path<(String, String)>,
+    Query(format): Query<ExportFormatQuery>,
+    Query(filters): Query<ApiFilters>,
+) -> Result<Response, StatusCode> {
+    let (identifier, summoner_id) =
+        resolve_summoner(&state.db, &platform_route, &summoner_slug).await?;
+    let filters = BackEndMatchFiltersSearch::from(filters);
+
+    let mut query = export_query();
+    push_matches_query(&mut query, summoner_id, &filters);
+    close_export_query(&mut query);
+    let mut columns_query = QueryBuilder::new("");
+    push_matches_query(&mut columns_query, summoner_id, &filters);
+
+    Ok(export_response(
+        state.db.clone(),
+        query,
+        columns_query.sql().to_string(),
+        format.format,
+        &identifier,
+        "matches",
+    ))
+}
+
+pub async fn export_champions(
+    State(state): State<AppState>,
+    Path((platform_route, summoner_slug)): Path<(String, String)>,
+    Query(format): Query<ExportFormatQuery>,
+    Query(filters): Query<ApiFilters>,
+) -> Result<Response, StatusCode> {
+    let (identifier, summoner_id) =
+        resolve_summoner(&state.db, &platform_route, &summoner_slug).await?;
+
+    let filters = BackEndMatchFiltersSearch::from(filters);
+
+    let mut query = export_query();
+    push_champions_query(&mut query, summoner_id, filters);
+    close_export_query(&mut query);
+    let mut columns_query = QueryBuilder::new("");
+    push_champions_query(&mut columns_query, summoner_id, filters);
+
+    Ok(export_response(
+        state.db.clone(),
+        query,
+        columns_query.sql().to_string(),
+        format.format,
+        &identifier,
+        "champions",
+    ))
+}
+
+fn push_matches_query(
+    query: &mut QueryBuilder<'_, Postgres>,
+    summoner_id: i32,
+    filters: &BackEndMatchFiltersSearch,
+) {
+    query.push(
+        r#"
+        SELECT lm.match_id       AS riot_match_id,
+               lm.platform,
+               lm.queue_id,
+               lm.map_id,
+               lm.game_mode,
+               lm.version,
+               lm.match_creation,
+               lm.match_end,
+               lm.match_duration,
+               lmp.*
+        FROM lol_match_participants as lmp
+                 JOIN lol_matches as lm ON lm.id = lmp.lol_match_id
+        WHERE lmp.summoner_id =
+        "#,
+    );
+    push_match_filters(query, summoner_id, filters);
+    query.push(" ORDER BY lm.match_end DESC");
+}
+
+async fn resolve_summoner(
+    db: &PgPool,
+    platform_route: &str,
+    summoner_slug: &str,
+) -> Result<(SummonerIdentifier, i32), StatusCode> {
+    let platform_route = PlatformRoute::from_code(platform_route).ok_or(StatusCode::NOT_FOUND)?;
+    let (game_name, tag_line) = parse_summoner_slug(summoner_slug);
+    let identifier = SummonerIdentifier {
+        game_name,
+        tag_line,
+        platform_route,
+    };
+    let summoner_id = resolve_id_by_s_identifier(db, &identifier)
+        .await
+        .map_err(|_| StatusCode::NOT_FOUND)?;
+    Ok((identifier, summoner_id))
+}
+
+/// Opens the wrapper that turns each row of the pushed query into its column names,
+/// text values and JSON; `close_export_query` ends it.
+fn export_query() -> QueryBuilder<'static, Postgres> {
+    QueryBuilder::new(
+        r#"
+        SELECT ARRAY(SELECT e.key FROM json_each_text(r.json) WITH ORDINALITY e ORDER BY e.ordinality)
+                   AS keys,
+               ARRAY(SELECT coalesce(e.value, '') FROM json_each_text(r.json) WITH ORDINALITY e ORDER BY e.ordinality)
+                   AS cells,
+               r.json::text AS json
+        FROM (
+        "#,
+    )
+}
+
+fn close_export_query(query: &mut QueryBuilder<'static, Postgres>) {
+    query.push(") AS t, LATERAL (SELECT row_to_json(t) AS json) AS r");
+}
+
+/// `columns_sql` is the unwrapped query, described for the CSV header when no row
+/// comes back.
+fn export_response(
+    db: PgPool,
+    mut query: QueryBuilder<'static, Postgres>,
+    columns_sql: String,
+    format: ExportFormat,
+    identifier: &SummonerIdentifier,
+    kind: &str,
+) -> Response {
+    let stream = async_stream::stream! {
+        let mut rows = query.build_query_as::<ExportRow>().fetch(&db);
+        let mut header_written = false;
+        while let Some(row) = rows.next().await {
+            match row {
+                Ok(row) => {
+                    let mut chunk = String::new();
+                    match format {
+                        ExportFormat::Csv => {
+                            if !header_written {
+                                push_csv_record(&mut chunk, &row.keys);
+                                header_written = true;
+                            }
+                            push_csv_record(&mut chunk, &row.cells);
+                        }
+                        ExportFormat::Ndjson => {
+                            chunk.push_str(&row.json);
+                            chunk.push('\n');
+                        }
+                    }
+                    yield Ok::<_, sqlx::Error>(chunk);
+                }
+                Err(e) => {
+                    log!("export stream failed: {}", e);
+                    yield Err(e);
+                    return;
+                }
+            }
+        }
+        if format == ExportFormat::Csv && !header_written {
+            match (&db).describe(&columns_sql).await {
+                Ok(describe) => {
+                    let keys = describe
+                        .columns()
+                        .iter()
+                        .map(|column| column.name().to_string())
+                        .collect::<Vec<_>>();
+                    let mut chunk = String::new();
+                    push_csv_record(&mut chunk, &keys);
+                    yield Ok(chunk);
+                }
+                Err(e) => {
+                    log!("export header failed: {}", e);
+                    yield Err(e);
+                }
+            }
+        }
+    };
+
+    let file_name = format!(
+        "{}-{}-{}-{}.{}",
+        file_name_part(&identifier.game_name),
+        file_name_part(&identifier.tag_line),
+        identifier.platform_route.code(),
+        kind,
+        format.extension()
+    );
+    (
+        [
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(format.content_type()),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                HeaderValue::from_str(&format!("attachment; filename=\"{}\"", file_name))
+                    .unwrap_or(HeaderValue::from_static("attachment")),
+            ),
+            (header::CACHE_CONTROL, HeaderValue::from_static("no-store")),
+        ],
+        Body::from_stream(stream),
+    )
+        .into_response()
+}
+
+fn push_csv_record(out: &mut String, fields: &[String]) {
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            out.push('"');
+            out.push_str(&field.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(field);
+        }
+    }
+    out.push_str("\r\n");
+}
+
+/// Riot IDs can hold any unicode; keep the header value plain ASCII.
+fn file_name_part(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
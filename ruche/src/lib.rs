@@ -4,10 +4,20 @@ pub mod views;
 pub mod backend;
 pub mod utils;
 
+#[cfg(feature = "ssr")]
+pub mod api;
+#[cfg(feature = "ssr")]
+pub mod export;
+#[cfg(feature = "ssr")]
+pub mod og_image;
 #[cfg(feature = "ssr")]
 pub mod serve;
 #[cfg(feature = "ssr")]
 pub mod sse;
+#[cfg(feature = "ssr")]
+pub mod webtransport;
+#[cfg(not(feature = "ssr"))]
+pub mod live_updates;
 
 
 
@@ -15,7 +25,9 @@ pub const DB_CHUNK_SIZE: usize = 500;
 
 #[cfg(feature = "ssr")]
 pub mod ssr {
+    use crate::backend::ingestion_queue::IngestionQueue;
     use crate::backend::live_game_cache;
+    use crate::backend::riot_budget::RiotBudget;
     use leptos::prelude::*;
     use riven::RiotApi;
     use sqlx::postgres::PgConnectOptions;
@@ -37,6 +49,7 @@ pub mod ssr {
         pub live_game_cache: Arc<live_game_cache::LiveGameCache>,
         pub max_matches: usize,
         pub hub: Arc<Hub>,
+        pub ingestion_queue: Arc<IngestionQueue>,
     }
 
     pub static S_IDENTIFIER_TO_ID: Lazy<Cache<SummonerIdentifier, Arc<i32>>> = Lazy::new(|| {
@@ -46,6 +59,9 @@ pub mod ssr {
             .build()
     });
 
+    /// Shared by every Riot call site, see `RiotBudget::acquire`.
+    pub static RIOT_BUDGET: Lazy<RiotBudget> = Lazy::new(RiotBudget::from_env);
+
     pub fn init_riot_api() -> RiotApi {
         let api_key = dotenv::var("RIOT_API_KEY").expect("RIOT_API_KEY must be set");
         RiotApi::new(api_key)
@@ -0,0 +1,177 @@
+use crate::backend::server_fns::update_sse_subscriptions::update_sse_subscriptions;
+use crate::utils::{SseEvent, SseMultiplexEvent, WEBTRANSPORT_PATH};
+use common::consts::platform_route::PlatformRoute;
+use futures::channel::mpsc;
+use futures::StreamExt;
+use gloo_net::eventsource::futures::EventSource;
+use js_sys::{Reflect, Uint8Array};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = WebTransport)]
+    #[derive(Clone)]
+    type JsWebTransport;
+    #[wasm_bindgen(constructor, catch, js_class = "WebTransport")]
+    fn new(url: &str) -> Result<JsWebTransport, JsValue>;
+    #[wasm_bindgen(method, getter, js_class = "WebTransport")]
+    fn ready(this: &JsWebTransport) -> js_sys::Promise;
+    #[wasm_bindgen(method, getter, js_class = "WebTransport", js_name = incomingUnidirectionalStreams)]
+    fn incoming_unidirectional_streams(this: &JsWebTransport) -> JsReadableStream;
+    #[wasm_bindgen(method, js_class = "WebTransport")]
+    fn close(this: &JsWebTransport);
+
+    #[wasm_bindgen(js_name = ReadableStream)]
+    type JsReadableStream;
+    #[wasm_bindgen(method, js_class = "ReadableStream", js_name = getReader)]
+    fn get_reader(this: &JsReadableStream) -> JsReader;
+
+    #[wasm_bindgen(js_name = ReadableStreamDefaultReader)]
+    type JsReader;
+    #[wasm_bindgen(method, js_class = "ReadableStreamDefaultReader")]
+    fn read(this: &JsReader) -> js_sys::Promise;
+}
+
+enum Transport {
+    WebTransport(JsWebTransport),
+    Sse(EventSource),
+}
+
+/// Open connection behind `summoner_events`; `close` on cleanup.
+#[derive(Clone, Default)]
+pub struct LiveUpdates {
+    closed: Rc<Cell<bool>>,
+    transport: Rc<RefCell<Option<Transport>>>,
+}
+
+impl LiveUpdates {
+    pub fn close(&self) {
+        self.closed.set(true);
+        match self.transport.borrow_mut().take() {
+            Some(Transport::WebTransport(transport)) => transport.close(),
+            Some(Transport::Sse(source)) => source.close(),
+            None => {}
+        }
+    }
+}
+
+/// Hub events of one summoner: over WebTransport when the browser supports it,
+/// through `/sse/match_updated/..` otherwise or once the session drops.
+pub fn summoner_events(
+    platform: PlatformRoute,
+    summoner_id: i32,
+) -> (mpsc::UnboundedReceiver<SseEvent>, LiveUpdates) {
+    let (tx, rx) = mpsc::unbounded();
+    let live_updates = LiveUpdates::default();
+    let inner = live_updates.clone();
+    leptos::task::spawn_local(async move {
+        if webtransport_supported() {
+            match run_webtransport(platform, summoner_id, &tx, &inner).await {
+                // events may have been missed between the two transports
+                Ok(true) | Err(true) => {
+                    let _ = tx.unbounded_send(SseEvent::Resync);
+                }
+                Ok(false) | Err(false) => {}
+            }
+        }
+        if !inner.closed.get() {
+            run_sse(platform, summoner_id, tx, &inner).await;
+        }
+    });
+    (rx, live_updates)
+}
+
+fn webtransport_supported() -> bool {
+    Reflect::has(&js_sys::global(), &JsValue::from_str("WebTransport")).unwrap_or(false)
+}
+
+/// Reads the session until it ends; `Ok`/`Err` carry whether events were delivered.
+async fn run_webtransport(
+    platform: PlatformRoute,
+    summoner_id: i32,
+    tx: &mpsc::UnboundedSender<SseEvent>,
+    live_updates: &LiveUpdates,
+) -> Result<bool, bool> {
+    let mut delivered = false;
+    let result = async {
+        let host = leptos::prelude::window().location().host()?;
+        let transport = JsWebTransport::new(&format!("https://{}{}", host, WEBTRANSPORT_PATH))?;
+        *live_updates.transport.borrow_mut() = Some(Transport::WebTransport(transport.clone()));
+        JsFuture::from(transport.ready()).await?;
+        let streams = transport.incoming_unidirectional_streams().get_reader();
+        let first = JsFuture::from(streams.read()).await?;
+        let reader = Reflect::get(&first, &JsValue::from_str("value"))?
+            .unchecked_into::<JsReadableStream>()
+            .get_reader();
+        let mut buffer: Vec<u8> = Vec::new();
+        loop {
+            let chunk = JsFuture::from(reader.read()).await?;
+            if Reflect::get(&chunk, &JsValue::from_str("done"))?.is_truthy() {
+                return Ok::<(), JsValue>(());
+            }
+            buffer.extend(
+                Uint8Array::new(&Reflect::get(&chunk, &JsValue::from_str("value"))?).to_vec(),
+            );
+            while buffer.len() >= 4 {
+                let len = u32::from_le_bytes([buffer[0], buffer[1], buffer[2], buffer[3]]) as usize;
+                if buffer.len() < 4 + len {
+                    break;
+                }
+                let frame = buffer.drain(..4 + len).skip(4).collect::<Vec<_>>();
+                match bitcode::decode::<SseMultiplexEvent>(&frame) {
+                    Ok(SseMultiplexEvent::Connected(connection_id)) => {
+                        update_sse_subscriptions(
+                            connection_id,
+                            vec![(platform, summoner_id)],
+                            vec![],
+                        )
+                        .await
+                        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+                    }
+                    Ok(SseMultiplexEvent::Topic {
+                        summoner_id: topic,
+                        event,
+                    }) if topic == summoner_id => {
+                        delivered = true;
+                        let _ = tx.unbounded_send(event);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    .await;
+    live_updates.transport.borrow_mut().take();
+    result.map(|_| delivered).map_err(|_| delivered)
+}
+
+async fn run_sse(
+    platform: PlatformRoute,
+    summoner_id: i32,
+    tx: mpsc::UnboundedSender<SseEvent>,
+    live_updates: &LiveUpdates,
+) {
+    let Ok(mut source) =
+        EventSource::new(&format!("/sse/match_updated/{}/{}", platform, summoner_id))
+    else {
+        return;
+    };
+    let Ok(mut messages) = source.subscribe("message") else {
+        return;
+    };
+    *live_updates.transport.borrow_mut() = Some(Transport::Sse(source));
+    // errors are transient: EventSource reconnects on its own
+    while let Some(message) = messages.next().await {
+        if let Some(event) = message
+            .ok()
+            .and_then(|(_, message)| message.data().as_string())
+            .and_then(|s| SseEvent::from_payload(&s))
+        {
+            let _ = tx.unbounded_send(event);
+        }
+    }
+}
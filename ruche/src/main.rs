@@ -12,21 +12,30 @@ async fn main() -> ruche::backend::ssr::AppResult<()> {
     use ruche::ssr::init_database;
     use leptos_axum::{generate_route_list, LeptosRoutes};
     use memory_serve::{load_assets, CacheControl, MemoryServe};
+    use ruche::api::require_api_key;
     use ruche::app::*;
+    use ruche::backend::ingestion_queue::IngestionQueue;
     use ruche::backend::live_game_cache::LiveGameCache;
+    use ruche::backend::server_fns::get_matches::ssr::invalidate_matches_statistics;
     use ruche::backend::task_director::TaskDirector;
     use ruche::backend::tasks::daily_sql_clean::DailySqlCleanTask;
     use ruche::backend::tasks::generate_sitemap::GenerateSiteMapTask;
     use ruche::backend::tasks::handle_live_game_cache::HandleLiveGameCacheTask;
+    use ruche::backend::tasks::update_champion_stats::UpdateChampionStatsTask;
+    use ruche::backend::tasks::update_leaderboards::UpdateLeaderboardsTask;
     use ruche::backend::tasks::update_matches::UpdateMatchesTask;
     use ruche::backend::tasks::update_pro_players::UpdateProPlayerTask;
+    use ruche::export::{export_champions, export_matches};
+    use ruche::og_image::{get_match_og_image, get_summoner_og_image};
+    use ruche::serve::get_riot_budget;
     use ruche::serve::get_sitemap;
     use ruche::serve::serve;
-    use ruche::sse::sse_broadcast_match_updated;
+    use ruche::sse::{sse_broadcast_match_updated, sse_multiplex};
     use ruche::sse::Hub;
     use ruche::ssr::init_riot_api;
     use ruche::ssr::AppState;
     use sqlx::PgPool;
+    use std::collections::HashSet;
     use std::net::SocketAddr;
     use std::sync::Arc;
     use tower::ServiceBuilder;
@@ -59,18 +68,42 @@ async fn main() -> ruche::backend::ssr::AppResult<()> {
             .parse()?,
     );
 
+    let leaderboard_interval_duration = tokio::time::Duration::from_secs(
+        dotenv::var("LEADERBOARD_TASK_UPDATE_INTERVAL")
+            .unwrap_or_else(|_| "3600".to_string())
+            .parse()?,
+    );
+
+    let champion_stats_interval_duration = tokio::time::Duration::from_secs(
+        dotenv::var("CHAMPION_STATS_TASK_UPDATE_INTERVAL")
+            .unwrap_or_else(|_| "21600".to_string())
+            .parse()?,
+    );
+
     let lol_pro_task_on_startup = dotenv::var("LOL_PRO_TASK_ON_STARTUP")
         .unwrap_or("false".to_string())
         .eq("true");
     let site_map_task_on_startup = dotenv::var("SITE_MAP_TASK_ON_STARTUP")
         .unwrap_or("false".to_string())
         .eq("true");
+    let leaderboard_task_on_startup = dotenv::var("LEADERBOARD_TASK_ON_STARTUP")
+        .unwrap_or("false".to_string())
+        .eq("true");
+    let champion_stats_task_on_startup = dotenv::var("CHAMPION_STATS_TASK_ON_STARTUP")
+        .unwrap_or("false".to_string())
+        .eq("true");
+    let sse_pg_notify = dotenv::var("SSE_PG_NOTIFY")
+        .unwrap_or("false".to_string())
+        .eq("true");
 
     log!("Starting Ruche as {}", env_type);
     log!("Update interval duration: {:?}", update_interval_duration);
     log!("Max matches: {}", max_matches);
     log!("LOL Pro Task on Startup: {}", lol_pro_task_on_startup);
     log!("Site Map Task on Startup: {}", site_map_task_on_startup);
+    log!("Leaderboard Task on Startup: {}", leaderboard_task_on_startup);
+    log!("Champion Stats Task on Startup: {}", champion_stats_task_on_startup);
+    log!("SSE pg_notify fan-out: {}", sse_pg_notify);
 
     if is_prod {
         leptos_options.site_addr = SocketAddr::from(([0, 0, 0, 0], 443));
@@ -86,8 +119,14 @@ async fn main() -> ruche::backend::ssr::AppResult<()> {
     let pool = init_database(is_prod).await;
     let riot_api = Arc::new(init_riot_api());
     let hub = Hub::new();
+    // applied on every instance, pg_notify included: their statistics caches stay in sync
+    hub.on_new_matches(|sid| invalidate_matches_statistics(HashSet::from([sid])));
+    if sse_pg_notify {
+        hub.enable_pg_notify(pool.clone());
+    }
     tokio::spawn(hub.clone().run(std::time::Duration::from_millis(500)));
     let live_game_cache = Arc::new(LiveGameCache::default());
+    let ingestion_queue = Arc::new(IngestionQueue::default());
 
     let mut task_director = TaskDirector::default();
     task_director.add_task(HandleLiveGameCacheTask::new(
@@ -95,6 +134,7 @@ async fn main() -> ruche::backend::ssr::AppResult<()> {
         riot_api.clone(),
         live_game_cache.clone(),
         hub.clone(),
+        ingestion_queue.clone(),
         live_game_cache_interval_duration,
     ));
 
@@ -104,6 +144,19 @@ async fn main() -> ruche::backend::ssr::AppResult<()> {
         Arc::clone(&riot_api),
         update_interval_duration,
         hub.clone(),
+        ingestion_queue.clone(),
+    ));
+
+    task_director.add_task(UpdateLeaderboardsTask::new(
+        pool.clone(),
+        riot_api.clone(),
+        leaderboard_interval_duration,
+        leaderboard_task_on_startup,
+    ));
+    task_director.add_task(UpdateChampionStatsTask::new(
+        pool.clone(),
+        champion_stats_interval_duration,
+        champion_stats_task_on_startup,
     ));
 
     if is_prod {
@@ -131,6 +184,7 @@ async fn main() -> ruche::backend::ssr::AppResult<()> {
         live_game_cache,
         max_matches,
         hub,
+        ingestion_queue,
     };
 
     let routes = generate_route_list(App);
@@ -175,9 +229,34 @@ async fn main() -> ruche::backend::ssr::AppResult<()> {
         )
         .route(
             "/sse/match_updated/{platform_route}/{summoner_id}",
-            get(sse_broadcast_match_updated).layer(sse_middleware),
+            get(sse_broadcast_match_updated).layer(sse_middleware.clone()),
         )
+        .route("/sse/stream", get(sse_multiplex).layer(sse_middleware))
         .route("/sitemap-index.xml", get(get_sitemap))
+        .route(
+            "/status/riot_budget",
+            get(get_riot_budget).layer(axum::middleware::from_fn_with_state(
+                app_state.clone(),
+                require_api_key,
+            )),
+        )
+        .nest("/api/v1", ruche::api::router(app_state.clone()))
+        .route(
+            "/export/{platform_route}/{summoner_slug}/matches",
+            get(export_matches),
+        )
+        .route(
+            "/export/{platform_route}/{summoner_slug}/champions",
+            get(export_champions),
+        )
+        .route(
+            "/og/summoners/{platform_route}/{summoner_slug}",
+            get(get_summoner_og_image),
+        )
+        .route(
+            "/og/matches/{platform_route}/{riot_match_id}",
+            get(get_match_og_image),
+        )
         .fallback(leptos_axum::file_and_error_handler::<LeptosOptions, _>(
             shell,
         ))
@@ -196,8 +275,8 @@ async fn main() -> ruche::backend::ssr::AppResult<()> {
                         .and(NotForContentType::const_new("text/css")),
                 ),
         )
-        .with_state(app_state);
-    serve(app, is_prod, site_address)
+        .with_state(app_state.clone());
+    serve(app, app_state, is_prod, site_address)
         .await
         .expect("failed to serve");
     Ok(())
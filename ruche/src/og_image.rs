@@ -0,0 +1,587 @@
+//! OpenGraph preview cards for summoner and match pages, referenced by `og:image`.
+//!
+//! Cards are drawn from the PNG sprites `asset-generation` writes to `OG_ASSETS_PATH`
+//! (AVIF decoding would pull in a native decoder) and cached as PNG files under
+//! `OG_CACHE_PATH`. A match card never changes; a summoner card is redrawn once it is
+//! older than `SUMMONER_CARD_TTL`, and deleted by the daily clean task after that.
+use crate::app::SummonerIdentifier;
+use crate::backend::riot_budget::RiotCallPriority;
+use crate::backend::riot_cache;
+use crate::backend::server_fns::get_champions::ssr::inner_get_champions;
+use crate::backend::server_fns::get_encounter::ssr::find_summoner_puuid_by_id;
+use crate::backend::server_fns::get_live_game::ssr::solo_queue_league;
+use crate::backend::server_fns::get_match::ssr::find_match_page;
+use crate::backend::server_fns::get_summoner::ssr::{
+    resolve_id_by_s_identifier, resolve_summoner_by_s_identifier,
+};
+use crate::backend::ssr::{AppError, AppResult};
+use crate::ssr::AppState;
+use crate::utils::{parse_summoner_slug, RiotMatchId};
+use crate::views::match_page::MatchPage;
+use crate::views::summoner_page::summoner_champions_page::ChampionStats;
+use crate::views::summoner_page::summoner_live_page::LiveGameParticipantLeague;
+use crate::views::summoner_page::Summoner;
+use ab_glyph::{point, Font, FontArc, GlyphId, PxScale, ScaleFont};
+use axum::extract::{Path, State};
+use axum::response::{IntoResponse, Response};
+use common::consts::champion::Champion;
+use common::consts::platform_route::PlatformRoute;
+use http::{header, HeaderValue, StatusCode};
+use image::imageops::FilterType;
+use image::{ImageFormat, Rgba, RgbaImage};
+use leptos::logging::log;
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const CARD_WIDTH: u32 = 1200;
+const CARD_HEIGHT: u32 = 630;
+const SUMMONER_CARD_TTL: Duration = Duration::from_secs(3600);
+
+const BACKGROUND: Rgba<u8> = Rgba([17, 24, 39, 255]);
+const PANEL: Rgba<u8> = Rgba([31, 41, 55, 255]);
+const TEXT: Rgba<u8> = Rgba([229, 231, 235, 255]);
+const MUTED: Rgba<u8> = Rgba([156, 163, 175, 255]);
+const BLUE: Rgba<u8> = Rgba([147, 197, 253, 255]);
+const RED: Rgba<u8> = Rgba([252, 165, 165, 255]);
+
+static OG_ASSETS: Lazy<OgAssets> = Lazy::new(OgAssets::load);
+
+static OG_CACHE_PATH: Lazy<PathBuf> = Lazy::new(|| {
+    PathBuf::from(dotenv::var("OG_CACHE_PATH").unwrap_or("target/og-cache".to_string()))
+});
+
+/// Same layout as `asset_generation::OgSpriteIndex`.
+#[derive(Deserialize)]
+struct SpriteIndex {
+    size: (u32, u32),
+    positions: HashMap<String, (u32, u32)>,
+}
+
+struct Sprite {
+    image: RgbaImage,
+    index: SpriteIndex,
+}
+
+impl Sprite {
+    fn load(root: &std::path::Path, name: &str) -> Option<Self> {
+        let image = image::open(root.join(format!("{}.png", name)))
+            .map_err(|e| log!("og: no {} sprite: {}", name, e))
+            .ok()?
+            .to_rgba8();
+        let index = std::fs::read(root.join(format!("{}.json", name)))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())?;
+        Some(Self { image, index })
+    }
+
+    fn get(&self, id: impl ToString) -> Option<RgbaImage> {
+        let (x, y) = *self.index.positions.get(&id.to_string())?;
+        let (w, h) = self.index.size;
+        Some(image::imageops::crop_imm(&self.image, x, y, w, h).to_image())
+    }
+}
+
+struct OgAssets {
+    root: PathBuf,
+    champions: Option<Sprite>,
+    font: Option<FontArc>,
+}
+
+impl OgAssets {
+    fn load() -> Self {
+        let root =
+            PathBuf::from(dotenv::var("OG_ASSETS_PATH").unwrap_or("ruche/og-assets".to_string()));
+        let font_path = dotenv::var("OG_FONT_PATH")
+            .unwrap_or("/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf".to_string());
+        // without a font the cards still get their images
+        let font = std::fs::read(&font_path)
+            .ok()
+            .and_then(|bytes| FontArc::try_from_vec(bytes).ok());
+        if font.is_none() {
+            log!(
+                "og: cannot load font {}, cards are drawn without text",
+                font_path
+            );
+        }
+        Self {
+            champions: Sprite::load(&root, "champions"),
+            root,
+            font,
+        }
+    }
+
+    fn profile_icon(&self, profile_icon_id: u16) -> Option<RgbaImage> {
+        image::open(
+            self.root
+                .join("profile_icons")
+                .join(format!("{}.png", profile_icon_id)),
+        )
+        .ok()
+        .map(|image| image.to_rgba8())
+    }
+
+    fn champion(&self, champion_id: u16) -> Option<RgbaImage> {
+        self.champions.as_ref()?.get(champion_id)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+pub async fn get_summoner_og_image(
+    State(state): State<AppState>,
+    Path((platform_route, summoner_slug)): Path<(String, String)>,
+) -> Response {
+    let Some(platform_route) = PlatformRoute::from_code(&platform_route) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let (game_name, tag_line) = parse_summoner_slug(&summoner_slug);
+    let identifier = SummonerIdentifier {
+        game_name,
+        tag_line,
+        platform_route,
+    };
+    let Ok(summoner_id) = resolve_id_by_s_identifier(&state.db, &identifier).await else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let cache_path = OG_CACHE_PATH.join(format!("summoner-{}.png", summoner_id));
+    if let Some(bytes) = read_cached(&cache_path, Some(SUMMONER_CARD_TTL)).await {
+        return png_response(bytes);
+    }
+    match summoner_card_data(&state, &identifier, summoner_id).await {
+        Ok(data) => render_and_cache(cache_path, move || draw_summoner_card(&data)).await,
+        Err(e) => {
+            log!("og: summoner card failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+pub async fn get_match_og_image(
+    State(state): State<AppState>,
+    Path((platform_route, riot_match_id)): Path<(String, String)>,
+) -> Response {
+    let Some(platform_route) = PlatformRoute::from_code(&platform_route) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    // the id ends up in a file name
+    if !riot_match_id
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    let riot_match_id = RiotMatchId::new(&riot_match_id);
+    let cache_path = OG_CACHE_PATH.join(format!("match-{}.png", riot_match_id));
+    if let Some(bytes) = read_cached(&cache_path, None).await {
+        return png_response(bytes);
+    }
+    match find_match_page(&state.db, platform_route, riot_match_id).await {
+        Ok(Some(match_page)) => {
+            render_and_cache(cache_path, move || draw_match_card(&match_page)).await
+        }
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => {
+            log!("og: match card failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+async fn read_cached(path: &std::path::Path, ttl: Option<Duration>) -> Option<Vec<u8>> {
+    if let Some(ttl) = ttl {
+        let modified = tokio::fs::metadata(path).await.ok()?.modified().ok()?;
+        if modified.elapsed().unwrap_or(Duration::MAX) > ttl {
+            return None;
+        }
+    }
+    tokio::fs::read(path).await.ok()
+}
+
+/// Deletes the summoner cards older than `SUMMONER_CARD_TTL`, returns how many went.
+/// Match cards stay: they never change and are only drawn for visited matches.
+pub async fn evict_stale_summoner_cards() -> usize {
+    let Ok(mut entries) = tokio::fs::read_dir(&*OG_CACHE_PATH).await else {
+        return 0;
+    };
+    let mut evicted = 0;
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let is_summoner_card = entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| name.starts_with("summoner-") && name.ends_with(".png"));
+        if !is_summoner_card {
+            continue;
+        }
+        let stale = entry
+            .metadata()
+            .await
+            .and_then(|metadata| metadata.modified())
+            .is_ok_and(|modified| modified.elapsed().unwrap_or(Duration::MAX) > SUMMONER_CARD_TTL);
+        if stale && tokio::fs::remove_file(entry.path()).await.is_ok() {
+            evicted += 1;
+        }
+    }
+    evicted
+}
+
+/// Draws off the async workers, then writes the file; a failed write only costs a redraw.
+async fn render_and_cache(
+    path: PathBuf,
+    draw: impl FnOnce() -> RgbaImage + Send + 'static,
+) -> Response {
+    let encoded = tokio::task::spawn_blocking(move || {
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        draw()
+            .write_to(&mut bytes, ImageFormat::Png)
+            .map(|_| bytes.into_inner())
+    })
+    .await;
+    match encoded {
+        Ok(Ok(bytes)) => {
+            if let Some(parent) = path.parent() {
+                let _ = tokio::fs::create_dir_all(parent).await;
+            }
+            if let Err(e) = tokio::fs::write(&path, &bytes).await {
+                log!("og: cannot cache {}: {}", path.display(), e);
+            }
+            png_response(bytes)
+        }
+        Ok(Err(e)) => {
+            log!("og: png encode failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+        Err(e) => {
+            log!("og: render task failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+fn png_response(bytes: Vec<u8>) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, HeaderValue::from_static("image/png")),
+            (
+                header::CACHE_CONTROL,
+                HeaderValue::from_static("public, max-age=3600"),
+            ),
+        ],
+        bytes,
+    )
+        .into_response()
+}
+
+// ---------------------------------------------------------------------------
+// Card data
+// ---------------------------------------------------------------------------
+
+struct SummonerCardData {
+    summoner: Summoner,
+    league: Option<LiveGameParticipantLeague>,
+    /// (games, wins) over the last `RECENT_GAMES`.
+    recent: (i64, i64),
+    top_champions: Vec<ChampionStats>,
+}
+
+const RECENT_GAMES: i64 = 20;
+
+async fn summoner_card_data(
+    state: &AppState,
+    identifier: &SummonerIdentifier,
+    summoner_id: i32,
+) -> AppResult<SummonerCardData> {
+    let summoner = resolve_summoner_by_s_identifier(&state.db, identifier).await?;
+    let puuid = find_summoner_puuid_by_id(&state.db, summoner_id).await?;
+    let (league, recent, champions) = tokio::join!(
+        riot_cache::league_entries_by_puuid(
+            &state.riot_api,
+            summoner.platform.to_riven(),
+            &puuid,
+            RiotCallPriority::Background,
+        ),
+        recent_results(&state.db, summoner_id),
+        inner_get_champions(&state.db, summoner_id, Default::default()),
+    );
+    Ok(SummonerCardData {
+        summoner,
+        // a preview without rank beats no preview when the budget is spent
+        league: league.ok().and_then(solo_queue_league),
+        recent: recent?,
+        top_champions: champions?.into_iter().take(3).collect(),
+    })
+}
+
+async fn recent_results(db: &PgPool, summoner_id: i32) -> AppResult<(i64, i64)> {
+    sqlx::query_as::<_, (i64, i64)>(
+        r#"
+        SELECT count(*), coalesce(sum(CASE WHEN recent.won THEN 1 ELSE 0 END), 0)
+        FROM (SELECT lmp.won
+              FROM lol_match_participants as lmp
+                       JOIN lol_matches as lm ON lm.id = lmp.lol_match_id
+              WHERE lmp.summoner_id = $1
+              ORDER BY lm.match_end DESC
+              LIMIT $2) as recent
+        "#,
+    )
+    .bind(summoner_id)
+    .bind(RECENT_GAMES)
+    .fetch_one(db)
+    .await
+    .map_err(AppError::from)
+}
+
+// ---------------------------------------------------------------------------
+// Drawing
+// ---------------------------------------------------------------------------
+
+fn draw_summoner_card(data: &SummonerCardData) -> RgbaImage {
+    let assets = &*OG_ASSETS;
+    let mut card = RgbaImage::from_pixel(CARD_WIDTH, CARD_HEIGHT, BACKGROUND);
+    let summoner = &data.summoner;
+
+    if let Some(icon) = assets.profile_icon(summoner.profile_icon_id) {
+        draw_image(&mut card, &icon, 60, 60, 180);
+    }
+    draw_text(
+        &mut card,
+        &format!("{}#{}", summoner.game_name, summoner.tag_line),
+        270,
+        70,
+        60.0,
+        TEXT,
+        870,
+    );
+    draw_text(
+        &mut card,
+        &format!(
+            "{} · Level {}",
+            summoner.platform.code(),
+            summoner.summoner_level
+        ),
+        270,
+        150,
+        32.0,
+        MUTED,
+        870,
+    );
+    let rank = data
+        .league
+        .map(|league| format!("{} · {}W {}L", league.label(), league.wins, league.losses))
+        .unwrap_or("Unranked".to_string());
+    draw_text(&mut card, &rank, 270, 200, 36.0, TEXT, 870);
+
+    let (games, wins) = data.recent;
+    if games > 0 {
+        draw_text(
+            &mut card,
+            &format!(
+                "Last {} games: {}W {}L ({}%)",
+                games,
+                wins,
+                games - wins,
+                wins * 100 / games
+            ),
+            60,
+            290,
+            36.0,
+            if wins * 2 >= games { BLUE } else { RED },
+            1080,
+        );
+    }
+
+    for (i, champion_stats) in data.top_champions.iter().enumerate() {
+        let x = 60 + i as i64 * 370;
+        let y = 370;
+        fill_rect(&mut card, x, y, 340, 200, PANEL);
+        if let Some(image) = assets.champion(champion_stats.champion_id) {
+            draw_image(&mut card, &image, x + 20, y + 20, 100);
+        }
+        let name = Champion::try_from(champion_stats.champion_id)
+            .map(|champion| champion.label())
+            .unwrap_or("Unknown");
+        draw_text(&mut card, name, x + 140, y + 30, 30.0, TEXT, 190);
+        draw_text(
+            &mut card,
+            &format!("{} games", champion_stats.total_matches),
+            x + 140,
+            y + 75,
+            26.0,
+            MUTED,
+            190,
+        );
+        draw_text(
+            &mut card,
+            &format!("{:.0}% WR", champion_stats.win_rate),
+            x + 20,
+            y + 140,
+            30.0,
+            if champion_stats.win_rate >= 50.0 {
+                BLUE
+            } else {
+                RED
+            },
+            300,
+        );
+    }
+
+    draw_footer(&mut card);
+    card
+}
+
+fn draw_match_card(match_page: &MatchPage) -> RgbaImage {
+    let assets = &*OG_ASSETS;
+    let mut card = RgbaImage::from_pixel(CARD_WIDTH, CARD_HEIGHT, BACKGROUND);
+    let duration = match_page.match_duration.unwrap_or_default();
+    draw_text(
+        &mut card,
+        &format!(
+            "{} · {}m {:02}s · Patch {}",
+            match_page.queue.label(),
+            duration / 60,
+            duration % 60,
+            match_page.version
+        ),
+        40,
+        30,
+        36.0,
+        TEXT,
+        1120,
+    );
+
+    for (column, team_id) in [100u16, 200].into_iter().enumerate() {
+        let x = 40 + column as i64 * 580;
+        let team = match_page
+            .participants
+            .iter()
+            .filter(|p| p.team_id == team_id)
+            .collect::<Vec<_>>();
+        let won = team.first().map(|p| p.won).unwrap_or_default();
+        draw_text(
+            &mut card,
+            if won { "Victory" } else { "Defeat" },
+            x,
+            90,
+            30.0,
+            if won { BLUE } else { RED },
+            540,
+        );
+        for (row, participant) in team.iter().enumerate() {
+            let y = 140 + row as i64 * 86;
+            fill_rect(&mut card, x, y, 540, 76, PANEL);
+            if let Some(image) = assets.champion(participant.champion_id) {
+                draw_image(&mut card, &image, x + 6, y + 6, 64);
+            }
+            draw_text(
+                &mut card,
+                &participant.game_name,
+                x + 84,
+                y + 20,
+                28.0,
+                TEXT,
+                300,
+            );
+            draw_text(
+                &mut card,
+                &format!(
+                    "{}/{}/{}",
+                    participant.kills, participant.deaths, participant.assists
+                ),
+                x + 400,
+                y + 20,
+                28.0,
+                MUTED,
+                130,
+            );
+        }
+    }
+
+    draw_footer(&mut card);
+    card
+}
+
+fn draw_footer(card: &mut RgbaImage) {
+    draw_text(card, "ruche.lol", 1010, 590, 26.0, MUTED, 170);
+}
+
+fn fill_rect(card: &mut RgbaImage, x: i64, y: i64, width: u32, height: u32, color: Rgba<u8>) {
+    let panel = RgbaImage::from_pixel(width, height, color);
+    image::imageops::overlay(card, &panel, x, y);
+}
+
+fn draw_image(card: &mut RgbaImage, image: &RgbaImage, x: i64, y: i64, size: u32) {
+    let resized = image::imageops::resize(image, size, size, FilterType::Triangle);
+    image::imageops::overlay(card, &resized, x, y);
+}
+
+/// Draws one line of text with its top at `y`, cut with an ellipsis past `max_width`.
+fn draw_text(
+    card: &mut RgbaImage,
+    text: &str,
+    x: i64,
+    y: i64,
+    size: f32,
+    color: Rgba<u8>,
+    max_width: u32,
+) {
+    let Some(font) = OG_ASSETS.font.as_ref() else {
+        return;
+    };
+    let scale = PxScale::from(size);
+    let scaled = font.as_scaled(scale);
+    let text = fit_text(font, scale, text, max_width as f32);
+
+    let mut caret = x as f32;
+    let baseline = y as f32 + scaled.ascent();
+    let mut previous: Option<GlyphId> = None;
+    for c in text.chars() {
+        let glyph_id = scaled.glyph_id(c);
+        if let Some(previous) = previous {
+            caret += scaled.kern(previous, glyph_id);
+        }
+        let glyph = glyph_id.with_scale_and_position(scale, point(caret, baseline));
+        caret += scaled.h_advance(glyph_id);
+        previous = Some(glyph_id);
+        let Some(outlined) = font.outline_glyph(glyph) else {
+            continue;
+        };
+        let bounds = outlined.px_bounds();
+        outlined.draw(|gx, gy, coverage| {
+            let px = bounds.min.x as i64 + gx as i64;
+            let py = bounds.min.y as i64 + gy as i64;
+            if px < 0 || py < 0 || px >= card.width() as i64 || py >= card.height() as i64 {
+                return;
+            }
+            let pixel = card.get_pixel_mut(px as u32, py as u32);
+            let alpha = coverage.clamp(0.0, 1.0);
+            for channel in 0..3 {
+                pixel.0[channel] = (pixel.0[channel] as f32 * (1.0 - alpha)
+                    + color.0[channel] as f32 * alpha)
+                    .round() as u8;
+            }
+        });
+    }
+}
+
+fn text_width(font: &FontArc, scale: PxScale, text: &str) -> f32 {
+    let scaled = font.as_scaled(scale);
+    text.chars()
+        .map(|c| scaled.h_advance(scaled.glyph_id(c)))
+        .sum()
+}
+
+fn fit_text(font: &FontArc, scale: PxScale, text: &str, max_width: f32) -> String {
+    if text_width(font, scale, text) <= max_width {
+        return text.to_string();
+    }
+    let mut fitted = text.to_string();
+    while !fitted.is_empty() && text_width(font, scale, &format!("{}…", fitted)) > max_width {
+        fitted.pop();
+    }
+    format!("{}…", fitted)
+}
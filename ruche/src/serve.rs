@@ -1,8 +1,10 @@
+use crate::ssr::AppState;
+use crate::webtransport::serve_h3;
 use axum::body::Body;
 use axum::extract::Request;
 use axum::handler::HandlerWithoutStateExt;
 use axum::response::{IntoResponse, Redirect};
-use axum::{BoxError, Router};
+use axum::{BoxError, Json, Router};
 use axum_extra::extract::Host;
 use axum_server::tls_rustls::RustlsConfig;
 use http::{HeaderValue, StatusCode, Uri};
@@ -17,16 +19,25 @@ use tower::ServiceExt;
 use tower_http::services::ServeFile;
 use tower_http::set_header::SetResponseHeaderLayer;
 
-pub async fn serve(app: Router, is_prod: bool, socket_addr: SocketAddr) -> Result<(), axum::Error> {
+pub async fn serve(
+    app: Router,
+    app_state: AppState,
+    is_prod: bool,
+    socket_addr: SocketAddr,
+) -> Result<(), axum::Error> {
     if is_prod {
         tokio::spawn(redirect_http_to_https());
-        serve_with_tsl(app, socket_addr).await
+        serve_with_tsl(app, app_state, socket_addr).await
     } else {
         serve_locally(app, socket_addr).await
     }
 }
 
-pub async fn serve_with_tsl(app: Router, socket_addr: SocketAddr) -> Result<(), axum::Error> {
+pub async fn serve_with_tsl(
+    app: Router,
+    app_state: AppState,
+    socket_addr: SocketAddr,
+) -> Result<(), axum::Error> {
     let lets_encrypt_dir = dotenv::var("LETS_ENCRYPT_PATH").expect("LETS_ENCRYPT_PATH not set");
     let lets_encrypt_dir = PathBuf::from(lets_encrypt_dir);
     let cert = lets_encrypt_dir.join("fullchain.pem");
@@ -46,12 +57,11 @@ pub async fn serve_with_tsl(app: Router, socket_addr: SocketAddr) -> Result<(),
     // Create a QUIC (HTTP/3) endpoint so we advertise Alt-Svc for browsers.
     // Keep the endpoint alive for the lifetime of the server by binding it here.
     let quic_ep = make_quinn_server_endpoint_dual(socket_addr, h3_tls);
-    let acceptor = h3_util::quinn::H3QuinnAcceptor::new(quic_ep);
 
-    // Spawn the H3 router in the background. Clone the app so we don't move it
-    // twice (once into the H3 task, once into the h2 server below).
+    // Serve H3 (and WebTransport sessions) in the background. Clone the app so we
+    // don't move it twice (once into the H3 task, once into the h2 server below).
     let alt_svc_value = format!("h3=\":{}\"; ma=2592000; persist=1", socket_addr.port());
-    let srv_h = axum_h3::H3Router::new(app.clone()).serve(acceptor);
+    let srv_h = serve_h3(quic_ep, app.clone(), app_state);
     log!("listening on {}", socket_addr);
     // Advertise HTTP/3 (h3) to browsers using Alt-Svc so that clients can attempt h3 (QUIC) on h3_addr
     let app = app.layer(SetResponseHeaderLayer::if_not_present(
@@ -59,11 +69,8 @@ pub async fn serve_with_tsl(app: Router, socket_addr: SocketAddr) -> Result<(),
         HeaderValue::from_str(&alt_svc_value).unwrap(),
     ));
     let srv = axum_server::bind_rustls(socket_addr, config).serve(app.into_make_service());
-    let (srv_h, srv) = tokio::join!(srv_h, srv,);
-    match srv_h {
-        Ok(_) => log!("H3 server exited normally"),
-        Err(e) => log!("H3 server exited with error: {}", e),
-    }
+    let ((), srv) = tokio::join!(srv_h, srv,);
+    log!("H3 server exited");
     match srv {
         Ok(_) => log!("H2 server exited normally"),
         Err(e) => log!("H2 server exited with error: {}", e),
@@ -136,6 +143,10 @@ pub async fn get_sitemap() -> impl IntoResponse {
     }
 }
 
+pub async fn get_riot_budget() -> impl IntoResponse {
+    Json(crate::ssr::RIOT_BUDGET.usage())
+}
+
 pub fn make_quinn_server_endpoint_dual(
     in_addr: SocketAddr,
     tls_config: Arc<ServerConfig>,
@@ -149,7 +160,7 @@ pub fn make_quinn_server_endpoint_dual(
         std::time::Duration::from_secs(120).try_into().unwrap(),
     ));
     tcfg.keep_alive_interval(Some(std::time::Duration::from_secs(15)));
-    // si vous projetez d’utiliser des datagrams (WebTransport plus tard)
+    // datagrams WebTransport (voir crate::webtransport)
     tcfg.datagram_receive_buffer_size(Some(1 << 20)); // 1 MiB
 
     let mut scfg = quinn::ServerConfig::with_crypto(Arc::new(
@@ -1,21 +1,29 @@
+use std::collections::{HashMap, VecDeque};
 use std::convert::Infallible;
 use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
-use std::sync::{Arc, Weak};
+use std::sync::{Arc, Mutex, OnceLock, Weak};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use axum::extract::{Path, State};
 use axum::response::sse::{Event, KeepAlive};
 use axum::response::Sse;
 use dashmap::{DashMap, DashSet};
+use http::HeaderMap;
 use leptos::logging::log;
-use tokio::sync::watch;
+use sqlx::PgPool;
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{StreamExt, StreamMap};
 
 use crate::backend::server_fns::get_encounter::ssr::find_summoner_puuid_by_id;
 use crate::backend::server_fns::get_live_game::ssr;
 use crate::ssr::AppState;
-use crate::utils::Puuid;
+use crate::utils::{Puuid, RiotMatchId, SseEvent, SseMultiplexEvent};
 use common::consts::platform_route::PlatformRoute;
 
+mod pg_notify;
+
 const GC_GRACE_MS: u64 = 10_000;
 const DEBOUNCE_MS: u64 = 500;
 #[inline]
@@ -26,41 +34,150 @@ fn now_millis() -> u64 {
         .as_millis() as u64
 }
 
-#[derive(Debug, Clone, Default, PartialEq, Eq, Copy)]
-pub struct SseSnapshot {
-    pub live_ver: u64,  // 0 = pas de partie ; >0 = epoch live
-    pub match_ver: u64, // monotone
+const CHANNEL_CAPACITY: usize = 64;
+/// Événements conservés par topic pour rejouer un `Last-Event-ID`
+const HISTORY_LEN: usize = 128;
+
+/// Événement numéroté ; l'id SSE est `"{epoch}-{seq}"`
+pub type SequencedEvent = (u64, SseEvent);
+
+/// État courant d'un summoner + événements accumulés depuis le dernier envoi
+#[derive(Default)]
+struct TopicState {
+    // date de création du topic : distingue les ids d'un topic recréé après GC
+    epoch: u64,
+    seq: u64,
+    history: VecDeque<SequencedEvent>,
+    live_game: Option<RiotMatchId>,
+    ingestion: Option<(u32, u32)>,
+    pending_matches: Vec<i32>,
+    // Some(x) = transition vers x depuis le dernier envoi
+    pending_live: Option<Option<RiotMatchId>>,
+    // même partie, mais phase / timer / draft modifiés
+    pending_live_update: bool,
+    pending_profile: Option<SseEvent>,
+    pending_ingestion: bool,
 }
 
-impl SseSnapshot {
-    pub fn new(live_ver: u64, match_ver: u64) -> Self {
-        Self {
-            live_ver,
-            match_ver,
+impl TopicState {
+    fn event_id(&self, seq: u64) -> String {
+        format!("{}-{}", self.epoch, seq)
+    }
+
+    /// Numérote `event` et le garde dans l'historique borné
+    fn record(&mut self, event: SseEvent) -> SequencedEvent {
+        self.seq += 1;
+        if self.history.len() == HISTORY_LEN {
+            self.history.pop_front();
+        }
+        self.history.push_back((self.seq, event.clone()));
+        (self.seq, event)
+    }
+
+    /// Événements manqués depuis `last_event_id`, ou `Resync` + état courant si
+    /// l'id est inconnu (autre epoch, historique déjà tourné)
+    fn replay_since(&self, last_event_id: Option<&str>) -> Vec<SequencedEvent> {
+        let Some(last_event_id) = last_event_id else {
+            return self.snapshot();
+        };
+        let last_seq = last_event_id
+            .split_once('-')
+            .filter(|(epoch, _)| epoch.parse::<u64>().ok() == Some(self.epoch))
+            .and_then(|(_, seq)| seq.parse::<u64>().ok());
+        let oldest = self.history.front().map_or(self.seq + 1, |(seq, _)| *seq);
+        match last_seq {
+            Some(last_seq) if last_seq <= self.seq && last_seq + 1 >= oldest => self
+                .history
+                .iter()
+                .filter(|(seq, _)| *seq > last_seq)
+                .cloned()
+                .collect(),
+            _ => self.resync(),
+        }
+    }
+
+    fn resync(&self) -> Vec<SequencedEvent> {
+        let mut events = vec![(self.seq, SseEvent::Resync)];
+        events.extend(self.snapshot());
+        events
+    }
+
+    /// État courant, numéroté avec le dernier seq émis
+    fn snapshot(&self) -> Vec<SequencedEvent> {
+        self.current_events()
+            .into_iter()
+            .map(|event| (self.seq, event))
+            .collect()
+    }
+
+    /// Événements à envoyer à un nouvel abonné
+    fn current_events(&self) -> Vec<SseEvent> {
+        let mut events = Vec::new();
+        if let Some(game_id) = self.live_game {
+            events.push(SseEvent::LiveGameStarted(game_id));
+        }
+        if let Some((done, total)) = self.ingestion {
+            events.push(SseEvent::IngestionProgress { done, total });
         }
+        events
+    }
+
+    /// Vide la rafale en cours, un événement par type au maximum
+    fn take_pending(&mut self) -> Vec<SseEvent> {
+        let mut events = Vec::new();
+        if !self.pending_matches.is_empty() {
+            events.push(SseEvent::NewMatches(std::mem::take(
+                &mut self.pending_matches,
+            )));
+        }
+        match self.pending_live.take() {
+            Some(Some(game_id)) => events.push(SseEvent::LiveGameStarted(game_id)),
+            Some(None) => events.push(SseEvent::LiveGameEnded),
+            // un `LiveGameStarted` fait déjà recharger la partie
+            None => {
+                if let Some(game_id) = self.live_game.filter(|_| self.pending_live_update) {
+                    events.push(SseEvent::LiveGameUpdated(game_id));
+                }
+            }
+        }
+        self.pending_live_update = false;
+        if let Some(profile) = self.pending_profile.take() {
+            events.push(profile);
+        }
+        if std::mem::take(&mut self.pending_ingestion) {
+            let (done, total) = self.ingestion.unwrap_or_default();
+            events.push(SseEvent::IngestionProgress { done, total });
+        }
+        events
     }
 }
 
 #[derive(Clone)]
 pub struct Topic {
-    tx: watch::Sender<SseSnapshot>,
-    state: Arc<KeyState>,
+    tx: broadcast::Sender<SequencedEvent>,
+    state: Arc<Mutex<TopicState>>,
     subs: Arc<AtomicUsize>,
     last_empty_at: Arc<AtomicU64>,
     last_sent_at: Arc<AtomicU64>,
     pending: Arc<AtomicBool>,
 }
 
-#[derive(Default)]
-pub struct KeyState {
-    live_ver: std::sync::atomic::AtomicU64,
-    match_ver: std::sync::atomic::AtomicU64,
-}
-
 pub struct Hub {
     pub topics: DashMap<i32, Topic>,
     pub dirty: DashSet<i32>,
     gc: DashSet<i32>,
+    // connexions `/sse/stream` ouvertes -> canal de leurs (dés)abonnements
+    connections: DashMap<u64, mpsc::UnboundedSender<SubscriptionChange>>,
+    // présent si SSE_PG_NOTIFY : les mises à jour transitent par Postgres
+    pg_publisher: OnceLock<mpsc::UnboundedSender<(i32, SseEvent)>>,
+    // enregistré au démarrage : caches à vider quand un summoner a de nouveaux matchs
+    new_matches_listener: OnceLock<Box<dyn Fn(i32) + Send + Sync>>,
+}
+
+/// (Dés)abonnements demandés pour une connexion multiplexée
+pub enum SubscriptionChange {
+    Add(Vec<(PlatformRoute, i32)>),
+    Remove(Vec<i32>),
 }
 
 impl Hub {
@@ -69,17 +186,31 @@ impl Hub {
             topics: DashMap::new(),
             dirty: DashSet::new(),
             gc: DashSet::new(),
+            connections: DashMap::new(),
+            pg_publisher: OnceLock::new(),
+            new_matches_listener: OnceLock::new(),
         })
     }
 
+    /// `listener` est appelé à chaque `NewMatches` appliqué, y compris ceux reçus
+    /// d'une autre instance via `pg_notify`
+    pub fn on_new_matches(&self, listener: impl Fn(i32) + Send + Sync + 'static) {
+        if self.new_matches_listener.set(Box::new(listener)).is_err() {
+            log!("Hub: new matches listener already set");
+        }
+    }
+
     fn or_insert_topic(&self, sid: i32) -> Topic {
         self.topics
             .entry(sid)
             .or_insert_with(|| {
-                let (tx, _rx) = watch::channel(SseSnapshot::default());
+                let (tx, _rx) = broadcast::channel(CHANNEL_CAPACITY);
                 Topic {
                     tx,
-                    state: Arc::new(KeyState::default()),
+                    state: Arc::new(Mutex::new(TopicState {
+                        epoch: now_millis(),
+                        ..TopicState::default()
+                    })),
                     subs: Arc::new(AtomicUsize::new(0)),
                     last_empty_at: Arc::new(AtomicU64::new(0)),
                     last_sent_at: Arc::new(AtomicU64::new(0)),
@@ -89,52 +220,143 @@ impl Hub {
             .clone()
     }
 
+    /// Applique `f` à l'état du topic (s'il existe) puis programme un envoi
     #[inline]
-    fn mark_pending(&self, sid: i32) {
+    fn update_state(&self, sid: i32, f: impl FnOnce(&mut TopicState)) {
         if let Some(t) = self.topics.get(&sid) {
+            f(&mut t.state.lock().unwrap());
             t.pending.store(true, Ordering::SeqCst);
             self.dirty.insert(sid);
         }
     }
 
     // Garde la version simple si vous en avez besoin ailleurs
-    pub fn subscribe(&self, sid: i32) -> watch::Receiver<SseSnapshot> {
+    pub fn subscribe(&self, sid: i32) -> broadcast::Receiver<SequencedEvent> {
         self.or_insert_topic(sid).tx.subscribe()
     }
 
-    pub fn bump_matches(&self, sid: i32) {
-        if let Some(t) = self.topics.get(&sid) {
-            t.state.match_ver.fetch_add(1, Ordering::SeqCst);
+    /// Nouveaux `lol_matches.id` ingérés pour ce summoner
+    pub fn push_new_matches(&self, sid: i32, match_ids: &[i32]) {
+        if match_ids.is_empty() {
+            return;
         }
-        self.mark_pending(sid);
+        self.dispatch(sid, SseEvent::NewMatches(match_ids.to_vec()));
     }
 
-    pub fn ensure_live_present(&self, sid: i32) {
-        if let Some(t) = self.topics.get(&sid) {
-            if t.state.live_ver.load(Ordering::SeqCst) == 0 {
-                t.state.live_ver.store(1, Ordering::SeqCst);
-            }
+    pub fn live_game_started(&self, sid: i32, game_id: RiotMatchId) {
+        self.dispatch(sid, SseEvent::LiveGameStarted(game_id));
+    }
+
+    pub fn live_game_ended(&self, sid: i32) {
+        self.dispatch(sid, SseEvent::LiveGameEnded);
+    }
+
+    /// La partie en cours a changé de phase, d'heure de début ou de draft
+    pub fn live_game_updated(&self, sid: i32, game_id: RiotMatchId) {
+        self.dispatch(sid, SseEvent::LiveGameUpdated(game_id));
+    }
+
+    pub fn profile_changed(
+        &self,
+        sid: i32,
+        game_name: String,
+        tag_line: String,
+        summoner_level: u16,
+        profile_icon_id: u16,
+    ) {
+        self.dispatch(
+            sid,
+            SseEvent::ProfileChanged {
+                game_name,
+                tag_line,
+                summoner_level,
+                profile_icon_id,
+            },
+        );
+    }
+
+    /// Progression de l'ingestion prioritaire ; `total == 0` efface l'indicateur
+    pub fn set_ingestion_progress(&self, sid: i32, done: u32, total: u32) {
+        self.dispatch(sid, SseEvent::IngestionProgress { done, total });
+    }
+
+    /// Active le fan-out inter-instances : les mises à jour passent par `pg_notify`
+    /// et ne sont appliquées qu'à leur réception (y compris par l'émetteur)
+    pub fn enable_pg_notify(self: &Arc<Self>, db: PgPool) {
+        let publisher = pg_notify::spawn(self.clone(), db);
+        if self.pg_publisher.set(publisher).is_err() {
+            log!("Hub: pg_notify already enabled");
         }
-        self.mark_pending(sid);
     }
 
-    pub fn bump_live_epoch(&self, sid: i32) {
-        if let Some(t) = self.topics.get(&sid) {
-            let cur = t.state.live_ver.load(Ordering::SeqCst);
-            let next = if cur == 0 { 1 } else { cur.saturating_add(1) };
-            t.state.live_ver.store(next, Ordering::SeqCst);
+    fn dispatch(&self, sid: i32, update: SseEvent) {
+        match self.pg_publisher.get() {
+            Some(publisher) => {
+                if publisher.send((sid, update)).is_err() {
+                    log!("Hub: pg_notify publisher stopped, update dropped sid={}", sid);
+                }
+            }
+            None => self.apply(sid, update),
         }
-        self.mark_pending(sid);
     }
 
-    pub fn set_live_none(&self, sid: i32) {
-        if let Some(t) = self.topics.get(&sid) {
-            t.state.live_ver.store(0, Ordering::SeqCst);
+    /// Applique une mise à jour aux abonnés locaux ; le debounce reste celui de `run`
+    fn apply(&self, sid: i32, update: SseEvent) {
+        match update {
+            SseEvent::NewMatches(match_ids) => {
+                if let Some(listener) = self.new_matches_listener.get() {
+                    listener(sid);
+                }
+                self.update_state(sid, |s| {
+                    for id in match_ids {
+                        if !s.pending_matches.contains(&id) {
+                            s.pending_matches.push(id);
+                        }
+                    }
+                })
+            }
+            SseEvent::LiveGameStarted(game_id) => self.update_state(sid, |s| {
+                if s.live_game != Some(game_id) {
+                    s.live_game = Some(game_id);
+                    s.pending_live = Some(Some(game_id));
+                }
+            }),
+            SseEvent::LiveGameEnded => self.update_state(sid, |s| {
+                if s.live_game.take().is_some() || s.pending_live.is_some() {
+                    s.pending_live = Some(None);
+                }
+            }),
+            SseEvent::LiveGameUpdated(game_id) => self.update_state(sid, |s| {
+                if s.live_game != Some(game_id) {
+                    s.live_game = Some(game_id);
+                    s.pending_live = Some(Some(game_id));
+                } else {
+                    s.pending_live_update = true;
+                }
+            }),
+            SseEvent::ProfileChanged {
+                game_name,
+                tag_line,
+                summoner_level,
+                profile_icon_id,
+            } => self.update_state(sid, |s| {
+                s.pending_profile = Some(SseEvent::ProfileChanged {
+                    game_name,
+                    tag_line,
+                    summoner_level,
+                    profile_icon_id,
+                });
+            }),
+            SseEvent::IngestionProgress { done, total } => self.update_state(sid, |s| {
+                s.ingestion = (total > 0).then_some((done, total));
+                s.pending_ingestion = true;
+            }),
+            // propre à chaque instance, jamais publié (voir `resync_all`)
+            SseEvent::Resync => {}
         }
-        self.mark_pending(sid);
     }
 
-    /// Tick périodique : publie les snapshots et ramasse les topics sans abonnés
+    /// Tick périodique : publie les événements en attente et ramasse les topics sans abonnés
     pub async fn run(self: Arc<Self>, period: Duration) {
         let mut tick = tokio::time::interval(period);
         loop {
@@ -165,17 +387,15 @@ impl Hub {
                     let due = last == 0 || now.saturating_sub(last) >= DEBOUNCE_MS;
 
                     if due {
-                        let snap = SseSnapshot::new(
-                            t.state.live_ver.load(Ordering::SeqCst),
-                            t.state.match_ver.load(Ordering::SeqCst),
-                        );
-                        let prev = *t.tx.borrow();
-                        if prev != snap {
-                            let _ = t.tx.send_replace(snap);
+                        // consommation de la rafale (avant le take pour ne rien perdre)
+                        t.pending.store(false, Ordering::SeqCst);
+                        // envoi sous le verrou : l'ordre des seq reste celui du flux
+                        let mut state = t.state.lock().unwrap();
+                        for event in state.take_pending() {
+                            let _ = t.tx.send(state.record(event));
                         }
+                        drop(state);
                         t.last_sent_at.store(now, Ordering::SeqCst);
-                        // consommation de la rafale
-                        t.pending.store(false, Ordering::SeqCst);
                         // on retire de dirty; une nouvelle rafale le réinsèrera
                         self.dirty.remove(&sid);
                     } else {
@@ -213,20 +433,68 @@ impl Hub {
     }
 }
 
+/// Abonnement + événements à envoyer en premier : ceux manqués depuis
+/// `last_event_id`, sinon l'état courant
 pub fn subscribe_with_guard(
     hub: Arc<Hub>,
     sid: i32,
-) -> (watch::Receiver<SseSnapshot>, SubscriptionGuard) {
+    last_event_id: Option<&str>,
+) -> (
+    broadcast::Receiver<SequencedEvent>,
+    Vec<SequencedEvent>,
+    SubscriptionGuard,
+) {
     let t = hub.or_insert_topic(sid);
     t.subs.fetch_add(1, Ordering::SeqCst);
-    let rx = t.tx.subscribe();
+    // abonnement sous le verrou : aucun événement perdu entre l'historique et le flux
+    let (rx, current) = {
+        let state = t.state.lock().unwrap();
+        (t.tx.subscribe(), state.replay_since(last_event_id))
+    };
     let guard = SubscriptionGuard {
         hub: Arc::downgrade(&hub),
         sid,
         subs: t.subs.clone(),
         last_empty_at: t.last_empty_at.clone(),
     };
-    (rx, guard)
+    (rx, current, guard)
+}
+
+impl Hub {
+    /// `Resync` + état courant, pour un abonné qui a décroché
+    fn resync(&self, sid: i32) -> Vec<SequencedEvent> {
+        self.topics
+            .get(&sid)
+            .map(|t| t.state.lock().unwrap().resync())
+            .unwrap_or_default()
+    }
+
+    /// `Resync` + état courant sur tous les topics locaux, quand des mises à jour
+    /// ont pu être perdues (reconnexion du listener pg_notify)
+    fn resync_all(&self) {
+        for t in self.topics.iter() {
+            let mut state = t.state.lock().unwrap();
+            let mut events = vec![SseEvent::Resync];
+            events.extend(state.current_events());
+            for event in events {
+                let _ = t.tx.send(state.record(event));
+            }
+        }
+    }
+
+    /// Transmet `change` à la connexion `/sse/stream`, `false` si elle est fermée
+    pub fn change_subscriptions(&self, connection_id: u64, change: SubscriptionChange) -> bool {
+        self.connections
+            .get(&connection_id)
+            .is_some_and(|tx| tx.send(change).is_ok())
+    }
+
+    fn event_id(&self, sid: i32, seq: u64) -> String {
+        self.topics
+            .get(&sid)
+            .map(|t| t.state.lock().unwrap().event_id(seq))
+            .unwrap_or_else(|| seq.to_string())
+    }
 }
 
 pub struct SubscriptionGuard {
@@ -248,69 +516,180 @@ impl Drop for SubscriptionGuard {
     }
 }
 
-fn snapshot_to_payload(s: &SseSnapshot) -> String {
-    // Votre format actuel: "live_ver?:match_ver" (live vide si 0)
-    if s.live_ver == 0 {
-        format!(":{}", s.match_ver)
+/// Publie la partie en cours du summoner (cache live, sinon appel spectator en tâche de fond)
+async fn refresh_live_game(state: &AppState, platform: PlatformRoute, summoner_id: i32) {
+    if let Some(live) = state.live_game_cache.get_game_data(summoner_id).await {
+        state.hub.live_game_started(summoner_id, live.game_id);
     } else {
-        format!("{}:{}", s.live_ver, s.match_ver)
+        let hub = state.hub.clone();
+        let db = state.db.clone();
+        let riot_api = state.riot_api.clone();
+        tokio::spawn(async move {
+            // summoner inconnu ou erreur db : pas de partie à publier
+            let puuid = match find_summoner_puuid_by_id(&db, summoner_id).await {
+                Ok(puuid) => Puuid::new(puuid.as_str()),
+                Err(_) => {
+                    hub.live_game_ended(summoner_id);
+                    return;
+                }
+            };
+            match ssr::get_live_game_data(&db, &riot_api, puuid, platform).await {
+                Ok(Some((_ids, live))) => hub.live_game_started(summoner_id, live.game_id),
+                Ok(None) | Err(_) => hub.live_game_ended(summoner_id),
+            }
+        });
     }
 }
+
 pub async fn sse_broadcast_match_updated(
     Path((platform_route, summoner_id)): Path<(String, i32)>,
     State(state): State<AppState>,
+    headers: HeaderMap,
 ) -> Sse<impl tokio_stream::Stream<Item = Result<Event, Infallible>>> {
     let platform = PlatformRoute::from_code(&platform_route).expect("invalid platform");
 
     // IMPORTANT: on récupère un guard pour décrémenter à la fermeture de la connexion SSE
-    let (mut rx, guard) = subscribe_with_guard(state.hub.clone(), summoner_id);
-
-    if state
-        .live_game_cache
-        .get_game_data(summoner_id)
-        .await
-        .is_some()
-    {
-        state.hub.ensure_live_present(summoner_id);
-    } else {
-        let hub = state.hub.clone();
-        let db = state.db.clone();
-        let riot_api = state.riot_api.clone();
-        tokio::spawn(async move {
-            let puuid = Puuid::new(
-                find_summoner_puuid_by_id(&db, summoner_id)
-                    .await
-                    .expect("puuid")
-                    .as_str(),
-            );
-            match ssr::get_live_game_data(&db, &riot_api, puuid, platform).await {
-                Ok(Some((_ids, _live))) => hub.bump_live_epoch(summoner_id),
-                Ok(None) | Err(_) => hub.set_live_none(summoner_id),
-            }
-        });
-    }
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok());
+    let (mut rx, current, guard) =
+        subscribe_with_guard(state.hub.clone(), summoner_id, last_event_id);
 
+    refresh_live_game(&state, platform, summoner_id).await;
+
+    let hub = state.hub.clone();
     let stream = async_stream::stream! {
-        let mut eid: u64 = 0;
         let _keep_guard_alive = guard;
-        // Optionnel: envoi immédiat si déjà live
-        let first = *rx.borrow();
-        if first.live_ver != 0 {
+        // rattrapage (Last-Event-ID) ou état courant envoyé immédiatement
+        for (seq, event) in current {
             yield Ok(Event::default()
-                .id({eid+=1; eid}.to_string())
-                .data(snapshot_to_payload(&SseSnapshot::new(1, 0)))
+                .id(hub.event_id(summoner_id, seq))
+                .data(event.to_payload())
                 .retry(Duration::from_millis(3000)));
         }
 
         loop {
-            if rx.changed().await.is_err() { break; }
-            let snap = *rx.borrow();
-            yield Ok(Event::default()
-                .id({ eid = eid.wrapping_add(1); eid }.to_string())
-                .data(snapshot_to_payload(&snap))
-                .retry(Duration::from_millis(3000)));
+            let events = match rx.recv().await {
+                Ok(event) => vec![event],
+                // abonné trop lent : les événements perdus sont remplacés par un resync
+                Err(broadcast::error::RecvError::Lagged(_)) => hub.resync(summoner_id),
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+            for (seq, event) in events {
+                yield Ok(Event::default()
+                    .id(hub.event_id(summoner_id, seq))
+                    .data(event.to_payload())
+                    .retry(Duration::from_millis(3000)));
+            }
+        }
+    };
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+const MAX_TOPICS_PER_CONNECTION: usize = 32;
+
+fn new_connection_id() -> u64 {
+    // RandomState est initialisé aléatoirement : id non devinable sans dépendance
+    use std::hash::{BuildHasher, Hasher};
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u64(now_millis());
+    hasher.finish()
+}
+
+struct ConnectionGuard {
+    hub: Weak<Hub>,
+    connection_id: u64,
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        if let Some(h) = self.hub.upgrade() {
+            h.connections.remove(&self.connection_id);
         }
+    }
+}
+
+enum MultiplexNext {
+    Change(Option<SubscriptionChange>),
+    Event(i32, Result<SequencedEvent, BroadcastStreamRecvError>),
+}
+
+/// Flux de plusieurs summoners, partagé par `/sse/stream` et WebTransport. Le
+/// premier message donne l'id de connexion ; les topics sont ajoutés/retirés via
+/// `update_sse_subscriptions`.
+pub fn multiplex_events(
+    state: AppState,
+) -> impl tokio_stream::Stream<Item = SseMultiplexEvent> + Send {
+    let connection_id = new_connection_id();
+    let (tx, mut changes) = mpsc::unbounded_channel();
+    state.hub.connections.insert(connection_id, tx);
+    let connection_guard = ConnectionGuard {
+        hub: Arc::downgrade(&state.hub),
+        connection_id,
     };
 
+    async_stream::stream! {
+        let _keep_connection_alive = connection_guard;
+        let hub = state.hub.clone();
+        let mut streams: StreamMap<i32, BroadcastStream<SequencedEvent>> = StreamMap::new();
+        // un guard par topic : même refcount que les flux par summoner
+        let mut guards: HashMap<i32, SubscriptionGuard> = HashMap::new();
+
+        yield SseMultiplexEvent::Connected(connection_id);
+
+        loop {
+            let next = tokio::select! {
+                change = changes.recv() => MultiplexNext::Change(change),
+                Some((sid, item)) = streams.next(), if !streams.is_empty() => {
+                    MultiplexNext::Event(sid, item)
+                }
+            };
+            let events: Vec<(i32, SequencedEvent)> = match next {
+                MultiplexNext::Change(None) => break,
+                MultiplexNext::Change(Some(SubscriptionChange::Add(topics))) => {
+                    let mut events = Vec::new();
+                    for (platform, sid) in topics {
+                        if guards.contains_key(&sid) || guards.len() >= MAX_TOPICS_PER_CONNECTION {
+                            continue;
+                        }
+                        let (rx, current, guard) = subscribe_with_guard(hub.clone(), sid, None);
+                        guards.insert(sid, guard);
+                        streams.insert(sid, BroadcastStream::new(rx));
+                        refresh_live_game(&state, platform, sid).await;
+                        events.extend(current.into_iter().map(|event| (sid, event)));
+                    }
+                    events
+                }
+                MultiplexNext::Change(Some(SubscriptionChange::Remove(sids))) => {
+                    for sid in sids {
+                        streams.remove(&sid);
+                        guards.remove(&sid);
+                    }
+                    continue;
+                }
+                MultiplexNext::Event(sid, Ok(event)) => vec![(sid, event)],
+                // abonné trop lent sur ce topic : resync du topic seul
+                MultiplexNext::Event(sid, Err(BroadcastStreamRecvError::Lagged(_))) => hub
+                    .resync(sid)
+                    .into_iter()
+                    .map(|event| (sid, event))
+                    .collect(),
+            };
+            for (summoner_id, (_seq, event)) in events {
+                yield SseMultiplexEvent::Topic { summoner_id, event };
+            }
+        }
+    }
+}
+
+pub async fn sse_multiplex(
+    State(state): State<AppState>,
+) -> Sse<impl tokio_stream::Stream<Item = Result<Event, Infallible>>> {
+    let stream = multiplex_events(state).map(|event| {
+        Ok(Event::default()
+            .data(event.to_payload())
+            .retry(Duration::from_millis(3000)))
+    });
     Sse::new(stream).keep_alive(KeepAlive::default())
 }
@@ -0,0 +1,91 @@
+use super::Hub;
+use crate::utils::SseEvent;
+use base64::Engine;
+use leptos::logging::log;
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+const CHANNEL: &str = "ruche_sse";
+// une notification par mise à jour, regroupées par requête
+const PUBLISH_BATCH: usize = 256;
+// Postgres fusionne les notifications identiques d'une même transaction :
+// chaque payload porte un numéro pour que deux mises à jour égales passent
+static PUBLISH_SEQ: AtomicU64 = AtomicU64::new(0);
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Démarre publisher + listener ; renvoie l'entrée du publisher
+pub(super) fn spawn(hub: Arc<Hub>, db: PgPool) -> mpsc::UnboundedSender<(i32, SseEvent)> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(publish(db.clone(), rx));
+    tokio::spawn(listen(hub, db));
+    tx
+}
+
+fn encode((sid, update): (i32, SseEvent)) -> String {
+    let seq = PUBLISH_SEQ.fetch_add(1, Ordering::Relaxed);
+    base64::engine::general_purpose::STANDARD_NO_PAD.encode(bitcode::encode(&(seq, sid, update)))
+}
+
+fn decode(payload: &str) -> Option<(i32, SseEvent)> {
+    let bytes = base64::engine::general_purpose::STANDARD_NO_PAD
+        .decode(payload)
+        .ok()?;
+    let (_seq, sid, update) = bitcode::decode::<(u64, i32, SseEvent)>(&bytes).ok()?;
+    Some((sid, update))
+}
+
+async fn publish(db: PgPool, mut rx: mpsc::UnboundedReceiver<(i32, SseEvent)>) {
+    let mut batch = Vec::with_capacity(PUBLISH_BATCH);
+    while rx.recv_many(&mut batch, PUBLISH_BATCH).await > 0 {
+        let payloads = batch.drain(..).map(encode).collect::<Vec<_>>();
+        if let Err(e) = sqlx::query("SELECT pg_notify($1, p) FROM UNNEST($2::TEXT[]) AS p")
+            .bind(CHANNEL)
+            .bind(&payloads)
+            .execute(&db)
+            .await
+        {
+            log!("Hub pg_notify: {} updates dropped: {:?}", payloads.len(), e);
+        }
+    }
+}
+
+/// Applique aux abonnés locaux les mises à jour de toutes les instances.
+/// Les notifications émises pendant une reconnexion sont perdues : les topics
+/// locaux reçoivent alors un `Resync`.
+async fn listen(hub: Arc<Hub>, db: PgPool) {
+    let mut reconnecting = false;
+    loop {
+        let mut listener = match PgListener::connect_with(&db).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                log!("Hub pg_notify: listener connection failed: {:?}", e);
+                tokio::time::sleep(RECONNECT_DELAY).await;
+                continue;
+            }
+        };
+        if let Err(e) = listener.listen(CHANNEL).await {
+            log!("Hub pg_notify: LISTEN failed: {:?}", e);
+            tokio::time::sleep(RECONNECT_DELAY).await;
+            continue;
+        }
+        if std::mem::replace(&mut reconnecting, true) {
+            hub.resync_all();
+        }
+        loop {
+            match listener.recv().await {
+                Ok(notification) => match decode(notification.payload()) {
+                    Some((sid, update)) => hub.apply(sid, update),
+                    None => log!("Hub pg_notify: invalid payload ignored"),
+                },
+                Err(e) => {
+                    log!("Hub pg_notify: listener error: {:?}", e);
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,7 @@
+use crate::app::SummonerIdentifier;
 use bitcode::{Decode, Encode};
 use common::consts::item::Item;
+use common::consts::platform_route::PlatformRoute;
 use reactive_stores_macro::Store;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Encode, Decode)]
@@ -57,6 +59,21 @@ pub fn format_duration(seconds: Option<i32>) -> String {
     format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
 }
 
+/// Unix time in milliseconds, from the browser clock on the client.
+pub fn now_millis() -> i64 {
+    #[cfg(feature = "ssr")]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or_default()
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        js_sys::Date::now() as i64
+    }
+}
+
 pub fn calculate_loss_and_win_rate<T: Into<f32>>(wins: T, total: T) -> (f32, f32) {
     let wins = wins.into();
     let total = total.into();
@@ -150,6 +167,65 @@ pub fn summoner_url_default(platform: &str, game_name: &str, tag_line: &str) ->
     )
 }
 
+pub fn match_url(platform: &str, riot_match_id: &str) -> String {
+    format!("/matches/{}/{}", platform, riot_match_id)
+}
+
+pub fn leaderboard_url(platform: &str) -> String {
+    format!("/leaderboards/{}", platform)
+}
+
+pub fn champion_stats_url(champion_id: u16) -> String {
+    format!("/champions/{}", champion_id)
+}
+
+pub fn champion_leaderboard_url(champion_id: u16) -> String {
+    format!("/leaderboards/champion/{}", champion_id)
+}
+
+/// `/compare` link for `summoner_identifiers`, each written as `PLATFORM:slug`.
+pub fn compare_url(summoner_identifiers: &[SummonerIdentifier]) -> String {
+    let players = summoner_identifiers
+        .iter()
+        .map(|summoner_identifier| {
+            format!(
+                "{}:{}",
+                summoner_identifier.platform_route.code(),
+                summoner_to_slug(&summoner_identifier.game_name, &summoner_identifier.tag_line)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("/compare?players={}", urlencoding::encode(&players))
+}
+
+pub fn parse_compare_players(players: &str) -> Vec<SummonerIdentifier> {
+    players
+        .split(',')
+        .filter_map(|player| {
+            let (platform, slug) = player.split_once(':')?;
+            let (game_name, tag_line) = parse_summoner_slug(slug);
+            Some(SummonerIdentifier {
+                game_name,
+                tag_line,
+                platform_route: PlatformRoute::from_code(platform)?,
+            })
+        })
+        .collect()
+}
+
+pub fn og_summoner_image_url(platform: &str, game_name: &str, tag_line: &str) -> String {
+    format!(
+        "/og/summoners/{}/{}",
+        platform,
+        summoner_to_slug(game_name, tag_line)
+    )
+}
+
+pub fn og_match_image_url(platform: &str, riot_match_id: &str) -> String {
+    format!("/og/matches/{}/{}", platform, riot_match_id)
+}
+
 pub fn summoner_not_found_url(platform: &str, game_name: &str, tag_line: &str) -> String {
     format!(
         "/?platform_route={}&game_name={}&tag_line={}",
@@ -177,25 +253,119 @@ pub fn format_float_to_2digits(value: f32) -> String {
     value.to_string()
 }
 
-#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Store)]
+/// Typed payload pushed on `/sse/match_updated/{platform}/{summoner_id}`,
+/// bitcode-encoded then base64'd into the SSE `data:` field.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub enum SseEvent {
+    /// `lol_matches.id` of matches newly ingested for this summoner.
+    NewMatches(Vec<i32>),
+    LiveGameStarted(RiotMatchId),
+    LiveGameEnded,
+    /// Same game, but its phase, start time or draft changed.
+    LiveGameUpdated(RiotMatchId),
+    ProfileChanged {
+        game_name: String,
+        tag_line: String,
+        summoner_level: u16,
+        profile_icon_id: u16,
+    },
+    /// Priority ingestion progress, `total == 0` once finished.
+    IngestionProgress { done: u32, total: u32 },
+    /// Missed events are no longer buffered: drop local state and refetch.
+    /// Followed by the current live game and ingestion progress.
+    Resync,
+}
+
+impl SseEvent {
+    pub fn to_payload(&self) -> String {
+        encode_sse_payload(self)
+    }
+
+    pub fn from_payload(payload: &str) -> Option<Self> {
+        decode_sse_payload(payload)
+    }
+}
+
+/// WebTransport session carrying `SseMultiplexEvent` frames (length-prefixed bitcode).
+pub const WEBTRANSPORT_PATH: &str = "/wt/stream";
+
+/// Payload of the multiplexed `/sse/stream` endpoint.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub enum SseMultiplexEvent {
+    /// First message of a connection: the id to pass to `update_sse_subscriptions`.
+    Connected(u64),
+    Topic { summoner_id: i32, event: SseEvent },
+}
+
+impl SseMultiplexEvent {
+    pub fn to_payload(&self) -> String {
+        encode_sse_payload(self)
+    }
+
+    pub fn from_payload(payload: &str) -> Option<Self> {
+        decode_sse_payload(payload)
+    }
+}
+
+fn encode_sse_payload<T: Encode>(value: &T) -> String {
+    use base64::Engine;
+    base64::engine::general_purpose::STANDARD_NO_PAD.encode(bitcode::encode(value))
+}
+
+fn decode_sse_payload<T: for<'a> Decode<'a>>(payload: &str) -> Option<T> {
+    use base64::Engine;
+    let bytes = base64::engine::general_purpose::STANDARD_NO_PAD
+        .decode(payload.trim())
+        .ok()?;
+    bitcode::decode(&bytes).ok()
+}
+
+#[derive(Clone, Default, Debug, PartialEq, Eq, Store)]
 pub struct SSEVersions {
     pub live_ver: Option<u64>,
+    pub live_game_id: Option<RiotMatchId>,
     pub match_ver: u64,
+    /// Ids from the last `NewMatches` event, consumed by the matches page;
+    /// empty after a `Resync`.
+    pub new_match_ids: Vec<i32>,
+    /// `(done, total)` while a priority ingestion is running for this summoner.
+    pub ingestion: Option<(u32, u32)>,
 }
 
 impl SSEVersions {
-    /// Parse "0:{live},1:{match}"
-    pub fn parse(s: &str) -> Option<Self> {
-        // très tolérant : "k:v" séparés par virgule
-        let parts = s.split(':').collect::<Vec<_>>();
-        if parts.len() != 2 {
-            return None;
+    /// Folds an incoming event into the versions the pages track.
+    pub fn apply(&mut self, event: SseEvent) {
+        match event {
+            SseEvent::NewMatches(ids) => {
+                self.match_ver = self.match_ver.wrapping_add(1);
+                self.new_match_ids = ids;
+            }
+            SseEvent::LiveGameStarted(game_id) => {
+                if self.live_game_id != Some(game_id) {
+                    self.live_ver = Some(self.live_ver.unwrap_or_default() + 1);
+                    self.live_game_id = Some(game_id);
+                }
+            }
+            SseEvent::LiveGameEnded => {
+                self.live_ver = None;
+                self.live_game_id = None;
+            }
+            SseEvent::LiveGameUpdated(game_id) => {
+                self.live_ver = Some(self.live_ver.unwrap_or_default() + 1);
+                self.live_game_id = Some(game_id);
+            }
+            SseEvent::ProfileChanged { .. } => {}
+            SseEvent::IngestionProgress { done, total } => {
+                self.ingestion = (total > 0).then_some((done, total));
+            }
+            SseEvent::Resync => {
+                // an empty `new_match_ids` tells pages to reload everything
+                *self = Self {
+                    match_ver: self.match_ver.wrapping_add(1),
+                    ..Self::default()
+                };
+            }
         }
-        let (live_ver, match_ver) = (parts.first()?, parts.get(1)?);
-        Some(Self {
-            live_ver: live_ver.parse::<u64>().ok(),
-            match_ver: match_ver.parse::<u64>().unwrap_or_default(),
-        })
     }
 }
 
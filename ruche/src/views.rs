@@ -19,7 +19,11 @@ use reactive_stores::Store as StoreObj;
 use reactive_stores_macro::Store;
 use std::fmt::{Debug, Formatter};
 
+pub mod champion_stats_page;
+pub mod compare_page;
 pub mod components;
+pub mod leaderboard_page;
+pub mod match_page;
 pub mod summoner_page;
 pub mod summoner_search_page;
 
@@ -204,12 +208,24 @@ pub struct MatchFiltersSearch {
     pub end_date: Option<String>,
 }
 
-#[derive(Debug, Encode, Decode, Default, PartialEq, Clone, Copy, Store)]
+#[derive(Debug, Encode, Decode, Default, PartialEq, Eq, Hash, Clone, Copy, Store)]
 pub struct BackEndMatchFiltersSearch {
     pub start_date: Option<CompactDate>,
     pub end_date: Option<CompactDate>,
     pub champion_id: Option<u16>,
     pub queue_id: Option<u16>,
+    /// Only `patch_from` when `patch_to` is empty, both ends included otherwise.
+    pub patch_from: Option<Patch>,
+    pub patch_to: Option<Patch>,
+    /// Advanced predicates on the summoner's own row, see `advanced_to_query`.
+    pub item_id: Option<u32>,
+    pub perk_id: Option<u16>,
+    pub summoner_spell1_id: Option<u16>,
+    pub summoner_spell2_id: Option<u16>,
+    pub teammate_champion_id: Option<u16>,
+    pub opponent_champion_id: Option<u16>,
+    /// `summoners.id` of a teammate (duo), linked from the encounters page.
+    pub teammate_summoner_id: Option<i32>,
     pub page: Option<u16>,
 }
 
@@ -224,6 +240,80 @@ impl BackEndMatchFiltersSearch {
         crate::backend::ssr::parse_date(self.end_date.map(|x| x.to_string()))
     }
 
+    /// Inclusive `(from, to)` patch range, in order.
+    pub fn patch_range(&self) -> Option<(Patch, Patch)> {
+        let patch_from = self.patch_from?;
+        let patch_to = self.patch_to.unwrap_or(patch_from);
+        Some((patch_from.min(patch_to), patch_from.max(patch_to)))
+    }
+
+    /// `patch_range` as `[major, minor]` arrays, compared with
+    /// `string_to_array(lm.version, '.')::INT[]`.
+    #[cfg(feature = "ssr")]
+    pub fn patch_range_to_db(&self) -> Option<(Vec<i32>, Vec<i32>)> {
+        self.patch_range()
+            .map(|(patch_from, patch_to)| (patch_from.to_db(), patch_to.to_db()))
+    }
+
+    /// Patch range predicate on `lm`, served by `idx_lol_matches_version_array`.
+    #[cfg(feature = "ssr")]
+    pub fn push_patch_filter(&self, query: &mut sqlx::QueryBuilder<'_, sqlx::Postgres>) {
+        if let Some((patch_from, patch_to)) = self.patch_range_to_db() {
+            query.push(" AND string_to_array(lm.version, '.')::INT[] BETWEEN ");
+            query.push_bind(patch_from);
+            query.push(" AND ");
+            query.push_bind(patch_to);
+        }
+    }
+
+    /// Compact form of the advanced predicates for the `adv` query parameter:
+    /// `.` separated tokens, `i<item>` `r<rune>` `s<spell>` (twice for both spells)
+    /// `t<teammate champion>` `o<opponent champion>` `d<teammate summoner id>`,
+    /// e.g. `i3078.r8112.s4.s14.o157`.
+    pub fn advanced_to_query(&self) -> Option<String> {
+        let tokens = [
+            self.item_id.map(|id| format!("i{}", id)),
+            self.perk_id.map(|id| format!("r{}", id)),
+            self.summoner_spell1_id.map(|id| format!("s{}", id)),
+            self.summoner_spell2_id.map(|id| format!("s{}", id)),
+            self.teammate_champion_id.map(|id| format!("t{}", id)),
+            self.opponent_champion_id.map(|id| format!("o{}", id)),
+            self.teammate_summoner_id.map(|id| format!("d{}", id)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+        (!tokens.is_empty()).then(|| tokens.join("."))
+    }
+
+    /// Reverse of `advanced_to_query`, unknown or malformed tokens are ignored.
+    pub fn with_advanced_query(mut self, query: &str) -> Self {
+        for token in query.split('.') {
+            let Some((key, id)) = token.split_at_checked(1) else {
+                continue;
+            };
+            match key {
+                "i" => self.item_id = id.parse().ok().or(self.item_id),
+                "r" => self.perk_id = id.parse().ok().or(self.perk_id),
+                "s" if self.summoner_spell1_id.is_none() => {
+                    self.summoner_spell1_id = id.parse().ok()
+                }
+                "s" => self.summoner_spell2_id = id.parse().ok().or(self.summoner_spell2_id),
+                "t" => {
+                    self.teammate_champion_id = id.parse().ok().or(self.teammate_champion_id)
+                }
+                "o" => {
+                    self.opponent_champion_id = id.parse().ok().or(self.opponent_champion_id)
+                }
+                "d" => {
+                    self.teammate_summoner_id = id.parse().ok().or(self.teammate_summoner_id)
+                }
+                _ => {}
+            }
+        }
+        self
+    }
+
     pub fn from_options(
         queue_id: Option<String>,
         champion_id: Option<String>,
@@ -237,6 +327,7 @@ impl BackEndMatchFiltersSearch {
             start_date: parse_date(start_date),
             end_date: parse_date(end_date),
             page,
+            ..Default::default()
         })
     }
 }
@@ -255,7 +346,7 @@ pub fn parse_date(date: Option<String>) -> Option<CompactDate> {
     })
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Encode, Decode)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Encode, Decode)]
 pub struct CompactDate(u16);
 
 impl CompactDate {
@@ -275,6 +366,35 @@ impl CompactDate {
     }
 }
 
+/// `major.minor` game version, as stored in `lol_matches.version`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Encode, Decode)]
+pub struct Patch {
+    pub major: u8,
+    pub minor: u8,
+}
+
+impl Patch {
+    /// Accepts `14.20` as well as full versions like `14.20.628.3153`.
+    pub fn parse(version: &str) -> Option<Self> {
+        let mut split = version.split('.');
+        Some(Patch {
+            major: split.next()?.parse().ok()?,
+            minor: split.next()?.parse().ok()?,
+        })
+    }
+
+    #[cfg(feature = "ssr")]
+    pub fn to_db(self) -> Vec<i32> {
+        vec![self.major as i32, self.minor as i32]
+    }
+}
+
+impl std::fmt::Display for Patch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
 impl std::fmt::Display for CompactDate {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let y = ((self.0 >> 9) & 0x7F) + 2000;
@@ -283,3 +403,60 @@ impl std::fmt::Display for CompactDate {
         write!(f, "{:04}-{:02}-{:02}", y, m, d)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::BackEndMatchFiltersSearch;
+
+    fn parse(query: &str) -> BackEndMatchFiltersSearch {
+        BackEndMatchFiltersSearch::default().with_advanced_query(query)
+    }
+
+    #[test]
+    fn advanced_query_round_trip() {
+        let filters = BackEndMatchFiltersSearch {
+            item_id: Some(3078),
+            perk_id: Some(8112),
+            summoner_spell1_id: Some(4),
+            summoner_spell2_id: Some(14),
+            teammate_champion_id: Some(412),
+            opponent_champion_id: Some(157),
+            teammate_summoner_id: Some(42),
+            ..Default::default()
+        };
+        let query = filters.advanced_to_query().unwrap();
+        assert_eq!(query, "i3078.r8112.s4.s14.t412.o157.d42");
+        assert_eq!(parse(&query), filters);
+        assert_eq!(BackEndMatchFiltersSearch::default().advanced_to_query(), None);
+    }
+
+    #[test]
+    fn advanced_query_spell_order() {
+        let filters = parse("s14.s4");
+        assert_eq!(filters.summoner_spell1_id, Some(14));
+        assert_eq!(filters.summoner_spell2_id, Some(4));
+        // a lone second spell comes back as the first one, same predicate
+        let filters = BackEndMatchFiltersSearch {
+            summoner_spell2_id: Some(4),
+            ..Default::default()
+        };
+        let parsed = parse(&filters.advanced_to_query().unwrap());
+        assert_eq!(parsed.summoner_spell1_id, Some(4));
+        assert_eq!(parsed.summoner_spell2_id, None);
+    }
+
+    #[test]
+    fn advanced_query_ignores_malformed_tokens() {
+        let filters = parse("..ix.r.s4.sfoo.s14.z9.é1.o-3.t99999999.d7");
+        assert_eq!(filters.item_id, None);
+        assert_eq!(filters.perk_id, None);
+        assert_eq!(filters.summoner_spell1_id, Some(4));
+        assert_eq!(filters.summoner_spell2_id, Some(14));
+        assert_eq!(filters.opponent_champion_id, None);
+        assert_eq!(filters.teammate_champion_id, None);
+        assert_eq!(filters.teammate_summoner_id, Some(7));
+        // a valid token is not undone by a later malformed one
+        assert_eq!(parse("i3078.ibad").item_id, Some(3078));
+        assert_eq!(parse(""), BackEndMatchFiltersSearch::default());
+    }
+}
@@ -0,0 +1,565 @@
+use crate::app::{ChampionStatsRouteParams, MetaStore, MetaStoreStoreFields};
+use crate::backend::server_fns::get_patch_champion::get_patch_champion;
+use crate::backend::server_fns::get_patch_champions::get_patch_champions;
+use crate::utils::{
+    calculate_and_format_kda, calculate_loss_and_win_rate, champion_leaderboard_url,
+    champion_stats_url, format_float_to_2digits, format_with_spaces,
+};
+use crate::views::{ImgChampion, ImgItem, ImgPerk, ImgSummonerSpell};
+use bitcode::{Decode, Encode};
+use common::consts::champion::Champion;
+use common::consts::item::Item;
+use common::consts::perk::Perk;
+use common::consts::queue::Queue;
+use common::consts::summoner_spell::SummonerSpell;
+use leptos::either::{Either, EitherOf3};
+use leptos::prelude::codee::binary::BitcodeCodec;
+use leptos::prelude::*;
+use leptos::{component, view, IntoView};
+use leptos_router::components::A;
+use leptos_router::hooks::use_params;
+use leptos_router::{lazy_route, LazyRoute};
+
+/// Queues aggregated by `UpdateChampionStatsTask`, solo/duo first.
+pub const CHAMPION_STATS_QUEUES: [Queue; 5] = [
+    Queue::SummonersRift5v5RankedSolo,
+    Queue::SummonersRift5v5RankedFlex,
+    Queue::SummonersRift5v5DraftPick,
+    Queue::SummonersRiftNormalQuickplay,
+    Queue::HowlingAbyss5v5Aram,
+];
+/// Latest patches rebuilt on every run.
+pub const CHAMPION_STATS_PATCHES: i64 = 3;
+/// Builds kept per champion, queue and kind.
+pub const CHAMPION_BUILDS_PER_KIND: i64 = 5;
+
+pub struct ChampionsStatsRoute {
+    patch_champions_resource: Resource<Result<PatchChampions, ServerFnError>, BitcodeCodec>,
+    version: RwSignal<Option<String>>,
+    queue_id: RwSignal<u16>,
+}
+
+#[lazy_route]
+impl LazyRoute for ChampionsStatsRoute {
+    fn data() -> Self {
+        let version = RwSignal::new(None::<String>);
+        let queue_id = RwSignal::new(Queue::SummonersRift5v5RankedSolo.id());
+        let patch_champions_resource = Resource::new_bitcode(
+            move || (version.get(), queue_id.get()),
+            |(version, queue_id)| async move { get_patch_champions(version, queue_id).await },
+        );
+        Self {
+            patch_champions_resource,
+            version,
+            queue_id,
+        }
+    }
+
+    fn view(this: Self) -> AnyView {
+        let ChampionsStatsRoute {
+            patch_champions_resource,
+            version,
+            queue_id,
+        } = this;
+
+        let meta_store = expect_context::<reactive_stores::Store<MetaStore>>();
+        batch(|| {
+            meta_store
+                .title()
+                .set("League of Legends Champion Stats by Patch | Ruche".to_string());
+            meta_store.description().set(
+                "Win rate, pick rate and ban rate of every League of Legends champion per patch and queue, from the matches stored on Ruche."
+                    .to_string(),
+            );
+            meta_store.url().set("/champions".to_string());
+            meta_store
+                .image()
+                .set("https://ruche.lol/assets/logo.avif".to_string());
+        });
+
+        view! {
+            <div class="my-0 mx-auto max-w-5xl text-center">
+                <A href="/" attr:class="p-6 text-4xl my-4">
+                    "Welcome to Ruche"
+                </A>
+                <Transition fallback=move || {
+                    view! { <div class="text-center">Loading Champions</div> }
+                }>
+                    {move || Suspend::new(async move {
+                        match patch_champions_resource.await {
+                            Ok(patch_champions) => {
+                                if patch_champions.champions.is_empty() {
+                                    Ok(
+                                        Either::Left(
+                                            view! {
+                                                <PatchQueueSelect
+                                                    patches=patch_champions.patches
+                                                    current_version=patch_champions.version
+                                                    version
+                                                    queue_id
+                                                />
+                                                <div class="text-center">No Champion Stats Yet</div>
+                                            },
+                                        ),
+                                    )
+                                } else {
+                                    Ok(
+                                        Either::Right(
+                                            view! {
+                                                <PatchQueueSelect
+                                                    patches=patch_champions.patches
+                                                    current_version=patch_champions.version
+                                                    version
+                                                    queue_id
+                                                />
+                                                <div class="flex my-card w-fit mx-auto">
+                                                    <table class="text-gray-200 space-y-2">
+                                                        <thead>
+                                                            <tr>
+                                                                <th class="text-left px-2">Champion</th>
+                                                                <th class="px-2">Games</th>
+                                                                <th class="px-2">Win rate</th>
+                                                                <th class="px-2">Pick rate</th>
+                                                                <th class="px-2">Ban rate</th>
+                                                                <th class="px-2">KDA</th>
+                                                            </tr>
+                                                        </thead>
+                                                        <tbody>
+                                                            {patch_champions
+                                                                .champions
+                                                                .into_iter()
+                                                                .filter(|stats| stats.total_matches > 0)
+                                                                .map(|stats| {
+                                                                    let champion = Champion::try_from(stats.champion_id)
+                                                                        .unwrap_or_default();
+                                                                    let (_, winrate) = calculate_loss_and_win_rate(
+                                                                        stats.total_wins as f32,
+                                                                        stats.total_matches as f32,
+                                                                    );
+                                                                    view! {
+                                                                        <tr>
+                                                                            <td class="text-left px-2">
+                                                                                <A
+                                                                                    href=champion_stats_url(stats.champion_id)
+                                                                                    attr:class="flex items-center text-blue-300 hover:underline"
+                                                                                >
+                                                                                    <ImgChampion
+                                                                                        champion
+                                                                                        parent_class="my-1 w-8 h-8 sprite-wrapper".to_string()
+                                                                                        class="rounded-full self-scale-66 sprite-inner".to_string()
+                                                                                    />
+                                                                                    <span class="ml-2">{champion.label()}</span>
+                                                                                </A>
+                                                                            </td>
+                                                                            <td class="px-2">{format_with_spaces(stats.total_matches)}</td>
+                                                                            <td class="px-2">{format_float_to_2digits(winrate)}%</td>
+                                                                            <td class="px-2">{format_float_to_2digits(stats.pick_rate)}%</td>
+                                                                            <td class="px-2">{format_float_to_2digits(stats.ban_rate)}%</td>
+                                                                            <td class="px-2">
+                                                                                {calculate_and_format_kda(
+                                                                                    stats.avg_kills,
+                                                                                    stats.avg_deaths,
+                                                                                    stats.avg_assists,
+                                                                                )}:1
+                                                                            </td>
+                                                                        </tr>
+                                                                    }
+                                                                })
+                                                                .collect::<Vec<_>>()}
+                                                        </tbody>
+                                                    </table>
+                                                </div>
+                                            },
+                                        ),
+                                    )
+                                }
+                            }
+                            Err(e) => Err(e),
+                        }
+                    })}
+                </Transition>
+            </div>
+        }
+        .into_any()
+    }
+}
+
+pub struct ChampionStatsRoute {
+    patch_champion_resource: Resource<Result<PatchChampion, ServerFnError>, BitcodeCodec>,
+    champion_memo: Memo<Champion>,
+    version: RwSignal<Option<String>>,
+    queue_id: RwSignal<u16>,
+}
+
+#[lazy_route]
+impl LazyRoute for ChampionStatsRoute {
+    fn data() -> Self {
+        let route_params = use_params::<ChampionStatsRouteParams>();
+        let champion_memo = Memo::new(move |_| {
+            route_params
+                .read()
+                .as_ref()
+                .ok()
+                .and_then(|params| params.champion_id)
+                .and_then(|champion_id| Champion::try_from(champion_id).ok())
+                .unwrap_or_default()
+        });
+        let version = RwSignal::new(None::<String>);
+        let queue_id = RwSignal::new(Queue::SummonersRift5v5RankedSolo.id());
+        let patch_champion_resource = Resource::new_bitcode(
+            move || (champion_memo.get(), version.get(), queue_id.get()),
+            |(champion, version, queue_id)| async move {
+                get_patch_champion(champion.into(), version, queue_id).await
+            },
+        );
+        Self {
+            patch_champion_resource,
+            champion_memo,
+            version,
+            queue_id,
+        }
+    }
+
+    fn view(this: Self) -> AnyView {
+        let ChampionStatsRoute {
+            patch_champion_resource,
+            champion_memo,
+            version,
+            queue_id,
+        } = this;
+
+        let meta_store = expect_context::<reactive_stores::Store<MetaStore>>();
+        batch(|| {
+            let champion = champion_memo.get_untracked();
+            meta_store.title().set(format!(
+                "{} Build, Runes & Win Rate | Ruche",
+                champion.label()
+            ));
+            meta_store.description().set(format!(
+                "{} win rate, pick rate and ban rate per patch, with the most played items, rune pages and summoner spells.",
+                champion.label()
+            ));
+            meta_store.url().set(champion_stats_url(champion.into()));
+            meta_store
+                .image()
+                .set("https://ruche.lol/assets/logo.avif".to_string());
+        });
+
+        view! {
+            <div class="my-0 mx-auto max-w-5xl text-center">
+                <A href="/" attr:class="p-6 text-4xl my-4">
+                    "Welcome to Ruche"
+                </A>
+                <div class="my-card flex space-x-2 my-2 items-center justify-center">
+                    {move || {
+                        let champion = champion_memo.get();
+                        view! {
+                            <ImgChampion
+                                champion
+                                parent_class="w-8 h-8 sprite-wrapper".to_string()
+                                class="rounded-full self-scale-66 sprite-inner".to_string()
+                            />
+                            <span class="text-xl">{champion.label()}</span>
+                            <A href="/champions" attr:class="my-button">
+                                All champions
+                            </A>
+                            <A href=champion_leaderboard_url(champion.into()) attr:class="my-button">
+                                Best players
+                            </A>
+                        }
+                    }}
+                </div>
+                <Transition fallback=move || {
+                    view! { <div class="text-center">Loading Champion</div> }
+                }>
+                    {move || Suspend::new(async move {
+                        match patch_champion_resource.await {
+                            Ok(patch_champion) => {
+                                let select = view! {
+                                    <PatchQueueSelect
+                                        patches=patch_champion.patches
+                                        current_version=patch_champion.version
+                                        version
+                                        queue_id
+                                    />
+                                };
+                                match patch_champion.stats {
+                                    Some(stats) => {
+                                        Ok(
+                                            Either::Left(
+                                                view! {
+                                                    {select}
+                                                    <ChampionPatchStatsCard stats />
+                                                    <div class="flex flex-wrap gap-2 justify-center">
+                                                        {ChampionBuildKind::ALL
+                                                            .into_iter()
+                                                            .map(|kind| {
+                                                                let builds = patch_champion
+                                                                    .builds
+                                                                    .iter()
+                                                                    .filter(|build| build.kind == kind)
+                                                                    .cloned()
+                                                                    .collect::<Vec<_>>();
+                                                                view! { <ChampionBuildList kind builds /> }
+                                                            })
+                                                            .collect::<Vec<_>>()}
+                                                    </div>
+                                                },
+                                            ),
+                                        )
+                                    }
+                                    None => {
+                                        Ok(
+                                            Either::Right(
+                                                view! {
+                                                    {select}
+                                                    <div class="text-center">
+                                                        No stored games on this patch and queue
+                                                    </div>
+                                                },
+                                            ),
+                                        )
+                                    }
+                                }
+                            }
+                            Err(e) => Err(e),
+                        }
+                    })}
+                </Transition>
+            </div>
+        }
+        .into_any()
+    }
+}
+
+/// `version` stays `None` until a patch is picked, the server then answers with the latest.
+#[component]
+pub fn PatchQueueSelect(
+    patches: Vec<String>,
+    current_version: String,
+    version: RwSignal<Option<String>>,
+    queue_id: RwSignal<u16>,
+) -> impl IntoView {
+    let to_opt_string = |v: String| if v.is_empty() { None } else { Some(v) };
+    view! {
+        <div class="my-card flex space-x-2 my-2 items-center justify-center">
+            <label for="patch">Patch</label>
+            <select
+                class="my-select"
+                id="patch"
+                prop:value=current_version
+                on:change=move |e| version.set(to_opt_string(event_target_value(&e)))
+            >
+                {patches
+                    .into_iter()
+                    .map(|patch| view! { <option value=patch.clone()>{patch.clone()}</option> })
+                    .collect::<Vec<_>>()}
+            </select>
+            <label for="queue">Queue</label>
+            <select
+                class="my-select"
+                id="queue"
+                prop:value=move || queue_id.get()
+                on:change=move |e| queue_id.set(event_target_value(&e).parse().unwrap_or_default())
+            >
+                {CHAMPION_STATS_QUEUES
+                    .iter()
+                    .map(|queue| view! { <option value=queue.id()>{queue.label()}</option> })
+                    .collect::<Vec<_>>()}
+            </select>
+        </div>
+    }
+}
+
+#[component]
+pub fn ChampionPatchStatsCard(stats: ChampionPatchStats) -> impl IntoView {
+    let (losses, winrate) =
+        calculate_loss_and_win_rate(stats.total_wins as f32, stats.total_matches as f32);
+    view! {
+        <div class="my-card flex space-x-6 my-2 justify-center">
+            <div class="flex flex-col">
+                <span class="text-gray-400">Win rate</span>
+                <span class="text-xl">{format_float_to_2digits(winrate)}%</span>
+                <span class="text-xs">{stats.total_wins}W {losses as u32}L</span>
+            </div>
+            <div class="flex flex-col">
+                <span class="text-gray-400">Pick rate</span>
+                <span class="text-xl">{format_float_to_2digits(stats.pick_rate)}%</span>
+                <span class="text-xs">{format_with_spaces(stats.total_matches)} games</span>
+            </div>
+            <div class="flex flex-col">
+                <span class="text-gray-400">Ban rate</span>
+                <span class="text-xl">{format_float_to_2digits(stats.ban_rate)}%</span>
+            </div>
+            <div class="flex flex-col">
+                <span class="text-gray-400">KDA</span>
+                <span class="text-xl">
+                    {calculate_and_format_kda(stats.avg_kills, stats.avg_deaths, stats.avg_assists)}:1
+                </span>
+                <span class="text-xs">
+                    {format!(
+                        "{}/{}/{}",
+                        format_float_to_2digits(stats.avg_kills),
+                        format_float_to_2digits(stats.avg_deaths),
+                        format_float_to_2digits(stats.avg_assists),
+                    )}
+                </span>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn ChampionBuildList(kind: ChampionBuildKind, builds: Vec<ChampionBuild>) -> impl IntoView {
+    view! {
+        <div class="my-card text-left">
+            <div class="text-gray-400 mb-1">{kind.label()}</div>
+            {if builds.is_empty() {
+                Either::Left(view! { <div class="text-sm">Not enough games</div> })
+            } else {
+                Either::Right(
+                    builds
+                        .into_iter()
+                        .map(|build| {
+                            let (_, winrate) = calculate_loss_and_win_rate(
+                                build.total_wins as f32,
+                                build.total_matches as f32,
+                            );
+                            view! {
+                                <div class="flex items-center space-x-2 my-1">
+                                    <div class="flex gap-0.5">
+                                        <ChampionBuildIcons kind build=build.build />
+                                    </div>
+                                    <span class="text-xs">
+                                        {format!(
+                                            "{} games {}%",
+                                            build.total_matches,
+                                            format_float_to_2digits(winrate),
+                                        )}
+                                    </span>
+                                </div>
+                            }
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            }}
+        </div>
+    }
+}
+
+#[component]
+pub fn ChampionBuildIcons(kind: ChampionBuildKind, build: Vec<u32>) -> impl IntoView {
+    match kind {
+        ChampionBuildKind::CoreItems | ChampionBuildKind::FinalItems => EitherOf3::A(
+            build
+                .into_iter()
+                .map(|item_id| {
+                    let item = Item::try_from(item_id).unwrap_or_default();
+                    view! { <ImgItem item class="rounded".to_string() /> }
+                })
+                .collect::<Vec<_>>(),
+        ),
+        ChampionBuildKind::Runes => EitherOf3::B(
+            build
+                .into_iter()
+                .map(|perk_id| {
+                    let perk = Perk::try_from(perk_id as u16).unwrap_or_default();
+                    view! {
+                        <ImgPerk
+                            perk
+                            class="self-scale-78 sprite-inner rounded-full".to_string()
+                            parent_class="w-[22px] h-[22px] sprite-wrapper".to_string()
+                        />
+                    }
+                })
+                .collect::<Vec<_>>(),
+        ),
+        ChampionBuildKind::SummonerSpells => EitherOf3::C(
+            build
+                .into_iter()
+                .map(|spell_id| {
+                    let summoner_spell =
+                        SummonerSpell::try_from(spell_id as u16).unwrap_or_default();
+                    view! { <ImgSummonerSpell summoner_spell class="rounded".to_string() /> }
+                })
+                .collect::<Vec<_>>(),
+        ),
+    }
+}
+
+#[derive(Clone, Default, Encode, Decode)]
+pub struct PatchChampions {
+    /// Aggregated patches, newest first.
+    pub patches: Vec<String>,
+    pub version: String,
+    pub champions: Vec<ChampionPatchStats>,
+}
+
+#[derive(Clone, Default, Encode, Decode)]
+pub struct PatchChampion {
+    pub patches: Vec<String>,
+    pub version: String,
+    /// `None` when the champion was neither picked nor banned on this patch and queue.
+    pub stats: Option<ChampionPatchStats>,
+    pub builds: Vec<ChampionBuild>,
+}
+
+#[derive(Clone, Encode, Decode)]
+pub struct ChampionPatchStats {
+    pub champion_id: u16,
+    pub total_matches: u32,
+    pub total_wins: u32,
+    /// Percent of the stored games of the patch and queue.
+    pub pick_rate: f32,
+    /// Percent of the stored games with bans, older games were ingested without them.
+    pub ban_rate: f32,
+    pub avg_kills: f32,
+    pub avg_deaths: f32,
+    pub avg_assists: f32,
+}
+
+#[derive(Clone, Encode, Decode)]
+pub struct ChampionBuild {
+    pub kind: ChampionBuildKind,
+    /// Item, perk or summoner spell ids depending on `kind`.
+    pub build: Vec<u32>,
+    pub total_matches: u32,
+    pub total_wins: u32,
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Encode, Decode)]
+pub enum ChampionBuildKind {
+    /// First three finished items, in purchase order (games with a stored timeline).
+    CoreItems = 0,
+    FinalItems = 1,
+    /// Primary style, keystone and 3 runes, secondary style and 2 runes, then 3 shards.
+    Runes = 2,
+    SummonerSpells = 3,
+}
+
+impl ChampionBuildKind {
+    pub const ALL: [ChampionBuildKind; 4] = [
+        ChampionBuildKind::CoreItems,
+        ChampionBuildKind::FinalItems,
+        ChampionBuildKind::Runes,
+        ChampionBuildKind::SummonerSpells,
+    ];
+
+    pub fn id(self) -> u8 {
+        self as u8
+    }
+
+    pub fn from_id(id: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.id() == id)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ChampionBuildKind::CoreItems => "Core items",
+            ChampionBuildKind::FinalItems => "Final items",
+            ChampionBuildKind::Runes => "Runes",
+            ChampionBuildKind::SummonerSpells => "Summoner spells",
+        }
+    }
+}
@@ -0,0 +1,486 @@
+use crate::app::{CompareQuery, MetaStore, MetaStoreStoreFields, SummonerIdentifier, SITE_URL};
+use crate::backend::server_fns::get_compare::get_compare;
+use crate::utils::{
+    calculate_and_format_kda, calculate_loss_and_win_rate, compare_url, format_float_to_2digits,
+    format_with_spaces, parse_compare_players, summoner_not_found_url, SSEVersions,
+    SSEVersionsStoreFields,
+};
+use crate::views::components::match_filters::MatchFilters;
+use crate::views::components::summoner_search_page::SummonerSearch;
+use crate::views::summoner_page::summoner_champions_page::ChampionStats;
+use crate::views::summoner_page::{Summoner, SummonerInfo};
+use crate::views::{BackEndMatchFiltersSearch, ImgChampion};
+use bitcode::{Decode, Encode};
+use common::consts::champion::Champion;
+use common::consts::platform_route::{PlatformRoute, PLATFORM_ROUTE_OPTIONS};
+use common::consts::position::Position;
+use itertools::Itertools;
+use leptos::either::Either;
+use leptos::ev::SubmitEvent;
+use leptos::html::{Input, Select};
+use leptos::prelude::codee::binary::BitcodeCodec;
+use leptos::prelude::*;
+use leptos::{component, view, IntoView};
+use leptos_router::components::A;
+use leptos_router::hooks::{use_navigate, use_query};
+use leptos_router::{lazy_route, LazyRoute};
+use reactive_stores::Store;
+
+pub const MAX_COMPARED_SUMMONERS: usize = 5;
+pub const TOP_COMPARED_CHAMPIONS: usize = 5;
+
+pub struct CompareRoute {
+    compare_resource: Resource<Result<CompareResult, ServerFnError>, BitcodeCodec>,
+    players_memo: Memo<Vec<SummonerIdentifier>>,
+    filters: Store<BackEndMatchFiltersSearch>,
+}
+
+#[lazy_route]
+impl LazyRoute for CompareRoute {
+    fn data() -> Self {
+        let compare_query = use_query::<CompareQuery>();
+        let players_memo = Memo::new(move |_| {
+            compare_query
+                .read()
+                .as_ref()
+                .ok()
+                .and_then(|q| q.players.as_deref().map(parse_compare_players))
+                .unwrap_or_default()
+        });
+        let sse_version = expect_context::<Store<SSEVersions>>();
+        let filters = Store::new(BackEndMatchFiltersSearch::default());
+
+        let compare_resource = Resource::new_bitcode(
+            move || {
+                (
+                    sse_version.match_ver().get(),
+                    players_memo.get(),
+                    filters.get(),
+                )
+            },
+            |(_, players, filters)| async move {
+                if players.is_empty() {
+                    Ok(CompareResult::default())
+                } else {
+                    get_compare(players, Some(filters)).await
+                }
+            },
+        );
+        Self {
+            compare_resource,
+            players_memo,
+            filters,
+        }
+    }
+
+    fn view(this: Self) -> AnyView {
+        let CompareRoute {
+            compare_resource,
+            players_memo,
+            filters,
+        } = this;
+        provide_context(filters);
+
+        let meta_store = expect_context::<reactive_stores::Store<MetaStore>>();
+        let set_meta = move |players: &[SummonerIdentifier]| {
+            let names = players
+                .iter()
+                .map(|player| format!("{}#{}", player.game_name, player.tag_line))
+                .join(" vs ");
+            batch(|| {
+                if names.is_empty() {
+                    meta_store
+                        .title()
+                        .set("Compare Summoners | Ruche".to_string());
+                } else {
+                    meta_store
+                        .title()
+                        .set(format!("{} — Compare | Ruche", names));
+                }
+                meta_store.description().set(
+                    "Compare League of Legends summoners side by side: win rate, K/D/A, champion pools, roles and recent form, filtered by queue and date."
+                        .to_string(),
+                );
+                meta_store.url().set(compare_url(players));
+                meta_store
+                    .image()
+                    .set(format!("{}/assets/logo.avif", SITE_URL));
+            });
+        };
+        // effects do not run during SSR, the head needs the first call
+        set_meta(&players_memo.read_untracked());
+        // adding or removing a player only changes the query
+        Effect::new(move |_| set_meta(&players_memo.read()));
+
+        view! {
+            <div class="my-0 mx-auto max-w-5xl text-center">
+                <A href="/" attr:class="p-6 text-4xl my-4">
+                    "Welcome to Ruche"
+                </A>
+                <SummonerSearch is_summoner_page=false />
+                <CompareAddSummoner players_memo />
+                <MatchFilters />
+                <Transition fallback=move || {
+                    view! { <div class="text-center">Loading Comparison</div> }
+                }>
+                    {move || Suspend::new(async move {
+                        match compare_resource.await {
+                            Ok(compare_result) => {
+                                if compare_result.summoners.is_empty()
+                                    && compare_result.not_found.is_empty()
+                                {
+                                    Ok(
+                                        Either::Left(
+                                            view! {
+                                                <div class="text-center">
+                                                    Add summoners to compare them side by side
+                                                </div>
+                                            },
+                                        ),
+                                    )
+                                } else {
+                                    let players = players_memo.get_untracked();
+                                    Ok(
+                                        Either::Right(
+                                            view! {
+                                                <CompareNotFound
+                                                    not_found=compare_result.not_found
+                                                    players=players.clone()
+                                                />
+                                                <div class="flex gap-2 my-2 items-start">
+                                                    {compare_result
+                                                        .summoners
+                                                        .into_iter()
+                                                        .map(|compare_summoner| {
+                                                            let remove_url = compare_url(
+                                                                &without_player(
+                                                                    &players,
+                                                                    &compare_summoner.summoner,
+                                                                ),
+                                                            );
+                                                            view! {
+                                                                <CompareSummonerColumn
+                                                                    compare_summoner
+                                                                    remove_url
+                                                                />
+                                                            }
+                                                        })
+                                                        .collect::<Vec<_>>()}
+                                                </div>
+                                            },
+                                        ),
+                                    )
+                                }
+                            }
+                            Err(e) => Err(e),
+                        }
+                    })}
+                </Transition>
+            </div>
+        }
+        .into_any()
+    }
+}
+
+fn without_player(players: &[SummonerIdentifier], summoner: &Summoner) -> Vec<SummonerIdentifier> {
+    players
+        .iter()
+        .filter(|player| {
+            !(player.platform_route == summoner.platform
+                && player.game_name.eq_ignore_ascii_case(&summoner.game_name)
+                && player.tag_line.eq_ignore_ascii_case(&summoner.tag_line))
+        })
+        .cloned()
+        .collect()
+}
+
+#[component]
+pub fn CompareAddSummoner(players_memo: Memo<Vec<SummonerIdentifier>>) -> impl IntoView {
+    let navigate = use_navigate();
+    let game_name_node = NodeRef::<Input>::new();
+    let tag_line_node = NodeRef::<Input>::new();
+    let platform_route_node = NodeRef::<Select>::new();
+    let is_full = move || players_memo.read().len() >= MAX_COMPARED_SUMMONERS;
+
+    let on_submit = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        let game_name = game_name_node.get().expect("game_name not valid").value();
+        let tag_line = tag_line_node.get().expect("tag_line not valid").value();
+        if game_name.is_empty() || is_full() {
+            return;
+        }
+        let mut players = players_memo.get_untracked();
+        players.push(SummonerIdentifier {
+            game_name,
+            tag_line,
+            platform_route: PlatformRoute::try_from(
+                platform_route_node
+                    .get()
+                    .expect("platform_route not valid")
+                    .value()
+                    .as_str(),
+            )
+            .unwrap_or_default(),
+        });
+        navigate(&compare_url(&players), Default::default());
+    };
+
+    view! {
+        <div class="w-full flex my-2 justify-center">
+            <form on:submit=on_submit class="flex space-x-2 items-center justify-center w-[768px]">
+                <span>Compare with</span>
+                <input
+                    class="my-input"
+                    type="text"
+                    node_ref=game_name_node
+                    placeholder="Game Name"
+                    name="compare_game_name"
+                />
+                <input
+                    class="my-input"
+                    type="text"
+                    node_ref=tag_line_node
+                    placeholder="Tag Line"
+                    name="compare_tag_line"
+                />
+                <select
+                    class="my-select"
+                    aria-label="Platform Route"
+                    node_ref=platform_route_node
+                    name="compare_platform_route"
+                >
+                    {PLATFORM_ROUTE_OPTIONS
+                        .iter()
+                        .map(|pt| view! { <option value=pt.code()>{pt.code()}</option> })
+                        .collect::<Vec<_>>()}
+                </select>
+                <button class="my-button" type="submit" disabled=is_full>
+                    Add
+                </button>
+            </form>
+        </div>
+    }
+}
+
+#[component]
+pub fn CompareNotFound(
+    not_found: Vec<SummonerIdentifier>,
+    players: Vec<SummonerIdentifier>,
+) -> impl IntoView {
+    not_found
+        .into_iter()
+        .map(|summoner_identifier| {
+            let remaining = players
+                .iter()
+                .filter(|player| **player != summoner_identifier)
+                .cloned()
+                .collect::<Vec<_>>();
+            view! {
+                <div class="my-card my-2 flex justify-between">
+                    <span>
+                        {format!(
+                            "{}#{} ({}) has no stored games yet.",
+                            summoner_identifier.game_name,
+                            summoner_identifier.tag_line,
+                            summoner_identifier.platform_route.code(),
+                        )}
+                    </span>
+                    <span class="flex space-x-2">
+                        <A href=summoner_not_found_url(
+                            summoner_identifier.platform_route.code(),
+                            &summoner_identifier.game_name,
+                            &summoner_identifier.tag_line,
+                        )>Search</A>
+                        <A href=compare_url(&remaining)>Remove</A>
+                    </span>
+                </div>
+            }
+        })
+        .collect::<Vec<_>>()
+}
+
+#[component]
+pub fn CompareSummonerColumn(
+    compare_summoner: CompareSummoner,
+    remove_url: String,
+) -> impl IntoView {
+    let CompareSummoner {
+        summoner,
+        stats,
+        roles,
+        recent_form,
+        champions,
+    } = compare_summoner;
+    let (summoner_level, _) = signal(summoner.summoner_level);
+    let (profile_icon_id, _) = signal(summoner.profile_icon_id);
+    let (losses, winrate) = calculate_loss_and_win_rate(stats.total_wins, stats.total_matches);
+    let total_role_matches = roles
+        .iter()
+        .map(|role| role.total_matches as u32)
+        .sum::<u32>();
+
+    view! {
+        <div class="flex flex-col flex-1 min-w-0 my-card text-left text-sm space-y-2">
+            <div class="flex justify-between items-start">
+                <SummonerInfo
+                    game_name=summoner.game_name
+                    tag_line=summoner.tag_line
+                    platform=summoner.platform
+                    pro_slug=summoner.pro_slug
+                    level_signal=summoner_level
+                    profile_icon_signal=profile_icon_id
+                />
+                <A href=remove_url attr:class="text-gray-400" attr:aria-label="Remove">
+                    "✕"
+                </A>
+            </div>
+
+            <div>
+                <div>
+                    {stats.total_wins}W {losses as u16}L {stats.total_matches}G
+                    {format_float_to_2digits(winrate.round())}%
+                </div>
+                <div>
+                    {format!(
+                        "{}/{}/{}",
+                        format_float_to_2digits(stats.avg_kills),
+                        format_float_to_2digits(stats.avg_deaths),
+                        format_float_to_2digits(stats.avg_assists),
+                    )}
+                </div>
+                <div>
+                    {calculate_and_format_kda(stats.avg_kills, stats.avg_deaths, stats.avg_assists)}
+                    :1 P/kill {stats.avg_kill_participation}%
+                </div>
+                <div>{format_float_to_2digits(stats.avg_cs_per_minute)} CS/min</div>
+                <div>
+                    {format_with_spaces(stats.avg_damage_dealt_to_champions)} dmg to champions
+                </div>
+            </div>
+
+            <div class="flex flex-col">
+                <span class="text-gray-400">Recent form</span>
+                <div class="flex gap-px">
+                    {recent_form
+                        .iter()
+                        .map(|won| {
+                            view! {
+                                <span
+                                    class="w-3 h-3 rounded-sm"
+                                    class=("bg-blue-500", *won)
+                                    class=("bg-red-500", !*won)
+                                ></span>
+                            }
+                        })
+                        .collect::<Vec<_>>()}
+                </div>
+            </div>
+
+            <div class="flex flex-col">
+                <span class="text-gray-400">Roles</span>
+                {roles
+                    .into_iter()
+                    .map(|role| {
+                        let share = if total_role_matches > 0 {
+                            role.total_matches as u32 * 100 / total_role_matches
+                        } else {
+                            0
+                        };
+                        let (_, role_winrate) = calculate_loss_and_win_rate(
+                            role.total_wins,
+                            role.total_matches,
+                        );
+                        view! {
+                            <div class="flex items-center space-x-1">
+                                <span class="w-14">{role.position.label()}</span>
+                                <div class="flex-1 h-2 bg-gray-700 rounded">
+                                    <div
+                                        class="h-2 bg-blue-500 rounded"
+                                        style=format!("width: {}%", share)
+                                    ></div>
+                                </div>
+                                <span class="w-20 text-right">
+                                    {role.total_matches}G {format_float_to_2digits(role_winrate.round())}%
+                                </span>
+                            </div>
+                        }
+                    })
+                    .collect::<Vec<_>>()}
+            </div>
+
+            <div class="flex flex-col">
+                <span class="text-gray-400">Champions</span>
+                {champions
+                    .into_iter()
+                    .map(|champion_stats| {
+                        let champion = Champion::try_from(champion_stats.champion_id)
+                            .unwrap_or_default();
+                        let (_, champion_winrate) = calculate_loss_and_win_rate(
+                            champion_stats.total_wins,
+                            champion_stats.total_matches,
+                        );
+                        view! {
+                            <div class="flex items-center space-x-1">
+                                <ImgChampion
+                                    champion=champion
+                                    parent_class="my-1 w-8 h-8 sprite-wrapper".to_string()
+                                    class="rounded-full self-scale-66 sprite-inner".to_string()
+                                />
+                                <div class="flex flex-col min-w-0">
+                                    <span class="truncate">{champion.label()}</span>
+                                    <span class="text-xs text-gray-400">
+                                        {champion_stats.total_matches}G
+                                        {format_float_to_2digits(champion_winrate.round())}%
+                                        {calculate_and_format_kda(
+                                            champion_stats.avg_kills,
+                                            champion_stats.avg_deaths,
+                                            champion_stats.avg_assists,
+                                        )}:1
+                                    </span>
+                                </div>
+                            </div>
+                        }
+                    })
+                    .collect::<Vec<_>>()}
+            </div>
+        </div>
+    }
+}
+
+#[derive(Clone, Default, Encode, Decode)]
+pub struct CompareResult {
+    pub summoners: Vec<CompareSummoner>,
+    /// Requested summoners that are not stored yet.
+    pub not_found: Vec<SummonerIdentifier>,
+}
+
+#[derive(Clone, Encode, Decode)]
+pub struct CompareSummoner {
+    pub summoner: Summoner,
+    pub stats: CompareSummonerStats,
+    /// Most played first.
+    pub roles: Vec<CompareRole>,
+    /// Results of the last 10 filtered games, newest first.
+    pub recent_form: Vec<bool>,
+    /// Most played first, at most `TOP_COMPARED_CHAMPIONS`.
+    pub champions: Vec<ChampionStats>,
+}
+
+#[derive(Clone, Default, Encode, Decode)]
+pub struct CompareSummonerStats {
+    pub total_matches: u16,
+    pub total_wins: u16,
+    pub avg_kills: f32,
+    pub avg_deaths: f32,
+    pub avg_assists: f32,
+    pub avg_kill_participation: u16,
+    pub avg_cs_per_minute: f32,
+    pub avg_damage_dealt_to_champions: u32,
+}
+
+#[derive(Clone, Encode, Decode)]
+pub struct CompareRole {
+    pub position: Position,
+    pub total_matches: u16,
+    pub total_wins: u16,
+}
@@ -1,3 +1,4 @@
+pub mod export_buttons;
 pub mod match_filters;
 pub mod pagination;
 
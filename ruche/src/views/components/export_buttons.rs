@@ -0,0 +1,67 @@
+use crate::app::SummonerIdentifier;
+use crate::utils::summoner_to_slug;
+use crate::views::BackEndMatchFiltersSearch;
+use leptos::prelude::*;
+use leptos::{component, view, IntoView};
+use reactive_stores::Store;
+
+/// Download links for the `/export` endpoints, following the current filters.
+#[component]
+pub fn ExportButtons(
+    summoner_identifier: Memo<SummonerIdentifier>,
+    /// `matches` or `champions`.
+    kind: &'static str,
+) -> impl IntoView {
+    let filters = expect_context::<Store<BackEndMatchFiltersSearch>>();
+    let href = move |format: &'static str| {
+        summoner_identifier.with(|identifier| export_url(identifier, kind, format, &filters.get()))
+    };
+
+    view! {
+        <div class="flex justify-end space-x-2 my-2 text-sm">
+            <a class="my-button" href=move || href("csv") rel="external" download>
+                Export CSV
+            </a>
+            <a class="my-button" href=move || href("ndjson") rel="external" download>
+                Export NDJSON
+            </a>
+        </div>
+    }
+}
+
+pub fn export_url(
+    identifier: &SummonerIdentifier,
+    kind: &str,
+    format: &str,
+    filters: &BackEndMatchFiltersSearch,
+) -> String {
+    let mut url = format!(
+        "/export/{}/{}/{}?format={}",
+        identifier.platform_route.code(),
+        summoner_to_slug(identifier.game_name.as_str(), identifier.tag_line.as_str()),
+        kind,
+        format
+    );
+    if let Some(champion_id) = filters.champion_id {
+        url.push_str(&format!("&champion_id={}", champion_id));
+    }
+    if let Some(queue_id) = filters.queue_id {
+        url.push_str(&format!("&queue_id={}", queue_id));
+    }
+    if let Some(patch_from) = filters.patch_from {
+        url.push_str(&format!("&patch={}", patch_from));
+    }
+    if let Some(patch_to) = filters.patch_to {
+        url.push_str(&format!("&patch_to={}", patch_to));
+    }
+    if let Some(start_date) = filters.start_date {
+        url.push_str(&format!("&start_date={}", start_date));
+    }
+    if let Some(end_date) = filters.end_date {
+        url.push_str(&format!("&end_date={}", end_date));
+    }
+    if let Some(adv) = filters.advanced_to_query() {
+        url.push_str(&format!("&adv={}", adv));
+    }
+    url
+}
@@ -1,21 +1,24 @@
 use crate::app::SummonerIdentifier;
 use crate::backend::server_fns::get_match_details::get_match_details;
-use crate::utils::{ProPlayerSlug, RiotMatchId, SSEVersions, SSEVersionsStoreFields};
+use crate::utils::{match_url, ProPlayerSlug, RiotMatchId, SSEVersions, SSEVersionsStoreFields};
 use bitcode::{Decode, Encode};
 use common::consts::platform_route::PlatformRoute;
-use leptos::either::{Either, EitherOf3};
+use leptos::either::{Either, EitherOf4};
 use leptos::prelude::*;
 use leptos::{component, view, IntoView};
+use leptos_router::components::A;
 
 use reactive_stores::Store;
 use std::fmt::Formatter;
 use std::sync::Arc;
 use crate::views::components::match_details::match_details_build::MatchDetailsBuild;
 use crate::views::components::match_details::match_details_overview::MatchDetailsOverview;
+use crate::views::components::match_details::match_details_pre_game::MatchDetailsPreGame;
 use crate::views::components::match_details::match_details_team::MatchDetailsTeam;
 
 pub mod match_details_build;
 pub mod match_details_overview;
+pub mod match_details_pre_game;
 pub mod match_details_team;
 
 #[component]
@@ -24,8 +27,10 @@ pub fn MatchDetails(
     riot_match_id: RiotMatchId,
     platform: PlatformRoute,
     in_encounter: bool,
+    #[prop(optional)] has_live_snapshot: bool,
 ) -> impl IntoView {
-    let summoner_identifier = expect_context::<Memo<SummonerIdentifier>>();
+    // absent on the standalone match page
+    let summoner_identifier = use_context::<Memo<SummonerIdentifier>>();
     let sse_version = expect_context::<Store<SSEVersions>>();
 
     let match_details = Resource::new_bitcode(
@@ -35,11 +40,11 @@ pub fn MatchDetails(
                 match_id,
                 riot_match_id,
                 platform,
-                summoner_identifier.get(),
+                summoner_identifier.map(|summoner_identifier| summoner_identifier.get()),
             )
         },
         |(_, match_id, riot_match_id, platform, summoner_identifier)| async move {
-            get_match_details(match_id, Some(summoner_identifier), platform, riot_match_id).await
+            get_match_details(match_id, summoner_identifier, platform, riot_match_id).await
         },
     );
     let (match_detail_tab, set_match_detail_tab) = signal(MatchDetailTabs::Overview);
@@ -83,6 +88,26 @@ pub fn MatchDetails(
                 >
                     Build
                 </button>
+                <Show when=move || has_live_snapshot>
+                    <button
+                        on:click=move |_| set_match_detail_tab(MatchDetailTabs::PreGame)
+                        class=move || {
+                            if matches!(match_detail_tab(), MatchDetailTabs::PreGame) {
+                                "active-tab"
+                            } else {
+                                "default-tab"
+                            }
+                        }
+                    >
+                        Pre-game
+                    </button>
+                </Show>
+                <A
+                    href=match_url(platform.code(), riot_match_id.as_ref())
+                    attr:class="default-tab ml-auto"
+                >
+                    Permalink
+                </A>
             </div>
             <div>
                 <Transition fallback=move || {
@@ -95,7 +120,7 @@ pub fn MatchDetails(
                                     let match_details = Arc::new(match_details);
                                     match &*match_detail_tab.read() {
                                         MatchDetailTabs::Overview => {
-                                            EitherOf3::A(
+                                            EitherOf4::A(
 
                                                 view! {
                                                     <MatchDetailsOverview
@@ -106,15 +131,20 @@ pub fn MatchDetails(
                                             )
                                         }
                                         MatchDetailTabs::Team => {
-                                            EitherOf3::B(
+                                            EitherOf4::B(
                                                 view! { <MatchDetailsTeam match_details=match_details /> },
                                             )
                                         }
                                         MatchDetailTabs::Build => {
-                                            EitherOf3::C(
+                                            EitherOf4::C(
                                                 view! { <MatchDetailsBuild match_details=match_details /> },
                                             )
                                         }
+                                        MatchDetailTabs::PreGame => {
+                                            EitherOf4::D(
+                                                view! { <MatchDetailsPreGame riot_match_id /> },
+                                            )
+                                        }
                                     }
                                 })
                             }
@@ -133,6 +163,7 @@ pub enum MatchDetailTabs {
     Overview,
     Team,
     Build,
+    PreGame,
 }
 
 #[derive(Clone, Encode, Decode)]
@@ -0,0 +1,42 @@
+use crate::backend::server_fns::get_live_game_snapshot::get_live_game_snapshot;
+use crate::utils::RiotMatchId;
+use crate::views::summoner_page::summoner_live_page::{LiveGameBans, MatchLiveTable};
+use leptos::either::Either;
+use leptos::prelude::*;
+use leptos::{component, view, IntoView};
+
+/// The live game as captured during loading screen / in game, before the outcome.
+#[component]
+pub fn MatchDetailsPreGame(riot_match_id: RiotMatchId) -> impl IntoView {
+    let snapshot = Resource::new_bitcode(
+        move || riot_match_id,
+        |riot_match_id| async move { get_live_game_snapshot(riot_match_id).await },
+    );
+    view! {
+        <Suspense fallback=move || {
+            view! { <div class="text-center">Loading Pre-game</div> }
+        }>
+            {move || Suspend::new(async move {
+                match snapshot.await {
+                    Ok(Some(game)) => {
+                        let (first_team, second_team): (Vec<_>, Vec<_>) = game
+                            .participants
+                            .iter()
+                            .cloned()
+                            .partition(|participant| participant.team_id == 100);
+                        Either::Left(
+                            view! {
+                                <div class="flex flex-col space-y-2">
+                                    <LiveGameBans bans=game.bans.clone() />
+                                    <MatchLiveTable team_id=100 participants=first_team />
+                                    <MatchLiveTable team_id=200 participants=second_team />
+                                </div>
+                            },
+                        )
+                    }
+                    _ => Either::Right(view! { <div class="text-center">No Pre-game Data</div> }),
+                }
+            })}
+        </Suspense>
+    }
+}
@@ -1,6 +1,12 @@
-use crate::views::{parse_date, BackEndMatchFiltersSearch, BackEndMatchFiltersSearchStoreFields};
+use crate::backend::server_fns::get_match_patches::get_match_patches;
+use crate::views::summoner_page::ActiveMatchFilters;
+use crate::views::{
+    parse_date, BackEndMatchFiltersSearch, BackEndMatchFiltersSearchStoreFields, Patch,
+};
 use common::consts::champion::CHAMPION_OPTIONS;
+use common::consts::perk::Perk;
 use common::consts::queue::Queue;
+use common::consts::summoner_spell::SummonerSpell;
 
 use leptos::prelude::*;
 
@@ -8,13 +14,27 @@ use leptos::{component, view, IntoView};
 use leptos_router::hooks::use_location;
 use reactive_stores::Store;
 
+/// `advanced` adds the item/rune/spell/teammate/opponent row, only honored by `get_matches`.
+/// A duo filter comes from an encounters page link and can only be cleared here.
 #[component]
-pub fn MatchFilters() -> impl IntoView {
+pub fn MatchFilters(#[prop(optional)] advanced: bool) -> impl IntoView {
     let filters = expect_context::<Store<BackEndMatchFiltersSearch>>();
+    if let Some(active_filters) = use_context::<ActiveMatchFilters>() {
+        active_filters.set(Some(filters));
+    }
     let location = use_location();
     Effect::new(move |_| {
         let _ = filters.champion_id().get();
         let _ = filters.queue_id().get();
+        let _ = filters.patch_from().get();
+        let _ = filters.patch_to().get();
+        let _ = filters.item_id().get();
+        let _ = filters.perk_id().get();
+        let _ = filters.summoner_spell1_id().get();
+        let _ = filters.summoner_spell2_id().get();
+        let _ = filters.teammate_champion_id().get();
+        let _ = filters.opponent_champion_id().get();
+        let _ = filters.teammate_summoner_id().get();
         let _ = filters.start_date().get();
         let _ = filters.end_date().get();
         filters.page().set(None);
@@ -26,6 +46,30 @@ pub fn MatchFilters() -> impl IntoView {
             parse_date(Some(v))
         }
     };
+    let patches = Resource::new_bitcode(|| (), |_| get_match_patches());
+    // the selects keep their value across sub-pages while the patches reload
+    let patch_options = move |selected: Option<Patch>| {
+        view! {
+            <Transition>
+                {move || Suspend::new(async move {
+                    patches
+                        .await
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|patch| {
+                            view! {
+                                <option value=patch.to_string() selected={selected == Some(patch)}>
+                                    {patch.to_string()}
+                                </option>
+                            }
+                        })
+                        .collect_view()
+                })}
+            </Transition>
+        }
+    };
+    let to_opt_patch = |v: String| Patch::parse(&v);
+    let to_opt_u32 = |v: String| v.parse::<u32>().ok().filter(|id| *id > 0);
     let to_opt_u16 = |v: String| {
         if v.is_empty() {
             None
@@ -83,6 +127,37 @@ pub fn MatchFilters() -> impl IntoView {
                         </select>
                     </div>
 
+                    <div class="flex flex-col">
+                        <label for="patch_from">Patch</label>
+                        <select
+                            class="my-select"
+                            name="patch_from"
+                            id="patch_from"
+                            on:change=move |e| {
+                                filters.patch_from().set(to_opt_patch(event_target_value(&e)))
+                            }
+                        >
+                            <option value="">All</option>
+                            {patch_options(filters.patch_from().get_untracked())}
+                        </select>
+                    </div>
+
+                    <div class="flex flex-col">
+                        <label for="patch_to">To Patch</label>
+                        <select
+                            class="my-select"
+                            name="patch_to"
+                            id="patch_to"
+                            prop:disabled=move || filters.patch_from().get().is_none()
+                            on:change=move |e| {
+                                filters.patch_to().set(to_opt_patch(event_target_value(&e)))
+                            }
+                        >
+                            <option value="">-</option>
+                            {patch_options(filters.patch_to().get_untracked())}
+                        </select>
+                    </div>
+
                     <div class="flex flex-col">
                         <label for="start_date">Start Date</label>
                         <input
@@ -117,6 +192,168 @@ pub fn MatchFilters() -> impl IntoView {
                         />
                     </div>
                 </div>
+                <Show when=move || advanced>
+                    <div class="flex text-left space-x-2 justify-center mt-2">
+                        <div class="flex flex-col">
+                            <label for="item_id">Item ID</label>
+                            <input
+                                class="my-input w-24"
+                                type="number"
+                                min="1"
+                                name="item_id"
+                                id="item_id"
+                                prop:value=filters
+                                    .item_id()
+                                    .get_untracked()
+                                    .map(|item_id| item_id.to_string())
+                                    .unwrap_or_default()
+                                on:change=move |e| {
+                                    filters.item_id().set(to_opt_u32(event_target_value(&e)))
+                                }
+                            />
+                        </div>
+
+                        <div class="flex flex-col">
+                            <label for="perk_id">Rune</label>
+                            <select
+                                class="my-select"
+                                name="perk_id"
+                                id="perk_id"
+                                prop:value=filters.perk_id().get_untracked()
+                                on:change=move |e| {
+                                    filters.perk_id().set(to_opt_u16(event_target_value(&e)))
+                                }
+                            >
+                                <option value="">All</option>
+                                <For each=Perk::options_runes key=|(id, _)| *id let:opt>
+                                    {
+                                        let (id, label) = opt;
+                                        view! { <option value=id>{label}</option> }
+                                    }
+                                </For>
+                            </select>
+                        </div>
+
+                        <div class="flex flex-col">
+                            <label for="summoner_spell1_id">Spells</label>
+                            <div class="flex space-x-1">
+                                <select
+                                    class="my-select"
+                                    name="summoner_spell1_id"
+                                    id="summoner_spell1_id"
+                                    prop:value=filters.summoner_spell1_id().get_untracked()
+                                    on:change=move |e| {
+                                        filters
+                                            .summoner_spell1_id()
+                                            .set(to_opt_u16(event_target_value(&e)))
+                                    }
+                                >
+                                    <option value="">All</option>
+                                    <For
+                                        each=SummonerSpell::options_all
+                                        key=|(id, _)| *id
+                                        let:opt
+                                    >
+                                        {
+                                            let (id, label) = opt;
+                                            view! { <option value=id>{label}</option> }
+                                        }
+                                    </For>
+                                </select>
+                                <select
+                                    class="my-select"
+                                    name="summoner_spell2_id"
+                                    id="summoner_spell2_id"
+                                    prop:value=filters.summoner_spell2_id().get_untracked()
+                                    on:change=move |e| {
+                                        filters
+                                            .summoner_spell2_id()
+                                            .set(to_opt_u16(event_target_value(&e)))
+                                    }
+                                >
+                                    <option value="">All</option>
+                                    <For
+                                        each=SummonerSpell::options_all
+                                        key=|(id, _)| *id
+                                        let:opt
+                                    >
+                                        {
+                                            let (id, label) = opt;
+                                            view! { <option value=id>{label}</option> }
+                                        }
+                                    </For>
+                                </select>
+                            </div>
+                        </div>
+
+                        <div class="flex flex-col">
+                            <label for="teammate_champion_id">With Champion</label>
+                            <select
+                                class="my-select"
+                                name="teammate_champion_id"
+                                id="teammate_champion_id"
+                                prop:value=filters.teammate_champion_id().get_untracked()
+                                on:change=move |e| {
+                                    filters
+                                        .teammate_champion_id()
+                                        .set(to_opt_u16(event_target_value(&e)))
+                                }
+                            >
+                                <option value="">All</option>
+                                <For
+                                    each=|| CHAMPION_OPTIONS.iter().cloned()
+                                    key=|(id, _)| *id
+                                    let:opt
+                                >
+                                    {
+                                        let (id, label) = opt;
+                                        view! { <option value=id>{label}</option> }
+                                    }
+                                </For>
+                            </select>
+                        </div>
+
+                        <div class="flex flex-col">
+                            <label for="opponent_champion_id">Against Champion</label>
+                            <select
+                                class="my-select"
+                                name="opponent_champion_id"
+                                id="opponent_champion_id"
+                                prop:value=filters.opponent_champion_id().get_untracked()
+                                on:change=move |e| {
+                                    filters
+                                        .opponent_champion_id()
+                                        .set(to_opt_u16(event_target_value(&e)))
+                                }
+                            >
+                                <option value="">All</option>
+                                <For
+                                    each=|| CHAMPION_OPTIONS.iter().cloned()
+                                    key=|(id, _)| *id
+                                    let:opt
+                                >
+                                    {
+                                        let (id, label) = opt;
+                                        view! { <option value=id>{label}</option> }
+                                    }
+                                </For>
+                            </select>
+                        </div>
+
+                        <Show when=move || filters.teammate_summoner_id().get().is_some()>
+                            <div class="flex flex-col">
+                                <span>Duo</span>
+                                <button
+                                    class="my-button"
+                                    title="Clear the duo filter"
+                                    on:click=move |_| filters.teammate_summoner_id().set(None)
+                                >
+                                    "Duo only ✕"
+                                </button>
+                            </div>
+                        </Show>
+                    </div>
+                </Show>
             </div>
         </div>
     }
@@ -1,5 +1,5 @@
-use crate::app::EncounterRouteParams;
-use crate::utils::{SSEVersions, SSEVersionsStoreFields};
+use crate::app::{EncounterRouteParams, SummonerIdentifier};
+use crate::utils::{compare_url, SSEVersions, SSEVersionsStoreFields};
 use leptos::prelude::*;
 use leptos::{component, view, IntoView};
 use leptos_router::components::A;
@@ -9,6 +9,7 @@ use reactive_stores::Store;
 #[component]
 pub fn SummonerNav() -> impl IntoView {
     let sse_in_live_game = expect_context::<Store<SSEVersions>>();
+    let summoner_identifier = expect_context::<Memo<SummonerIdentifier>>();
     let encounter_params = use_params::<EncounterRouteParams>();
     let is_encounter_route = move || {
         encounter_params
@@ -62,6 +63,14 @@ pub fn SummonerNav() -> impl IntoView {
                             </span>
                         </Show>
                     </li>
+                    <li>
+                        <A
+                            href=move || compare_url(&[summoner_identifier.get()])
+                            attr:class="tab"
+                        >
+                            "Compare"
+                        </A>
+                    </li>
                 </ul>
             </nav>
         </div>
@@ -0,0 +1,539 @@
+use crate::app::{
+    ChampionLeaderboardRouteParams, LeaderboardRouteParams, MetaStore, MetaStoreStoreFields,
+};
+use crate::backend::server_fns::get_champion_leaderboard::get_champion_leaderboard;
+use crate::backend::server_fns::get_leaderboard::get_leaderboard;
+use crate::utils::{
+    calculate_and_format_kda, calculate_loss_and_win_rate, champion_leaderboard_url,
+    format_float_to_2digits, leaderboard_url, summoner_url, ProPlayerSlug,
+};
+use crate::views::components::pagination::Pagination;
+use crate::views::summoner_page::summoner_live_page::LiveGameParticipantLeague;
+use crate::views::{
+    BackEndMatchFiltersSearch, BackEndMatchFiltersSearchStoreFields, ImgChampion, ImgSrc,
+    ProPlayerSlugView,
+};
+use bitcode::{Decode, Encode};
+use common::consts::champion::{Champion, CHAMPION_OPTIONS};
+use common::consts::platform_route::{PlatformRoute, PLATFORM_ROUTE_OPTIONS};
+use common::consts::profile_icon::ProfileIcon;
+use common::consts::queue::Queue;
+use common::consts::HasStaticSrcAsset;
+use leptos::either::Either;
+use leptos::prelude::codee::binary::BitcodeCodec;
+use leptos::prelude::*;
+use leptos::{component, view, IntoView};
+use leptos_router::components::A;
+use leptos_router::hooks::{use_navigate, use_params};
+use leptos_router::{lazy_route, LazyRoute};
+use reactive_stores::Store;
+
+/// Queues with a ladder, solo/duo first.
+pub const LEADERBOARD_QUEUES: [Queue; 2] = [
+    Queue::SummonersRift5v5RankedSolo,
+    Queue::SummonersRift5v5RankedFlex,
+];
+pub const LEADERBOARD_PAGE_SIZE: i64 = 50;
+/// Ranked solo/duo games needed on a champion to enter its leaderboard.
+pub const CHAMPION_LEADERBOARD_MIN_MATCHES: i64 = 20;
+/// Players kept per champion leaderboard.
+pub const CHAMPION_LEADERBOARD_SIZE: i64 = 100;
+
+pub struct LeaderboardRoute {
+    leaderboard_resource: Resource<Result<LeaderboardPage, ServerFnError>, BitcodeCodec>,
+    platform_route_memo: Memo<PlatformRoute>,
+    queue_id: RwSignal<u16>,
+    stored_only: RwSignal<bool>,
+    filters: Store<BackEndMatchFiltersSearch>,
+}
+
+#[lazy_route]
+impl LazyRoute for LeaderboardRoute {
+    fn data() -> Self {
+        let route_params = use_params::<LeaderboardRouteParams>();
+        let platform_route_memo = Memo::new(move |_| {
+            route_params
+                .read()
+                .as_ref()
+                .ok()
+                .and_then(|params| params.platform_route)
+                .unwrap_or_default()
+        });
+        let queue_id = RwSignal::new(Queue::SummonersRift5v5RankedSolo.id());
+        let stored_only = RwSignal::new(false);
+        // only the page is used, for <Pagination/>
+        let filters = Store::new(BackEndMatchFiltersSearch::default());
+
+        let leaderboard_resource = Resource::new_bitcode(
+            move || {
+                (
+                    platform_route_memo.get(),
+                    queue_id.get(),
+                    stored_only.get(),
+                    filters.page().get(),
+                )
+            },
+            |(platform_route, queue_id, stored_only, page)| async move {
+                get_leaderboard(platform_route, queue_id, stored_only, page).await
+            },
+        );
+        Self {
+            leaderboard_resource,
+            platform_route_memo,
+            queue_id,
+            stored_only,
+            filters,
+        }
+    }
+
+    fn view(this: Self) -> AnyView {
+        let LeaderboardRoute {
+            leaderboard_resource,
+            platform_route_memo,
+            queue_id,
+            stored_only,
+            filters,
+        } = this;
+        provide_context(filters);
+        let navigate = use_navigate();
+
+        let meta_store = expect_context::<reactive_stores::Store<MetaStore>>();
+        batch(|| {
+            let platform_route = platform_route_memo.get_untracked();
+            meta_store.title().set(format!(
+                "{} Ranked Leaderboard | Ruche",
+                platform_route.code()
+            ));
+            meta_store.description().set(format!(
+                "Challenger, Grandmaster and Master ladder of {} in ranked solo/duo and flex, alongside every player followed on Ruche.",
+                platform_route.code()
+            ));
+            meta_store.url().set(leaderboard_url(platform_route.code()));
+            meta_store
+                .image()
+                .set("https://ruche.lol/assets/logo.avif".to_string());
+        });
+
+        view! {
+            <div class="my-0 mx-auto max-w-5xl text-center">
+                <A href="/" attr:class="p-6 text-4xl my-4">
+                    "Welcome to Ruche"
+                </A>
+                <div class="my-card flex space-x-2 my-2 items-center justify-center">
+                    <select
+                        class="my-select"
+                        aria-label="Platform Route"
+                        prop:value=move || platform_route_memo.get().code()
+                        on:change=move |e| {
+                            filters.page().set(None);
+                            navigate(&leaderboard_url(&event_target_value(&e)), Default::default());
+                        }
+                    >
+                        {PLATFORM_ROUTE_OPTIONS
+                            .iter()
+                            .map(|pt| view! { <option value=pt.code()>{pt.code()}</option> })
+                            .collect::<Vec<_>>()}
+                    </select>
+                    <select
+                        class="my-select"
+                        aria-label="Queue"
+                        prop:value=move || queue_id.get()
+                        on:change=move |e| {
+                            filters.page().set(None);
+                            queue_id.set(event_target_value(&e).parse().unwrap_or_default());
+                        }
+                    >
+                        {LEADERBOARD_QUEUES
+                            .iter()
+                            .map(|queue| view! { <option value=queue.id()>{queue.label()}</option> })
+                            .collect::<Vec<_>>()}
+                    </select>
+                    <button
+                        class=move || if stored_only.get() { "default-tab" } else { "active-tab" }
+                        on:click=move |_| {
+                            filters.page().set(None);
+                            stored_only.set(false);
+                        }
+                    >
+                        Ladder
+                    </button>
+                    <button
+                        class=move || if stored_only.get() { "active-tab" } else { "default-tab" }
+                        on:click=move |_| {
+                            filters.page().set(None);
+                            stored_only.set(true);
+                        }
+                    >
+                        Ruche
+                    </button>
+                    <ChampionLeaderboardSelect champion_id=None />
+                </div>
+                <Transition fallback=move || {
+                    view! { <div class="text-center">Loading Leaderboard</div> }
+                }>
+                    {move || Suspend::new(async move {
+                        match leaderboard_resource.await {
+                            Ok(leaderboard) => {
+                                let total_pages = leaderboard.total_pages;
+                                if leaderboard.entries.is_empty() {
+                                    Ok(
+                                        Either::Left(
+                                            view! { <div class="text-center">No Players Found</div> },
+                                        ),
+                                    )
+                                } else {
+                                    Ok(
+                                        Either::Right(
+                                            view! {
+                                                <div class="flex my-card w-fit mx-auto">
+                                                    <table class="text-gray-200 space-y-2">
+                                                        <thead>
+                                                            <tr>
+                                                                <th class="px-2">#</th>
+                                                                <th class="text-left px-2">Summoner</th>
+                                                                <th class="px-2">Rank</th>
+                                                                <th class="px-2">Record</th>
+                                                            </tr>
+                                                        </thead>
+                                                        <tbody>
+                                                            {leaderboard
+                                                                .entries
+                                                                .into_iter()
+                                                                .map(|entry| {
+                                                                    view! {
+                                                                        <tr>
+                                                                            <td class="px-2">{entry.position}</td>
+                                                                            <td class="text-left px-2 w-[260px]">
+                                                                                <LeaderboardSummonerCell summoner=entry
+                                                                                    .summoner />
+                                                                            </td>
+                                                                            <td class="px-2">{entry.league.label()}</td>
+                                                                            <td class="px-2">
+                                                                                <LeagueRecord league=entry.league />
+                                                                            </td>
+                                                                        </tr>
+                                                                    }
+                                                                })
+                                                                .collect::<Vec<_>>()}
+                                                        </tbody>
+                                                    </table>
+                                                </div>
+                                                <Show when=move || (total_pages > 1)>
+                                                    <Pagination max_page=total_pages />
+                                                </Show>
+                                            },
+                                        ),
+                                    )
+                                }
+                            }
+                            Err(e) => Err(e),
+                        }
+                    })}
+                </Transition>
+            </div>
+        }
+        .into_any()
+    }
+}
+
+pub struct ChampionLeaderboardRoute {
+    leaderboard_resource: Resource<Result<ChampionLeaderboardPage, ServerFnError>, BitcodeCodec>,
+    champion_memo: Memo<Champion>,
+    filters: Store<BackEndMatchFiltersSearch>,
+}
+
+#[lazy_route]
+impl LazyRoute for ChampionLeaderboardRoute {
+    fn data() -> Self {
+        let route_params = use_params::<ChampionLeaderboardRouteParams>();
+        let champion_memo = Memo::new(move |_| {
+            route_params
+                .read()
+                .as_ref()
+                .ok()
+                .and_then(|params| params.champion_id)
+                .and_then(|champion_id| Champion::try_from(champion_id).ok())
+                .unwrap_or_default()
+        });
+        let filters = Store::new(BackEndMatchFiltersSearch::default());
+
+        let leaderboard_resource = Resource::new_bitcode(
+            move || (champion_memo.get(), filters.page().get()),
+            |(champion, page)| async move { get_champion_leaderboard(champion.into(), page).await },
+        );
+        Self {
+            leaderboard_resource,
+            champion_memo,
+            filters,
+        }
+    }
+
+    fn view(this: Self) -> AnyView {
+        let ChampionLeaderboardRoute {
+            leaderboard_resource,
+            champion_memo,
+            filters,
+        } = this;
+        provide_context(filters);
+
+        let meta_store = expect_context::<reactive_stores::Store<MetaStore>>();
+        batch(|| {
+            let champion = champion_memo.get_untracked();
+            meta_store
+                .title()
+                .set(format!("Best {} Players | Ruche", champion.label()));
+            meta_store.description().set(format!(
+                "Top {} players followed on Ruche, ranked by ranked solo/duo win rate over at least {} games.",
+                champion.label(),
+                CHAMPION_LEADERBOARD_MIN_MATCHES
+            ));
+            meta_store
+                .url()
+                .set(champion_leaderboard_url(champion.into()));
+            meta_store
+                .image()
+                .set("https://ruche.lol/assets/logo.avif".to_string());
+        });
+
+        view! {
+            <div class="my-0 mx-auto max-w-5xl text-center">
+                <A href="/" attr:class="p-6 text-4xl my-4">
+                    "Welcome to Ruche"
+                </A>
+                <div class="my-card flex space-x-2 my-2 items-center justify-center">
+                    {move || {
+                        let champion = champion_memo.get();
+                        view! {
+                            <ImgChampion
+                                champion
+                                parent_class="w-8 h-8 sprite-wrapper".to_string()
+                                class="rounded-full self-scale-66 sprite-inner".to_string()
+                            />
+                            <span class="text-xl">{champion.label()}</span>
+                        }
+                    }}
+                    <ChampionLeaderboardSelect champion_id=Some(champion_memo) />
+                </div>
+                <Transition fallback=move || {
+                    view! { <div class="text-center">Loading Leaderboard</div> }
+                }>
+                    {move || Suspend::new(async move {
+                        match leaderboard_resource.await {
+                            Ok(leaderboard) => {
+                                let total_pages = leaderboard.total_pages;
+                                if leaderboard.entries.is_empty() {
+                                    Ok(
+                                        Either::Left(
+                                            view! {
+                                                <div class="text-center">
+                                                    {format!(
+                                                        "No player has {} ranked solo/duo games on this champion yet",
+                                                        CHAMPION_LEADERBOARD_MIN_MATCHES,
+                                                    )}
+                                                </div>
+                                            },
+                                        ),
+                                    )
+                                } else {
+                                    Ok(
+                                        Either::Right(
+                                            view! {
+                                                <div class="flex my-card w-fit mx-auto">
+                                                    <table class="text-gray-200 space-y-2">
+                                                        <thead>
+                                                            <tr>
+                                                                <th class="px-2">#</th>
+                                                                <th class="text-left px-2">Summoner</th>
+                                                                <th class="px-2">Rank</th>
+                                                                <th class="px-2">Record</th>
+                                                                <th class="px-2">KDA</th>
+                                                            </tr>
+                                                        </thead>
+                                                        <tbody>
+                                                            {leaderboard
+                                                                .entries
+                                                                .into_iter()
+                                                                .map(|entry| {
+                                                                    let (losses, winrate) = calculate_loss_and_win_rate(
+                                                                        entry.total_wins,
+                                                                        entry.total_matches,
+                                                                    );
+                                                                    view! {
+                                                                        <tr>
+                                                                            <td class="px-2">{entry.position}</td>
+                                                                            <td class="text-left px-2 w-[260px]">
+                                                                                <LeaderboardSummonerCell summoner=Some(
+                                                                                    entry.summoner,
+                                                                                ) />
+                                                                            </td>
+                                                                            <td class="px-2">
+                                                                                {entry
+                                                                                    .league
+                                                                                    .map(|league| league.label())
+                                                                                    .unwrap_or_else(|| "Unranked".to_string())}
+                                                                            </td>
+                                                                            <td class="px-2">
+                                                                                {format!(
+                                                                                    "{}W {}L {}%",
+                                                                                    entry.total_wins,
+                                                                                    losses as u16,
+                                                                                    format_float_to_2digits(winrate),
+                                                                                )}
+                                                                            </td>
+                                                                            <td class="px-2 text-xs">
+                                                                                <div>
+                                                                                    {calculate_and_format_kda(
+                                                                                        entry.avg_kills,
+                                                                                        entry.avg_deaths,
+                                                                                        entry.avg_assists,
+                                                                                    )}:1
+                                                                                </div>
+                                                                                <div>
+                                                                                    {format!(
+                                                                                        "{}/{}/{}",
+                                                                                        format_float_to_2digits(entry.avg_kills),
+                                                                                        format_float_to_2digits(entry.avg_deaths),
+                                                                                        format_float_to_2digits(entry.avg_assists),
+                                                                                    )}
+                                                                                </div>
+                                                                            </td>
+                                                                        </tr>
+                                                                    }
+                                                                })
+                                                                .collect::<Vec<_>>()}
+                                                        </tbody>
+                                                    </table>
+                                                </div>
+                                                <Show when=move || (total_pages > 1)>
+                                                    <Pagination max_page=total_pages />
+                                                </Show>
+                                            },
+                                        ),
+                                    )
+                                }
+                            }
+                            Err(e) => Err(e),
+                        }
+                    })}
+                </Transition>
+            </div>
+        }
+        .into_any()
+    }
+}
+
+/// Jumps to a champion leaderboard; `None` outside of one.
+#[component]
+pub fn ChampionLeaderboardSelect(champion_id: Option<Memo<Champion>>) -> impl IntoView {
+    let navigate = use_navigate();
+    view! {
+        <select
+            class="my-select"
+            aria-label="Champion Leaderboard"
+            prop:value=move || {
+                champion_id
+                    .map(|champion| u16::from(champion.get()).to_string())
+                    .unwrap_or_default()
+            }
+            on:change=move |e| {
+                if let Ok(champion_id) = event_target_value(&e).parse::<u16>() {
+                    navigate(&champion_leaderboard_url(champion_id), Default::default());
+                }
+            }
+        >
+            <option value="">Champion leaderboards</option>
+            <For each=|| CHAMPION_OPTIONS.iter().cloned() key=|(id, _)| *id let:opt>
+                {
+                    let (id, label) = opt;
+                    view! { <option value=id>{label}</option> }
+                }
+            </For>
+        </select>
+    }
+}
+
+/// Apex players never searched on Ruche have no stored summoner to link to.
+#[component]
+pub fn LeaderboardSummonerCell(summoner: Option<LeaderboardSummoner>) -> impl IntoView {
+    match summoner {
+        Some(summoner) => {
+            let profile_icon = ProfileIcon(summoner.profile_icon_id);
+            Either::Left(view! {
+                <div class="flex items-center py-0.5">
+                    <ImgSrc
+                        alt=profile_icon.to_string()
+                        src=profile_icon.get_static_asset_url()
+                        class="w-8 h-8 rounded".to_string()
+                        height=32
+                        width=32
+                    />
+                    <A
+                        href=summoner_url(
+                            summoner.platform.code(),
+                            &summoner.game_name,
+                            &summoner.tag_line,
+                        )
+                        attr:class="ml-2 text-blue-300 hover:underline"
+                    >
+                        {format!("{}#{}", summoner.game_name, summoner.tag_line)}
+                    </A>
+                    <ProPlayerSlugView pro_player_slug=summoner.pro_slug small=true />
+                </div>
+            })
+        }
+        None => Either::Right(view! { <span class="text-gray-400">Not on Ruche yet</span> }),
+    }
+}
+
+#[component]
+pub fn LeagueRecord(league: LiveGameParticipantLeague) -> impl IntoView {
+    let (_, winrate) = calculate_loss_and_win_rate(league.wins, league.wins + league.losses);
+    format!(
+        "{}W {}L {}%",
+        league.wins,
+        league.losses,
+        format_float_to_2digits(winrate)
+    )
+}
+
+#[derive(Clone, Default, Encode, Decode)]
+pub struct LeaderboardPage {
+    pub total_pages: u16,
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+#[derive(Clone, Encode, Decode)]
+pub struct LeaderboardEntry {
+    /// 1-based rank on the whole ladder, not the page.
+    pub position: u32,
+    pub league: LiveGameParticipantLeague,
+    pub summoner: Option<LeaderboardSummoner>,
+}
+
+#[derive(Clone, Encode, Decode)]
+pub struct LeaderboardSummoner {
+    pub game_name: String,
+    pub tag_line: String,
+    pub platform: PlatformRoute,
+    pub profile_icon_id: u16,
+    pub pro_slug: Option<ProPlayerSlug>,
+}
+
+#[derive(Clone, Default, Encode, Decode)]
+pub struct ChampionLeaderboardPage {
+    pub total_pages: u16,
+    pub entries: Vec<ChampionLeaderboardEntry>,
+}
+
+#[derive(Clone, Encode, Decode)]
+pub struct ChampionLeaderboardEntry {
+    pub position: u16,
+    pub summoner: LeaderboardSummoner,
+    /// Current ranked solo/duo standing, when known.
+    pub league: Option<LiveGameParticipantLeague>,
+    pub total_matches: u16,
+    pub total_wins: u16,
+    pub avg_kills: f32,
+    pub avg_deaths: f32,
+    pub avg_assists: f32,
+}
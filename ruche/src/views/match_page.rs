@@ -0,0 +1,210 @@
+use crate::app::{MatchRouteParams, MetaStore, MetaStoreStoreFields, SITE_URL};
+use crate::backend::server_fns::get_match::get_match;
+use crate::utils::{match_url, og_match_image_url, DurationSince, RiotMatchId};
+use crate::views::components::match_details::MatchDetails;
+use crate::views::components::summoner_search_page::SummonerSearch;
+use crate::views::summoner_page::summoner_matches_page::MatchInfoCard;
+use bitcode::{Decode, Encode};
+use common::consts::champion::Champion;
+use common::consts::platform_route::PlatformRoute;
+use common::consts::queue::Queue;
+use itertools::Itertools;
+use leptos::either::Either;
+use leptos::prelude::codee::binary::BitcodeCodec;
+use leptos::prelude::*;
+use leptos::{component, view, IntoView};
+use leptos_router::components::A;
+use leptos_router::hooks::use_params;
+use leptos_router::{lazy_route, LazyRoute};
+
+pub struct MatchPageRoute {
+    match_resource: Resource<Result<MatchPage, ServerFnError>, BitcodeCodec>,
+}
+
+#[lazy_route]
+impl LazyRoute for MatchPageRoute {
+    fn data() -> Self {
+        let match_route_params = use_params::<MatchRouteParams>();
+        // blocking: title and OpenGraph tags have to be in the first chunk
+        let match_resource = Resource::new_bitcode_blocking(
+            move || {
+                match_route_params.get().ok().and_then(|params| {
+                    Some((
+                        params.platform_route?,
+                        RiotMatchId::new(&params.riot_match_id?),
+                    ))
+                })
+            },
+            |key| async move {
+                match key {
+                    Some((platform, riot_match_id)) => get_match(platform, riot_match_id).await,
+                    None => Err(ServerFnError::new("match not found")),
+                }
+            },
+        );
+        Self { match_resource }
+    }
+
+    fn view(this: Self) -> AnyView {
+        let MatchPageRoute { match_resource } = this;
+        let meta_store = expect_context::<reactive_stores::Store<MetaStore>>();
+
+        view! {
+            <div class="my-0 mx-auto max-w-5xl text-center">
+                <A href="/" attr:class="p-6 text-4xl my-4">
+                    "Welcome to Ruche"
+                </A>
+                <SummonerSearch is_summoner_page=false />
+                <div class="flex justify-center">
+                    <div class="w-[768px] my-2">
+                        <Transition fallback=move || {
+                            view! { <div class="text-center">Loading Match</div> }
+                        }>
+                            {move || Suspend::new(async move {
+                                match match_resource.await {
+                                    Ok(match_page) => {
+                                        batch(|| {
+                                            meta_store.title().set(match_page.title());
+                                            meta_store.description().set(match_page.description());
+                                            meta_store
+                                                .url()
+                                                .set(
+                                                    match_url(
+                                                        match_page.platform.code(),
+                                                        match_page.riot_match_id.as_ref(),
+                                                    ),
+                                                );
+                                            meta_store
+                                                .image()
+                                                .set(
+                                                    format!(
+                                                        "{}{}",
+                                                        SITE_URL,
+                                                        og_match_image_url(
+                                                            match_page.platform.code(),
+                                                            match_page.riot_match_id.as_ref(),
+                                                        ),
+                                                    ),
+                                                );
+                                        });
+                                        Either::Left(view! { <MatchPageHeader match_page /> })
+                                    }
+                                    Err(_) => {
+                                        Either::Right(
+                                            view! { <div class="text-center">Match Not Found</div> },
+                                        )
+                                    }
+                                }
+                            })}
+                        </Transition>
+                    </div>
+                </div>
+            </div>
+        }
+        .into_any()
+    }
+}
+
+#[component]
+pub fn MatchPageHeader(match_page: MatchPage) -> impl IntoView {
+    let team_line = |team_id: u16| {
+        match_page
+            .participants
+            .iter()
+            .filter(|p| p.team_id == team_id)
+            .map(|p| {
+                format!(
+                    "{}#{} ({}/{}/{})",
+                    p.game_name, p.tag_line, p.kills, p.deaths, p.assists
+                )
+            })
+            .join(", ")
+    };
+    let blue_team = team_line(100);
+    let red_team = team_line(200);
+
+    view! {
+        <div class="flex my-card space-x-4 text-left">
+            <MatchInfoCard
+                queue=match_page.queue
+                match_ended_since=match_page.match_ended_since
+                match_duration=match_page.match_duration
+            />
+            <div class="flex flex-col gap-1 text-sm">
+                <div>{match_page.riot_match_id.to_string()}</div>
+                <div class="text-gray-400">{format!("Patch {}", match_page.version)}</div>
+                <div class="text-blue-300">{blue_team}</div>
+                <div class="text-red-300">{red_team}</div>
+            </div>
+        </div>
+        <MatchDetails
+            match_id=match_page.match_id
+            riot_match_id=match_page.riot_match_id
+            platform=match_page.platform
+            in_encounter=false
+            has_live_snapshot=match_page.has_live_snapshot
+        />
+    }
+}
+
+#[derive(Clone, Encode, Decode)]
+pub struct MatchPage {
+    pub match_id: i32,
+    pub riot_match_id: RiotMatchId,
+    pub platform: PlatformRoute,
+    pub queue: Queue,
+    pub match_duration: Option<i32>,
+    pub match_ended_since: DurationSince,
+    pub version: String,
+    pub has_live_snapshot: bool,
+    /// Ordered by team, then by participant slot.
+    pub participants: Vec<MatchPageParticipant>,
+}
+
+#[derive(Clone, Encode, Decode)]
+pub struct MatchPageParticipant {
+    pub champion_id: u16,
+    pub team_id: u16,
+    pub won: bool,
+    pub kills: u16,
+    pub deaths: u16,
+    pub assists: u16,
+    pub game_name: String,
+    pub tag_line: String,
+}
+
+impl MatchPage {
+    fn team_champions(&self, won: bool) -> String {
+        self.participants
+            .iter()
+            .filter(|p| p.won == won)
+            .map(|p| {
+                Champion::try_from(p.champion_id)
+                    .map(|champion| champion.label())
+                    .unwrap_or("Unknown")
+            })
+            .join(", ")
+    }
+
+    pub fn title(&self) -> String {
+        format!(
+            "{} — {} Match Details | Ruche",
+            self.riot_match_id,
+            self.queue.label()
+        )
+    }
+
+    pub fn description(&self) -> String {
+        let minutes = self.match_duration.unwrap_or_default() / 60;
+        let seconds = self.match_duration.unwrap_or_default() % 60;
+        format!(
+            "{} on patch {}, {}m {:02}s. Winners: {}. Losers: {}. Full scoreboard, builds, runes and timelines.",
+            self.queue.label(),
+            self.version,
+            minutes,
+            seconds,
+            self.team_champions(true),
+            self.team_champions(false)
+        )
+    }
+}
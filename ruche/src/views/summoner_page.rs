@@ -1,10 +1,12 @@
 use crate::app::{
     to_summoner_identifier_memo, MetaStore, MetaStoreStoreFields, SummonerIdentifier,
-    SummonerRouteParams,
+    SummonerRouteParams, SITE_URL,
 };
 use crate::backend::server_fns::get_summoner::get_summoner;
 use crate::backend::server_fns::update_summoner::UpdateSummoner;
-use crate::utils::{summoner_url, ProPlayerSlug};
+use crate::utils::{
+    og_summoner_image_url, summoner_url, ProPlayerSlug, SSEVersions, SSEVersionsStoreFields,
+};
 use crate::views::{BackEndMatchFiltersSearch, ImgSrc, PendingLoading, ProPlayerSlugView};
 use bitcode::{Decode, Encode};
 use common::consts::platform_route::PlatformRoute;
@@ -36,6 +38,10 @@ pub struct SummonerPageRoute {
     pub summoner_identifier_memo: Memo<SummonerIdentifier>,
 }
 
+/// Filters of the tab being shown, registered by its `MatchFilters` so the update
+/// button syncs the queue the user is looking at.
+pub type ActiveMatchFilters = RwSignal<Option<Store<BackEndMatchFiltersSearch>>>;
+
 pub fn expect_filters() -> Store<BackEndMatchFiltersSearch>{
     #[cfg(feature = "ssr")]
     {
@@ -70,8 +76,11 @@ impl LazyRoute for SummonerPageRoute {
             summoner_identifier_memo,
         } = this;
         provide_context(summoner_identifier_memo);
+        let active_filters: ActiveMatchFilters = RwSignal::new(None);
+        provide_context(active_filters);
 
         let meta_store = expect_context::<reactive_stores::Store<MetaStore>>();
+        let sse_versions = expect_context::<Store<SSEVersions>>();
         view! {
             <div class="my-0 mx-auto max-w-5xl text-center">
                 <A href="/" attr:class="p-6 text-4xl my-4">
@@ -111,53 +120,57 @@ impl LazyRoute for SummonerPageRoute {
                                     });
                                     #[cfg(not(feature = "ssr"))]
                                     {
-                                        use reactive_stores::Store;
+                                        use crate::live_updates::summoner_events;
+                                        use crate::utils::SseEvent;
                                         use futures::StreamExt;
                                         use send_wrapper::SendWrapper;
-                                        use crate::utils::SSEVersions;
-                                        let summoner_sse_url = format!(
-                                            "/sse/match_updated/{}/{}",
+                                        let (mut events, live_updates) = summoner_events(
                                             summoner.platform,
                                             summoner.id,
                                         );
-                                        let mut source = SendWrapper::new(
-                                            gloo_net::eventsource::futures::EventSource::new(
-                                                    &summoner_sse_url,
-                                                )
-                                                .expect("couldn't connect to SSE stream"),
-                                        );
-                                        let s = ReadSignal::from_stream_unsync(
-                                            source
-                                                .subscribe("message")
-                                                .expect("couldn't subscribe to SSE stream")
-                                                .filter_map(|value| async move {
-                                                    value
-                                                        .ok()
-                                                        .and_then(|(_, ev)| ev.data().as_string())
-                                                        .and_then(|s| SSEVersions::parse(&s))
-                                                }),
-                                        );
-                                        on_cleanup(move || source.take().close());
-                                        let sse_versions = expect_context::<Store<SSEVersions>>();
-                                        Effect::watch(
-                                            move || s.get(),
-                                            move |new_ver, _, _| {
-                                                if let Some(ver) = *new_ver {
-                                                    let prev = sse_versions.get_untracked();
-                                                    if prev != ver {
-                                                        sse_versions.set(ver);
+                                        let live_updates = SendWrapper::new(live_updates);
+                                        on_cleanup(move || live_updates.close());
+                                        // one event at a time: a signal would only keep the last of a burst
+                                        leptos::task::spawn_local(async move {
+                                            while let Some(event) = events.next().await {
+                                                match event {
+                                                    SseEvent::ProfileChanged {
+                                                        summoner_level,
+                                                        profile_icon_id,
+                                                        ..
+                                                    } => {
+                                                        if summoner_level != level_signal.get_untracked() {
+                                                            set_level(summoner_level);
+                                                        }
+                                                        if profile_icon_id != profile_icon_signal.get_untracked() {
+                                                            set_profile_icon(profile_icon_id);
+                                                        }
                                                     }
+                                                    event => sse_versions.update(|v| v.apply(event)),
                                                 }
-                                            },
-                                            false,
-                                        );
+                                            }
+                                        });
                                     }
                                     meta_store
                                         .image()
                                         .set(
-                                            ProfileIcon(summoner.profile_icon_id).get_static_asset_url(),
+                                            format!(
+                                                "{}{}",
+                                                SITE_URL,
+                                                og_summoner_image_url(
+                                                    summoner.platform.code(),
+                                                    &summoner.game_name,
+                                                    &summoner.tag_line,
+                                                ),
+                                            ),
                                         );
 
+                                    let previous_names = summoner
+                                        .previous_names
+                                        .iter()
+                                        .map(|(game_name, tag_line)| format!("{}#{}", game_name, tag_line))
+                                        .collect::<Vec<_>>();
+
                                     // Mise à jour seulement si différent
 
                                     view! {
@@ -178,19 +191,49 @@ impl LazyRoute for SummonerPageRoute {
                                                         on:click=move |e| {
                                                             e.prevent_default();
                                                             pending.set(true);
+                                                            // the tab may be gone already, its filters with it
+                                                            let queue_id = active_filters
+                                                                .get_untracked()
+                                                                .and_then(|filters| filters.try_get_untracked())
+                                                                .and_then(|filters| filters.queue_id);
                                                             update_summoner_action
                                                                 .dispatch(UpdateSummoner {
                                                                     summoner_id: summoner.id,
                                                                     game_name: summoner.game_name.clone(),
                                                                     tag_line: summoner.tag_line.clone(),
                                                                     platform_route: summoner.platform,
+                                                                    queue_id,
+                                                                    // the filters have no match type, a queue already implies it
+                                                                    match_type: None,
                                                                 });
                                                         }
                                                     >
                                                         <PendingLoading pending>Update</PendingLoading>
                                                     </button>
+                                                    {move || {
+                                                        sse_versions
+                                                            .ingestion()
+                                                            .get()
+                                                            .filter(|(done, total)| done < total)
+                                                            .map(|(done, total)| {
+                                                                view! {
+                                                                    <div class="text-sm text-gray-400 mt-1">
+                                                                        {format!("Fetched {}/{} matches", done, total)}
+                                                                    </div>
+                                                                }
+                                                            })
+                                                    }}
 
                                                 </div>
+                                                {(!previous_names.is_empty())
+                                                    .then(|| {
+                                                        view! {
+                                                            <div class="text-sm text-gray-400 text-left self-center">
+                                                                "Previously known as "
+                                                                {previous_names.join(", ")}
+                                                            </div>
+                                                        }
+                                                    })}
                                             </div>
                                         </div>
                                     }
@@ -260,4 +303,6 @@ pub struct Summoner {
     pub profile_icon_id: u16,
     pub summoner_level: u16,
     pub platform: PlatformRoute,
+    /// Riot IDs used before the current one, most recent first.
+    pub previous_names: Vec<(String, String)>,
 }
@@ -18,6 +18,7 @@ use leptos::{component, view, IntoView};
 use leptos_router::hooks::use_params;
 use leptos_router::{lazy_route, LazyRoute};
 use reactive_stores::Store;
+use crate::views::components::export_buttons::ExportButtons;
 use crate::views::components::match_filters::MatchFilters;
 
 pub struct SummonerChampionsRoute {
@@ -92,6 +93,7 @@ impl LazyRoute for SummonerChampionsRoute {
         view! {
             <MatchFilters />
             <div>
+                <ExportButtons summoner_identifier=summoner_identifier_memo kind="champions" />
                 <Transition fallback=move || {
                     view! { <div class="text-center">Loading Champions</div> }
                 }>
@@ -171,6 +171,24 @@ impl LazyRoute for SummonerEncountersRoute {
                                                                             encounter.with_win_count,
                                                                             encounter.with_match_count,
                                                                         );
+                                                                        let identifier = summoner_identifier_memo.get_untracked();
+                                                                        let duo_matches_url = BackEndMatchFiltersSearch {
+                                                                            teammate_summoner_id: Some(encounter.id),
+                                                                            ..Default::default()
+                                                                        }
+                                                                            .advanced_to_query()
+                                                                            .map(|adv| {
+                                                                                format!(
+                                                                                    "{}?adv={}",
+                                                                                    summoner_url(
+                                                                                        identifier.platform_route.code(),
+                                                                                        &identifier.game_name,
+                                                                                        &identifier.tag_line,
+                                                                                    ),
+                                                                                    adv,
+                                                                                )
+                                                                            })
+                                                                            .unwrap_or_default();
                                                                         let summoner_encounter_url = summoner_encounter_url(
                                                                             encounter.platform.code(),
                                                                             &encounter.game_name,
@@ -206,13 +224,19 @@ impl LazyRoute for SummonerEncountersRoute {
                                                                                     </div>
                                                                                 </td>
                                                                                 <td class="px-2">
-                                                                                    {format!(
-                                                                                        "{}W {}L {}G {}%",
-                                                                                        encounter.with_win_count,
-                                                                                        with_losses as u16,
-                                                                                        encounter.with_match_count,
-                                                                                        format_float_to_2digits(with_winrate),
-                                                                                    )}
+                                                                                    <A
+                                                                                        href=duo_matches_url
+                                                                                        attr:class="hover:underline"
+                                                                                        attr:title="Matches played together"
+                                                                                    >
+                                                                                        {format!(
+                                                                                            "{}W {}L {}G {}%",
+                                                                                            encounter.with_win_count,
+                                                                                            with_losses as u16,
+                                                                                            encounter.with_match_count,
+                                                                                            format_float_to_2digits(with_winrate),
+                                                                                        )}
+                                                                                    </A>
                                                                                 </td>
                                                                                 <td class="px-2">
                                                                                     {format!(
@@ -5,7 +5,8 @@ use crate::app::{
 use crate::backend::server_fns::get_live_game::get_live_game;
 use crate::utils::{
     calculate_and_format_kda, calculate_loss_and_win_rate, format_float_to_2digits,
-    summoner_encounter_url, summoner_url, ProPlayerSlug, RiotMatchId, SSEVersions,
+    format_with_spaces, summoner_encounter_url, summoner_url, ProPlayerSlug, RiotMatchId,
+    SSEVersions, SSEVersionsStoreFields,
 };
 use crate::views::{ImgChampion, ImgPerk, ImgSummonerSpell, PendingLoading, ProPlayerSlugView};
 use bitcode::{Decode, Encode};
@@ -13,8 +14,10 @@ use common::consts::champion::Champion;
 use common::consts::map::Map;
 use common::consts::perk::Perk;
 use common::consts::platform_route::PlatformRoute;
+use common::consts::position::Position;
 use common::consts::queue::Queue;
 use common::consts::summoner_spell::SummonerSpell;
+use common::consts::tier::Tier;
 use leptos::either::Either;
 use leptos::prelude::codee::binary::BitcodeCodec;
 use leptos::prelude::*;
@@ -42,7 +45,7 @@ impl LazyRoute for SummonerLiveRoute {
         let live_game_resource = Resource::new_bitcode(
             move || {
                 (
-                    sse_versions.get(),
+                    sse_versions.live_ver().get(),
                     refresh_signal.get(),
                     summoner_identifier.get(),
                 )
@@ -120,14 +123,13 @@ impl LazyRoute for SummonerLiveRoute {
                                             <div class="flex space-x-2">
                                                 <div>{result.queue.label()}</div>
                                                 <div>{result.game_map.label()}</div>
-                                                <div>
-                                                    {format!(
-                                                        "{:02}:{:02}",
-                                                        result.game_length / 60,
-                                                        result.game_length % 60,
-                                                    )}
-                                                </div>
+                                                <div>{result.phase.label()}</div>
+                                                <LiveGameTimer
+                                                    game_start_time=result.game_start_time
+                                                    game_length=result.game_length
+                                                />
                                             </div>
+                                            <LiveGameBans bans=result.bans.clone() />
                                             <MatchLiveTable team_id=100 participants=first_team />
                                             <MatchLiveTable team_id=200 participants=second_team />
 
@@ -150,6 +152,75 @@ impl LazyRoute for SummonerLiveRoute {
     }
 }
 
+/// Elapsed time ticking from `game_start_time` once mounted; the spectator snapshot
+/// before that (server render, hydration) and until the game starts.
+#[component]
+pub fn LiveGameTimer(game_start_time: i64, game_length: u16) -> impl IntoView {
+    let now = RwSignal::new(None::<i64>);
+    #[cfg(not(feature = "ssr"))]
+    if game_start_time > 0 {
+        use crate::utils::now_millis;
+        // effects run after hydration, the first tick can't mismatch the server html
+        Effect::new(move |_| now.set(Some(now_millis())));
+        if let Ok(handle) = set_interval_with_handle(
+            move || now.set(Some(now_millis())),
+            std::time::Duration::from_secs(1),
+        ) {
+            on_cleanup(move || handle.clear());
+        }
+    }
+    move || {
+        let seconds = match now.get() {
+            Some(now) if game_start_time > 0 => (now - game_start_time).max(0) / 1000,
+            _ => game_length as i64,
+        };
+        format!("{:02}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+#[component]
+pub fn LiveGameBans(bans: Vec<LiveGameBan>) -> impl IntoView {
+    let team_bans = |team_id: u16| {
+        bans.iter()
+            .filter(|ban| ban.team_id == team_id && ban.champion_id > 0)
+            .map(|ban| {
+                view! {
+                    <ImgChampion
+                        champion=Champion::try_from(ban.champion_id).unwrap_or_default()
+                        class="self-scale-66 rounded-full block sprite-inner grayscale".to_string()
+                        parent_class="w-6 h-6 sprite-wrapper".to_string()
+                    />
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    (!bans.is_empty()).then(|| {
+        view! {
+            <div class="flex justify-between text-xs">
+                <div class="flex items-center gap-1">
+                    <span class="text-blue-800">Bans</span>
+                    {team_bans(100)}
+                </div>
+                <div class="flex items-center gap-1">
+                    {team_bans(200)}
+                    <span class="text-red-800">Bans</span>
+                </div>
+            </div>
+        }
+    })
+}
+
+const PREMADE_GROUP_CLASSES: [&str; 4] = [
+    "bg-amber-600",
+    "bg-fuchsia-700",
+    "bg-teal-700",
+    "bg-sky-700",
+];
+
+fn premade_group_class(group: u8) -> &'static str {
+    PREMADE_GROUP_CLASSES[(group.saturating_sub(1) as usize) % PREMADE_GROUP_CLASSES.len()]
+}
+
 #[component]
 pub fn MatchLiveTable(team_id: i32, participants: Vec<LiveGameParticipant>) -> impl IntoView {
     let is_blue_team = || team_id == 100;
@@ -161,7 +232,7 @@ pub fn MatchLiveTable(team_id: i32, participants: Vec<LiveGameParticipant>) -> i
                 <col width="16" />
                 <col width="15" />
                 <col />
-                <col width="32" />
+                <col width="44" />
                 <col width="132" />
                 <col width="124" />
                 <col width="100" />
@@ -178,7 +249,7 @@ pub fn MatchLiveTable(team_id: i32, participants: Vec<LiveGameParticipant>) -> i
                     </th>
                     <th class="text-left"></th>
                     <th></th>
-                    <th>S2024</th>
+                    <th>Rank</th>
                     <th>Ranked Stats</th>
                     <th colspan="2">Champion Stats</th>
 
@@ -245,6 +316,21 @@ pub fn MatchLiveTable(team_id: i32, participants: Vec<LiveGameParticipant>) -> i
                                 </td>
                                 <td class="pl-[5px] py-1 text-ellipsis overflow-hidden text-left">
                                     <div class="flex items-center gap-1">
+                                        {participant
+                                            .premade_group
+                                            .map(|group| {
+                                                view! {
+                                                    <span
+                                                        class=format!(
+                                                            "text-xs rounded px-0.5 text-center {}",
+                                                            premade_group_class(group),
+                                                        )
+                                                        title="Queued together in recent games"
+                                                    >
+                                                        {format!("P{}", group)}
+                                                    </span>
+                                                }
+                                            })}
                                         {(participant.encounter_count > 0)
                                             .then(|| {
                                                 view! {
@@ -280,9 +366,51 @@ pub fn MatchLiveTable(team_id: i32, participants: Vec<LiveGameParticipant>) -> i
                                     <span class="text-[11px]">
                                         Lvl. {participant.summoner_level}
                                     </span>
+                                    {participant
+                                        .mastery
+                                        .map(|mastery| {
+                                            view! {
+                                                <span class="text-[11px] ml-1">
+                                                    {format!(
+                                                        "M{} {}",
+                                                        mastery.level,
+                                                        format_with_spaces(mastery.points),
+                                                    )}
+                                                </span>
+                                            }
+                                        })}
+                                </td>
+                                <td class="py-1 text-[11px]">
+                                    {participant.main_role.map(|role| role.label())}
+                                </td>
+                                <td class="py-1">
+                                    {match participant.league {
+                                        Some(league) => {
+                                            Either::Left(
+                                                view! {
+                                                    <div>{league.label()}</div>
+                                                    <div>{league.wins}W {league.losses}L</div>
+                                                },
+                                            )
+                                        }
+                                        None => Either::Right(view! { <div>Unranked</div> }),
+                                    }}
+                                    <div class="flex justify-center gap-px mt-0.5">
+                                        {participant
+                                            .recent_form
+                                            .iter()
+                                            .map(|won| {
+                                                view! {
+                                                    <span
+                                                        class="w-1.5 h-1.5 rounded-sm"
+                                                        class=("bg-blue-500", *won)
+                                                        class=("bg-red-500", !*won)
+                                                    ></span>
+                                                }
+                                            })
+                                            .collect::<Vec<_>>()}
+                                    </div>
                                 </td>
-                                <td></td>
-                                <td></td>
                                 <td class="py-1">
                                     {match participant.ranked_stats {
                                         Some(ranked_stats) => {
@@ -373,13 +501,41 @@ pub fn MatchLiveTable(team_id: i32, participants: Vec<LiveGameParticipant>) -> i
 }
 #[derive(Clone, Encode, Decode)]
 pub struct LiveGame {
+    /// Spectator snapshot, only shown while `game_start_time` is unknown.
     pub game_length: u16,
+    /// Unix millis, 0 until the loading screen ends.
+    pub game_start_time: i64,
+    pub phase: LiveGamePhase,
+    /// In pick-turn order; `champion_id` 0 for a skipped ban.
+    pub bans: Vec<LiveGameBan>,
     pub game_map: Map,
     pub queue: Queue,
     pub game_id: RiotMatchId,
     pub participants: Vec<LiveGameParticipant>,
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Encode, Decode)]
+pub enum LiveGamePhase {
+    #[default]
+    Loading,
+    InProgress,
+}
+
+impl LiveGamePhase {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LiveGamePhase::Loading => "Loading",
+            LiveGamePhase::InProgress => "In Game",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct LiveGameBan {
+    pub champion_id: u16,
+    pub team_id: u16,
+}
+
 #[derive(Clone, Encode, Decode)]
 pub struct LiveGameParticipant {
     pub summoner_id: i32,
@@ -397,6 +553,16 @@ pub struct LiveGameParticipant {
     pub pro_player_slug: Option<ProPlayerSlug>,
     pub ranked_stats: Option<LiveGameParticipantRankedStats>,
     pub champion_stats: Option<LiveGameParticipantChampionStats>,
+    /// Current ranked solo/duo entry from the Riot API.
+    pub league: Option<LiveGameParticipantLeague>,
+    /// Mastery on the picked champion.
+    pub mastery: Option<LiveGameParticipantMastery>,
+    /// Results of the last stored games, newest first (at most 10).
+    pub recent_form: Vec<bool>,
+    /// Most played position over the last stored games.
+    pub main_role: Option<Position>,
+    /// Players of a team sharing a group queued together recently (1-based).
+    pub premade_group: Option<u8>,
 }
 
 #[derive(Clone, Default, Encode, Decode)]
@@ -405,6 +571,37 @@ pub struct LiveGameParticipantRankedStats {
     pub total_ranked_wins: u16,
 }
 
+#[derive(Clone, Copy, Encode, Decode)]
+pub struct LiveGameParticipantLeague {
+    pub tier: Tier,
+    /// 1..=4, 0 for apex tiers.
+    pub division: u8,
+    pub league_points: u16,
+    pub wins: u16,
+    pub losses: u16,
+}
+
+impl LiveGameParticipantLeague {
+    pub fn label(&self) -> String {
+        if self.tier.is_apex() || self.division == 0 {
+            format!("{} {} LP", self.tier.label(), self.league_points)
+        } else {
+            format!(
+                "{} {} {} LP",
+                self.tier.label(),
+                self.division,
+                self.league_points
+            )
+        }
+    }
+}
+
+#[derive(Clone, Copy, Encode, Decode)]
+pub struct LiveGameParticipantMastery {
+    pub level: u16,
+    pub points: u32,
+}
+
 #[derive(Clone, Default, Encode, Decode)]
 pub struct LiveGameParticipantChampionStats {
     pub total_champion_played: u16,
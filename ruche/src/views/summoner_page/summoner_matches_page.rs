@@ -1,6 +1,6 @@
 use crate::app::{
     to_summoner_identifier_memo, MetaStore, MetaStoreStoreFields, SummonerIdentifier,
-    SummonerRouteParams,
+    SummonerMatchesQuery, SummonerRouteParams,
 };
 use crate::backend::server_fns::get_matches::get_matches;
 use crate::utils::{
@@ -10,7 +10,8 @@ use crate::utils::{
 };
 use crate::views::components::pagination::Pagination;
 use crate::views::{
-    BackEndMatchFiltersSearch, ImgChampion, ImgItem, ImgPerk, ImgSummonerSpell, ProPlayerSlugView,
+    get_default_navigation_option, BackEndMatchFiltersSearch, BackEndMatchFiltersSearchStoreFields,
+    ImgChampion, ImgItem, ImgPerk, ImgSummonerSpell, ProPlayerSlugView,
 };
 use bitcode::{Decode, Encode};
 
@@ -21,20 +22,25 @@ use common::consts::perk::Perk;
 use common::consts::platform_route::PlatformRoute;
 use common::consts::queue::Queue;
 use common::consts::summoner_spell::SummonerSpell;
-use leptos::either::Either;
 use leptos::prelude::codee::binary::BitcodeCodec;
 use leptos::prelude::*;
 use leptos::{component, view, IntoView};
 use leptos_router::components::A;
-use leptos_router::hooks::use_params;
+use leptos_router::hooks::{use_navigate, use_params, use_query};
 use leptos_router::{lazy_route, LazyRoute};
 use reactive_stores::Store;
+use crate::views::components::export_buttons::ExportButtons;
 use crate::views::components::match_filters::MatchFilters;
 
 pub struct SummonerMatchesRoute {
     matches_resource: Resource<Result<GetSummonerMatchesResult, ServerFnError>, BitcodeCodec>,
     summoner_identifier_memo: Memo<SummonerIdentifier>,
-    filters:Store<BackEndMatchFiltersSearch>
+    filters:Store<BackEndMatchFiltersSearch>,
+    new_rows: RwSignal<Vec<SummonerMatch>>,
+    fresh_info: RwSignal<Option<MatchesResultInfo>>,
+    infinite_scroll: RwSignal<bool>,
+    more_rows: RwSignal<Vec<SummonerMatch>>,
+    more_cursor: RwSignal<Option<Option<MatchesCursor>>>,
 }
 
 #[lazy_route]
@@ -43,24 +49,85 @@ impl LazyRoute for SummonerMatchesRoute {
         let summoner_route_params = use_params::<SummonerRouteParams>();
         let summoner_identifier_memo = to_summoner_identifier_memo(summoner_route_params);
         let sse_versions = expect_context::<Store<SSEVersions>>();
-        let filters = Store::new(BackEndMatchFiltersSearch::default());
+        // advanced filters are shareable through `?adv=`
+        let advanced_query = use_query::<SummonerMatchesQuery>()
+            .read_untracked()
+            .as_ref()
+            .ok()
+            .and_then(|query| query.adv.clone());
+        let filters = Store::new(
+            advanced_query
+                .map(|adv| BackEndMatchFiltersSearch::default().with_advanced_query(&adv))
+                .unwrap_or_default(),
+        );
 
         let matches_resource = Resource::new_bitcode(
-            move || {
-                (
-                    sse_versions.match_ver().get(),
-                    filters.get(),
-                    summoner_identifier_memo.get(),
-                )
+            move || (filters.get(), summoner_identifier_memo.get()),
+            |(filters, summoner_identifier)| async move {
+                get_matches(summoner_identifier, Some(filters), None, None).await
+            },
+        );
+
+        // Rows announced over SSE, merged on top of the loaded page
+        let new_rows = RwSignal::new(Vec::<SummonerMatch>::new());
+        let fresh_info = RwSignal::new(None::<MatchesResultInfo>);
+        // Infinite scroll: rows loaded after the first page, and the cursor after them
+        // (`None` until a first chunk was loaded, the page's own cursor applies)
+        let infinite_scroll = RwSignal::new(false);
+        let more_rows = RwSignal::new(Vec::<SummonerMatch>::new());
+        let more_cursor = RwSignal::new(None::<Option<MatchesCursor>>);
+        Effect::watch(
+            move || (filters.get(), summoner_identifier_memo.get()),
+            move |_, _, _| {
+                new_rows.set(vec![]);
+                fresh_info.set(None);
+                more_rows.set(vec![]);
+                more_cursor.set(None);
             },
-            |(_, filters, summoner_identifier)| async move {
-                get_matches(summoner_identifier, Some(filters)).await
+            false,
+        );
+        Effect::watch(
+            move || sse_versions.match_ver().get(),
+            move |_, _, _| {
+                let match_ids = sse_versions.new_match_ids().get_untracked();
+                let filters = filters.get_untracked();
+                // resync, or new rows shifting every later page: reload instead
+                if match_ids.is_empty() || filters.page.unwrap_or(1) > 1 {
+                    new_rows.set(vec![]);
+                    fresh_info.set(None);
+                    more_rows.set(vec![]);
+                    more_cursor.set(None);
+                    matches_resource.refetch();
+                    return;
+                }
+                let summoner_identifier = summoner_identifier_memo.get_untracked();
+                leptos::task::spawn_local(async move {
+                    if let Ok(result) =
+                        get_matches(summoner_identifier, Some(filters), Some(match_ids), None).await
+                    {
+                        new_rows.update(|rows| {
+                            *rows = merge_new_matches(
+                                result.matches,
+                                std::mem::take(rows),
+                                usize::MAX,
+                                false,
+                            )
+                        });
+                        fresh_info.set(Some(result.matches_result_info));
+                    }
+                });
             },
+            false,
         );
         Self {
             matches_resource,
             summoner_identifier_memo,
-            filters
+            filters,
+            new_rows,
+            fresh_info,
+            infinite_scroll,
+            more_rows,
+            more_cursor,
         }
     }
 
@@ -68,7 +135,12 @@ impl LazyRoute for SummonerMatchesRoute {
         let SummonerMatchesRoute {
             matches_resource,
             summoner_identifier_memo,
-            filters
+            filters,
+            new_rows,
+            fresh_info,
+            infinite_scroll,
+            more_rows,
+            more_cursor,
         } = this;
         provide_context(filters);
         let meta_store = expect_context::<reactive_stores::Store<MetaStore>>();
@@ -84,10 +156,105 @@ impl LazyRoute for SummonerMatchesRoute {
             ));
             meta_store.url().set(me.matches_route());
         });
+        let navigate = use_navigate();
+        let advanced_query = Memo::new(move |_| filters.read().advanced_to_query());
+        Effect::watch(
+            move || advanced_query.get(),
+            move |advanced_query, _, _| {
+                let matches_route = summoner_identifier_memo.read_untracked().matches_route();
+                let url = match advanced_query {
+                    Some(adv) => format!("{}?adv={}", matches_route, adv),
+                    None => matches_route,
+                };
+                navigate(&url, get_default_navigation_option());
+            },
+            false,
+        );
+
+        let base_cursor = Memo::new(move |_| {
+            matches_resource.with(|result| {
+                result
+                    .as_ref()
+                    .and_then(|result| result.as_ref().ok())
+                    .and_then(|result| result.next_cursor)
+            })
+        });
+        let next_cursor = move || more_cursor.get().unwrap_or_else(|| base_cursor.get());
+        let loading_more = RwSignal::new(false);
+        let load_more = move || {
+            let Some(cursor) = more_cursor
+                .get_untracked()
+                .unwrap_or_else(|| base_cursor.get_untracked())
+            else {
+                return;
+            };
+            if loading_more.get_untracked() {
+                return;
+            }
+            loading_more.set(true);
+            let requested_filters = filters.get_untracked();
+            let summoner_identifier = summoner_identifier_memo.get_untracked();
+            leptos::task::spawn_local(async move {
+                let result = get_matches(
+                    summoner_identifier,
+                    Some(requested_filters),
+                    None,
+                    Some(cursor),
+                )
+                .await;
+                // the filters changed meanwhile: these rows belong to another list
+                if filters.get_untracked() == requested_filters {
+                    if let Ok(result) = result {
+                        more_rows.update(|rows| rows.extend(result.matches));
+                        more_cursor.set(Some(result.next_cursor));
+                    }
+                }
+                loading_more.set(false);
+            });
+        };
+        #[cfg(not(feature = "ssr"))]
+        {
+            let handle = window_event_listener(leptos::ev::scroll, move |_| {
+                if !infinite_scroll.get_untracked() {
+                    return;
+                }
+                let viewport_bottom = window().scroll_y().unwrap_or_default()
+                    + window()
+                        .inner_height()
+                        .ok()
+                        .and_then(|height| height.as_f64())
+                        .unwrap_or_default();
+                let page_height = document()
+                    .document_element()
+                    .map(|element| element.scroll_height())
+                    .unwrap_or_default() as f64;
+                // about a screen ahead
+                if page_height - viewport_bottom < 800.0 {
+                    load_more();
+                }
+            });
+            on_cleanup(move || handle.remove());
+        }
 
         view! {
-            <MatchFilters />
+            <MatchFilters advanced=true />
             <div class="w-[768px] inline-block align-top justify-center">
+                <div class="flex justify-between items-center">
+                    <button
+                        class=move || {
+                            if infinite_scroll.get() { "active-tab" } else { "default-tab" }
+                        }
+                        on:click=move |_| {
+                            infinite_scroll.update(|enabled| *enabled = !*enabled);
+                            if filters.page().get_untracked().is_some() {
+                                filters.page().set(None);
+                            }
+                        }
+                    >
+                        Infinite Scroll
+                    </button>
+                    <ExportButtons summoner_identifier=summoner_identifier_memo kind="matches" />
+                </div>
                 <div class="">
                     <Transition fallback=move || {
                         view! { <div class="text-center">Loading Matches</div> }
@@ -96,70 +263,105 @@ impl LazyRoute for SummonerMatchesRoute {
                             match matches_resource.await {
                                 Ok(matches_result) => {
                                     let total_pages = matches_result.total_pages;
-                                    if matches_result.matches.is_empty() {
-                                        Ok(
-                                            Either::Left(
-                                                view! { <div class="text-center">No Matches Found</div> },
-                                            ),
-                                        )
-                                    } else {
-                                        Ok(
-                                            Either::Right({
-                                                let (losses, winrate) = calculate_loss_and_win_rate(
-                                                    matches_result.matches_result_info.total_wins,
-                                                    matches_result.matches_result_info.total_matches,
-                                                );
-                                                view! {
-                                                    <div class="my-2 flex my-card w-fit">
-                                                        <div class="flex flex-col">
-                                                            <div>
-                                                                {matches_result.matches_result_info.total_matches}G
-                                                                {matches_result.matches_result_info.total_wins}W
-                                                                {losses as u16}L
-                                                            </div>
-                                                            <div>{format_float_to_2digits(winrate)}%</div>
-                                                        </div>
-                                                        <div class="flex flex-col ml-2">
-                                                            <div>
-                                                                {format!(
-                                                                    "{:.2}/{:.2}/{:.2}",
-                                                                    matches_result.matches_result_info.avg_kills,
-                                                                    matches_result.matches_result_info.avg_deaths,
-                                                                    matches_result.matches_result_info.avg_assists,
-                                                                )}
-                                                            </div>
-                                                            <div>
-                                                                {calculate_and_format_kda(
-                                                                    matches_result.matches_result_info.avg_kills,
-                                                                    matches_result.matches_result_info.avg_deaths,
-                                                                    matches_result.matches_result_info.avg_assists,
-                                                                )}:1
+                                    let base_matches = matches_result.matches;
+                                    let base_info = matches_result.matches_result_info;
+                                    let rows = Signal::derive(move || {
+                                        if infinite_scroll.get() {
+                                            let mut rows = base_matches.clone();
+                                            rows.extend(more_rows.get());
+                                            let has_more = next_cursor().is_some();
+                                            merge_new_matches(new_rows.get(), rows, usize::MAX, has_more)
+                                        } else {
+                                            let has_more = total_pages > 1;
+                                            merge_new_matches(
+                                                new_rows.get(),
+                                                base_matches.clone(),
+                                                20,
+                                                has_more,
+                                            )
+                                        }
+                                    });
+                                    let info = Signal::derive(move || {
+                                        fresh_info.get().unwrap_or_else(|| base_info.clone())
+                                    });
+                                    Ok(
+                                        view! {
+                                            <Show
+                                                when=move || rows.with(|rows| !rows.is_empty())
+                                                fallback=|| {
+                                                    view! { <div class="text-center">No Matches Found</div> }
+                                                }
+                                            >
+                                                {move || {
+                                                    let info = info.get();
+                                                    let (losses, winrate) = calculate_loss_and_win_rate(
+                                                        info.total_wins as f32,
+                                                        info.total_matches as f32,
+                                                    );
+                                                    view! {
+                                                        <div class="my-2 flex my-card w-fit">
+                                                            <div class="flex flex-col">
+                                                                <div>
+                                                                    {info.total_matches}G
+                                                                    {info.total_wins}W
+                                                                    {losses as u32}L
+                                                                </div>
+                                                                <div>{format_float_to_2digits(winrate)}%</div>
                                                             </div>
-                                                            <div>
-                                                                P/kill
-                                                                {format!(
-                                                                    "{:.2}",
-                                                                    matches_result.matches_result_info.avg_kill_participation,
-                                                                )}%
+                                                            <div class="flex flex-col ml-2">
+                                                                <div>
+                                                                    {format!(
+                                                                        "{:.2}/{:.2}/{:.2}",
+                                                                        info.avg_kills,
+                                                                        info.avg_deaths,
+                                                                        info.avg_assists,
+                                                                    )}
+                                                                </div>
+                                                                <div>
+                                                                    {calculate_and_format_kda(
+                                                                        info.avg_kills,
+                                                                        info.avg_deaths,
+                                                                        info.avg_assists,
+                                                                    )}:1
+                                                                </div>
+                                                                <div>
+                                                                    P/kill
+                                                                    {format!("{:.2}", info.avg_kill_participation)}%
+                                                                </div>
                                                             </div>
                                                         </div>
-                                                    </div>
-                                                    <div class="text-gray-200 space-y-2">
-                                                        <For
-                                                            each=move || matches_result.matches.clone()
-                                                            key=|match_| match_.match_id
-                                                            let:match_
-                                                        >
-                                                            <MatchCard match_=match_ />
-                                                        </For>
-                                                    </div>
-                                                    <Show when=move || (total_pages > 1)>
-                                                        <Pagination max_page=total_pages />
-                                                    </Show>
-                                                }
-                                            }),
-                                        )
-                                    }
+                                                    }
+                                                }}
+                                                <div class="text-gray-200 space-y-2">
+                                                    <For
+                                                        each=move || rows.get()
+                                                        key=|match_| match_.match_id
+                                                        let:match_
+                                                    >
+                                                        <MatchCard match_=match_ />
+                                                    </For>
+                                                </div>
+                                                <Show when=move || {
+                                                    !infinite_scroll.get() && total_pages > 1
+                                                }>
+                                                    <Pagination max_page=total_pages />
+                                                </Show>
+                                                <Show when=move || {
+                                                    infinite_scroll.get() && next_cursor().is_some()
+                                                }>
+                                                    <button
+                                                        class="my-button mt-4"
+                                                        disabled=move || loading_more.get()
+                                                        on:click=move |_| load_more()
+                                                    >
+                                                        {move || {
+                                                            if loading_more.get() { "Loading..." } else { "Load More" }
+                                                        }}
+                                                    </button>
+                                                </Show>
+                                            </Show>
+                                        },
+                                    )
                                 }
                                 Err(e) => Err(e),
                             }
@@ -171,6 +373,29 @@ impl LazyRoute for SummonerMatchesRoute {
     }
 }
 
+/// Merges `new_rows` into `base` by position, newest first, keeping at most `max_rows`.
+/// When more history follows `base`, rows older than its last one are dropped: backfilled
+/// games belong to a later page.
+fn merge_new_matches(
+    new_rows: Vec<SummonerMatch>,
+    mut base: Vec<SummonerMatch>,
+    max_rows: usize,
+    has_more: bool,
+) -> Vec<SummonerMatch> {
+    let oldest = base.last().filter(|_| has_more).map(|m| m.position);
+    for match_ in new_rows {
+        if oldest.is_some_and(|oldest| match_.position < oldest)
+            || base.iter().any(|m| m.match_id == match_.match_id)
+        {
+            continue;
+        }
+        base.push(match_);
+    }
+    base.sort_by(|a, b| b.position.cmp(&a.position));
+    base.truncate(max_rows);
+    base
+}
+
 #[component]
 pub fn MatchCard(match_: SummonerMatch) -> impl IntoView {
     let (show_details, set_show_details) = signal(false);
@@ -336,6 +561,7 @@ pub fn MatchCard(match_: SummonerMatch) -> impl IntoView {
                     riot_match_id=match_.riot_match_id
                     platform=match_.platform
                     in_encounter=false
+                    has_live_snapshot=match_.has_live_snapshot
                 />
             </Show>
         </div>
@@ -477,6 +703,8 @@ pub struct GetSummonerMatchesResult {
     pub total_pages: u16,
     pub matches: Vec<SummonerMatch>,
     pub matches_result_info: MatchesResultInfo,
+    /// Position after the last row, `None` once the history is exhausted.
+    pub next_cursor: Option<MatchesCursor>,
 }
 
 #[derive(Clone, Default, Encode, Decode)]
@@ -485,8 +713,51 @@ pub struct MatchesResultInfo {
     pub avg_deaths: f32,
     pub avg_assists: f32,
     pub avg_kill_participation: u16,
-    pub total_matches: u16,
-    pub total_wins: u16,
+    pub total_matches: u32,
+    pub total_wins: u32,
+}
+
+/// Keyset position `(match_end, lol_match_id)` of a participant row, opaque outside
+/// of `fetch_matches`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Encode, Decode)]
+pub struct MatchesCursor {
+    match_end_micros: i64,
+    lol_match_id: i32,
+}
+
+impl MatchesCursor {
+    #[cfg(feature = "ssr")]
+    pub fn new(match_end: chrono::NaiveDateTime, lol_match_id: i32) -> Self {
+        MatchesCursor {
+            match_end_micros: match_end.and_utc().timestamp_micros(),
+            lol_match_id,
+        }
+    }
+
+    #[cfg(feature = "ssr")]
+    pub fn match_end(&self) -> chrono::NaiveDateTime {
+        chrono::DateTime::from_timestamp_micros(self.match_end_micros)
+            .unwrap_or_default()
+            .naive_utc()
+    }
+
+    pub fn lol_match_id(&self) -> i32 {
+        self.lol_match_id
+    }
+
+    /// URL safe form for the public API.
+    pub fn to_token(&self) -> String {
+        use base64::Engine;
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bitcode::encode(self))
+    }
+
+    pub fn from_token(token: &str) -> Option<Self> {
+        use base64::Engine;
+        let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(token.trim())
+            .ok()?;
+        bitcode::decode(&bytes).ok()
+    }
 }
 #[derive(Clone, Encode, Decode)]
 pub struct SummonerMatch {
@@ -516,6 +787,10 @@ pub struct SummonerMatch {
     pub won: bool,
     pub queue: Queue,
     pub platform: PlatformRoute,
+    /// A pre-game `LiveGame` was captured for this match.
+    pub has_live_snapshot: bool,
+    /// Sort key of the history, newest first.
+    pub position: MatchesCursor,
 }
 
 #[derive(Clone, Encode, Decode)]
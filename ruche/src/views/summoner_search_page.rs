@@ -1,4 +1,6 @@
 use crate::app::{MetaStore, MetaStoreStoreFields};
+use crate::utils::leaderboard_url;
+use common::consts::platform_route::PlatformRoute;
 use leptos::prelude::*;
 use leptos::{component, view, IntoView};
 use leptos_router::components::A;
@@ -29,6 +31,14 @@ pub fn SummonerSearchPage() -> impl IntoView {
             </A>
             <img src="/assets/logo.avif" class="w-[420px] h-[420px] mx-auto" />
             <SummonerSearch is_summoner_page=false />
+            <div class="flex justify-center space-x-2 my-2">
+                <A href=leaderboard_url(PlatformRoute::default().code()) attr:class="my-button">
+                    Leaderboards
+                </A>
+                <A href="/champions" attr:class="my-button">
+                    Champions
+                </A>
+            </div>
         </div>
     }
 }
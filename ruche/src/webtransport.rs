@@ -0,0 +1,134 @@
+use crate::sse::multiplex_events;
+use crate::ssr::AppState;
+use crate::utils::WEBTRANSPORT_PATH;
+use axum::body::Body;
+use axum::{BoxError, Router};
+use bytes::{Buf, Bytes};
+use futures::StreamExt;
+use h3::ext::Protocol;
+use h3::server::RequestStream;
+use h3_webtransport::server::WebTransportSession;
+use http::Method;
+use leptos::logging::log;
+use tokio::io::AsyncWriteExt;
+use tower::ServiceExt;
+
+type H3Stream = RequestStream<h3_quinn::BidiStream<Bytes>, Bytes>;
+type H3RecvStream = RequestStream<h3_quinn::RecvStream, Bytes>;
+type H3Session = WebTransportSession<h3_quinn::Connection, Bytes>;
+
+/// HTTP/3 server on the QUIC endpoint: WebTransport CONNECTs on
+/// `WEBTRANSPORT_PATH` open a hub session, every other request goes to `app`.
+pub async fn serve_h3(endpoint: quinn::Endpoint, app: Router, state: AppState) {
+    while let Some(incoming) = endpoint.accept().await {
+        let app = app.clone();
+        let state = state.clone();
+        tokio::spawn(async move {
+            let result = match incoming.await {
+                Ok(conn) => handle_connection(conn, app, state).await,
+                Err(e) => Err(e.into()),
+            };
+            if let Err(e) = result {
+                log!("H3 connection closed with error: {}", e);
+            }
+        });
+    }
+}
+
+async fn handle_connection(
+    conn: quinn::Connection,
+    app: Router,
+    state: AppState,
+) -> Result<(), BoxError> {
+    let mut h3_conn = h3::server::builder()
+        .enable_webtransport(true)
+        .enable_extended_connect(true)
+        .enable_datagram(true)
+        .max_webtransport_sessions(1)
+        .send_grease(true)
+        .build(h3_quinn::Connection::new(conn))
+        .await?;
+
+    loop {
+        let Some(resolver) = h3_conn.accept().await? else {
+            return Ok(());
+        };
+        let (req, stream) = resolver.resolve_request().await?;
+        let is_webtransport = req.method() == Method::CONNECT
+            && req.extensions().get::<Protocol>() == Some(&Protocol::WEB_TRANSPORT)
+            && req.uri().path() == WEBTRANSPORT_PATH;
+        if is_webtransport {
+            // the session takes over the connection
+            let session = WebTransportSession::accept(req, stream, h3_conn).await?;
+            return serve_session(session, state).await;
+        }
+        let app = app.clone();
+        tokio::spawn(async move {
+            if let Err(e) = serve_request(app, req, stream).await {
+                log!("H3 request failed: {}", e);
+            }
+        });
+    }
+}
+
+/// Forwards one request to `app`, both bodies streamed as they come: uploads are
+/// not buffered and SSE works over h3 too.
+async fn serve_request(
+    app: Router,
+    req: http::Request<()>,
+    stream: H3Stream,
+) -> Result<(), BoxError> {
+    let (mut send, recv) = stream.split();
+    let req = req.map(|_| Body::from_stream(request_body(recv)));
+    let response = match app.oneshot(req).await {
+        Ok(response) => response,
+        Err(never) => match never {},
+    };
+    let (parts, body) = response.into_parts();
+    send.send_response(http::Response::from_parts(parts, ()))
+        .await?;
+    let mut data = body.into_data_stream();
+    while let Some(chunk) = data.next().await {
+        send.send_data(chunk?).await?;
+    }
+    send.finish().await?;
+    Ok(())
+}
+
+/// Data frames of the request, ending after the first error.
+fn request_body(
+    recv: H3RecvStream,
+) -> impl futures::Stream<Item = Result<Bytes, h3::error::StreamError>> + Send + 'static {
+    futures::stream::unfold(Some(recv), |recv| async move {
+        let mut recv = recv?;
+        match recv.recv_data().await {
+            Ok(Some(mut chunk)) => Some((Ok(chunk.copy_to_bytes(chunk.remaining())), Some(recv))),
+            Ok(None) => None,
+            Err(e) => Some((Err(e), None)),
+        }
+    })
+}
+
+/// Writes `SseMultiplexEvent`s on one server-opened uni stream, each frame being
+/// a little-endian `u32` length followed by the bitcode bytes.
+async fn serve_session(session: H3Session, state: AppState) -> Result<(), BoxError> {
+    let mut send = session.open_uni(session.session_id()).await?;
+    let mut events = Box::pin(multiplex_events(state));
+    loop {
+        tokio::select! {
+            event = events.next() => {
+                let Some(event) = event else { break };
+                let bytes = bitcode::encode(&event);
+                send.write_all(&(bytes.len() as u32).to_le_bytes()).await?;
+                send.write_all(&bytes).await?;
+            }
+            // the client never opens streams: anything else means the session is gone
+            accepted = session.accept_uni() => {
+                if !matches!(accepted, Ok(Some(_))) {
+                    break;
+                }
+            }
+        }
+    }
+    Ok(())
+}